@@ -0,0 +1,497 @@
+// Import necessary items from the near_sdk crate
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, near, serde_json, AccountId};
+
+use crate::{Certification, EventStatus};
+
+// Name and version of the NEP-297 standard every log of this contract is emitted under
+const STANDARD: &str = "charles_contract";
+const VERSION: &str = "1.0.0";
+
+// Implemented by every log struct, naming the NEP-297 `event` field it is emitted with
+pub trait ContractLog: Serialize {
+    const EVENT: &'static str;
+}
+
+// Define the struct Envelope, the standard NEP-297 wrapper around a single log
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Envelope<'a, T: ContractLog> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'static str,
+    data: [&'a T; 1],
+}
+
+// Emit a log as `EVENT_JSON:{...}`; contract methods must not log through anything else
+pub fn emit<T: ContractLog>(log: &T) {
+    let envelope = Envelope {
+        standard: STANDARD,
+        version: VERSION,
+        event: T::EVENT,
+        data: [log],
+    };
+    let json = serde_json::to_string(&envelope).expect("Log must serialize to JSON");
+    env::log_str(&format!("EVENT_JSON:{}", json));
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct EventCreatedLog emitted by add_event
+pub struct EventCreatedLog {
+    pub event_id: u64,             // ID of the new event
+    pub creator: AccountId,        // Account the event is attributed to
+    pub title: String,             // Sanitized title
+    pub estimated_budget: U128,    // Estimated budget in yoctoNEAR
+}
+
+impl ContractLog for EventCreatedLog {
+    const EVENT: &'static str = "event_created";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct EventEditedLog emitted by edit_event
+pub struct EventEditedLog {
+    pub event_id: u64,             // ID of the edited event
+    pub editor: AccountId,         // Account that made the edit
+    pub estimated_budget: U128,    // Estimated budget after the edit
+}
+
+impl ContractLog for EventEditedLog {
+    const EVENT: &'static str = "event_edited";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct VoteCastLog emitted by add_vote, try_add_vote and add_vote_as
+pub struct VoteCastLog {
+    pub event_id: u64,             // ID of the event voted for
+    pub voter: AccountId,          // Account the vote is attributed to
+    pub cast_by: AccountId,        // Account that sent the transaction, a sub-account for add_vote_as
+    pub fee: U128,                 // Deposit recorded for refund, in yoctoNEAR
+    pub weight: u32,               // Weight the vote counts for, above 1 on loyalty-weighted events
+}
+
+impl ContractLog for VoteCastLog {
+    const EVENT: &'static str = "vote_cast";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct VoteRemovedLog emitted by remove_vote
+pub struct VoteRemovedLog {
+    pub event_id: u64,             // ID of the event the vote was removed from
+    pub voter: AccountId,          // Account whose vote was removed
+    pub refund: U128,              // Fee refunded to the voter, in yoctoNEAR
+    pub storage_freed_bytes: u64,  // Contract storage released by the removal; not refunded, as voters never pay for it
+}
+
+impl ContractLog for VoteRemovedLog {
+    const EVENT: &'static str = "vote_removed";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct VoteMigratedLog emitted by migrate_my_vote
+pub struct VoteMigratedLog {
+    pub voter: AccountId,          // Account whose vote moved
+    pub from_event_id: u64,        // ID of the event the vote was taken off
+    pub to_event_id: u64,          // ID of the event the vote now counts for
+    pub weight: u32,               // Weight the vote counts for on the new event
+}
+
+impl ContractLog for VoteMigratedLog {
+    const EVENT: &'static str = "vote_migrated";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct StatusChangedLog emitted whenever an event moves to another lifecycle state
+pub struct StatusChangedLog {
+    pub event_id: u64,             // ID of the event
+    pub status: EventStatus,       // State the event is now in
+}
+
+impl ContractLog for StatusChangedLog {
+    const EVENT: &'static str = "status_changed";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct DeadlineExtendedLog emitted by extend_deadline
+pub struct DeadlineExtendedLog {
+    pub event_id: u64,             // ID of the event
+    pub extended_by: AccountId,    // Creator or owner who extended the deadline
+    pub previous_deadline: u64,    // Voting deadline before the extension, in nanoseconds
+    pub voting_deadline: u64,      // New voting deadline, in nanoseconds
+}
+
+impl ContractLog for DeadlineExtendedLog {
+    const EVENT: &'static str = "deadline_extended";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct DonationReceivedLog emitted by donate
+pub struct DonationReceivedLog {
+    pub event_id: u64,             // ID of the event donated to
+    pub donor: AccountId,          // Account that donated
+    pub amount: U128,              // Amount donated, in yoctoNEAR
+    pub matched: U128,             // Amount a sponsor added on top from their match, in yoctoNEAR
+    pub matched_by: Option<AccountId>, // Sponsor whose match was drawn, if any
+}
+
+impl ContractLog for DonationReceivedLog {
+    const EVENT: &'static str = "donation_received";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct FundingUnlockedLog emitted by the vote that reaches an event's funding threshold
+pub struct FundingUnlockedLog {
+    pub event_id: u64,             // ID of the event
+    pub total_votes: u64,          // Vote count that unlocked funding
+}
+
+impl ContractLog for FundingUnlockedLog {
+    const EVENT: &'static str = "funding_unlocked";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct EventFundedLog emitted by the donation that brings an event to its estimated budget,
+// and by mark_funded
+pub struct EventFundedLog {
+    pub event_id: u64,             // ID of the event
+    pub funds_raised: U128,        // Total donated at that point, in yoctoNEAR
+}
+
+impl ContractLog for EventFundedLog {
+    const EVENT: &'static str = "event_funded";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct CategoryCapWarningLog emitted by add_event when a budget exceeds its category's cap
+pub struct CategoryCapWarningLog {
+    pub event_id: u64,             // ID of the new event
+    pub category: String,          // Category of the event's template
+    pub cap: U128,                 // Most budget a round may approve in the category
+}
+
+impl ContractLog for CategoryCapWarningLog {
+    const EVENT: &'static str = "category_cap_warning";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct EventApprovedLog emitted by finalize_round for every event it approves
+pub struct EventApprovedLog {
+    pub event_id: u64,             // ID of the approved event
+    pub round: u64,                // Round the event was approved in
+}
+
+impl ContractLog for EventApprovedLog {
+    const EVENT: &'static str = "event_approved";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct CertificationDecidedLog emitted by certify_result and reject_result
+pub struct CertificationDecidedLog {
+    pub event_id: u64,             // ID of the approved event
+    pub auditor: AccountId,        // Auditor who decided
+    pub certification: Certification, // Certified, or Rejected with the reason
+}
+
+impl ContractLog for CertificationDecidedLog {
+    const EVENT: &'static str = "certification_decided";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct VotersDedupedLog emitted by dedup_voters when it drops repeated entries
+pub struct VotersDedupedLog {
+    pub event_id: u64,             // ID of the cleaned-up event
+    pub removed: u64,              // Number of repeated entries dropped
+    pub total_votes: u64,          // Votes left, one per distinct voter
+    pub weighted_total: u64,       // Sum of the recorded weights of the votes left
+}
+
+impl ContractLog for VotersDedupedLog {
+    const EVENT: &'static str = "voters_deduped";
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct HeartbeatLog emitted after every `heartbeat_interval`-th logged state change
+pub struct HeartbeatLog {
+    pub checksum: String,          // Hex rolling checksum after the change, as get_checksum returns it
+    pub event_count: u64,          // Number of events ever added
+    pub total_votes_cast: u64,     // Votes held by all events
+    pub total_raised: U128,        // Donations held by all events, in yoctoNEAR
+    pub change_seq: u64,           // Number of state changes logged so far
+}
+
+impl ContractLog for HeartbeatLog {
+    const EVENT: &'static str = "heartbeat";
+}
+
+/*
+ * Snapshot tests pinning the exact JSON of every log, so indexers notice any schema change
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::get_logs;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    // Emit the log in a fresh context and return the single line it produced
+    fn emitted<T: ContractLog>(log: &T) -> String {
+        testing_env!(VMContextBuilder::new().build());
+        emit(log);
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        logs[0].clone()
+    }
+
+    // Snapshot test for EventCreatedLog
+    #[test]
+    fn event_created_snapshot() {
+        let log = EventCreatedLog {
+            event_id: 3,
+            creator: "alice.testnet".parse().unwrap(),
+            title: "Art Show".to_string(),
+            estimated_budget: U128(200),
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"event_created","data":[{"event_id":3,"creator":"alice.testnet","title":"Art Show","estimated_budget":"200"}]}"#
+        );
+    }
+
+    // Snapshot test for EventEditedLog
+    #[test]
+    fn event_edited_snapshot() {
+        let log = EventEditedLog {
+            event_id: 3,
+            editor: "alice.testnet".parse().unwrap(),
+            estimated_budget: U128(300),
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"event_edited","data":[{"event_id":3,"editor":"alice.testnet","estimated_budget":"300"}]}"#
+        );
+    }
+
+    // Snapshot test for VoteCastLog
+    #[test]
+    fn vote_cast_snapshot() {
+        let log = VoteCastLog {
+            event_id: 0,
+            voter: "dao.near".parse().unwrap(),
+            cast_by: "alice.dao.near".parse().unwrap(),
+            fee: U128(1),
+            weight: 2,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"vote_cast","data":[{"event_id":0,"voter":"dao.near","cast_by":"alice.dao.near","fee":"1","weight":2}]}"#
+        );
+    }
+
+    // Snapshot test for VoteRemovedLog
+    #[test]
+    fn vote_removed_snapshot() {
+        let log = VoteRemovedLog {
+            event_id: 0,
+            voter: "bob.testnet".parse().unwrap(),
+            refund: U128(0),
+            storage_freed_bytes: 0,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"vote_removed","data":[{"event_id":0,"voter":"bob.testnet","refund":"0","storage_freed_bytes":0}]}"#
+        );
+    }
+
+    // Snapshot test for VoteMigratedLog
+    #[test]
+    fn vote_migrated_snapshot() {
+        let log = VoteMigratedLog {
+            voter: "bob.testnet".parse().unwrap(),
+            from_event_id: 0,
+            to_event_id: 1,
+            weight: 1,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"vote_migrated","data":[{"voter":"bob.testnet","from_event_id":0,"to_event_id":1,"weight":1}]}"#
+        );
+    }
+
+    // Snapshot test for StatusChangedLog
+    #[test]
+    fn status_changed_snapshot() {
+        let log = StatusChangedLog {
+            event_id: 5,
+            status: EventStatus::Deleted,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"status_changed","data":[{"event_id":5,"status":"Deleted"}]}"#
+        );
+    }
+
+    // Snapshot test for DeadlineExtendedLog
+    #[test]
+    fn deadline_extended_snapshot() {
+        let log = DeadlineExtendedLog {
+            event_id: 1,
+            extended_by: "bob.testnet".parse().unwrap(),
+            previous_deadline: 1_000,
+            voting_deadline: 2_000,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"deadline_extended","data":[{"event_id":1,"extended_by":"bob.testnet","previous_deadline":1000,"voting_deadline":2000}]}"#
+        );
+    }
+
+    // Snapshot test for DonationReceivedLog
+    #[test]
+    fn donation_received_snapshot() {
+        let log = DonationReceivedLog {
+            event_id: 2,
+            donor: "bob.testnet".parse().unwrap(),
+            amount: U128(1_000_000_000_000_000_000_000_000),
+            matched: U128(0),
+            matched_by: None,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"donation_received","data":[{"event_id":2,"donor":"bob.testnet","amount":"1000000000000000000000000","matched":"0","matched_by":null}]}"#
+        );
+    }
+
+    // Snapshot test for FundingUnlockedLog
+    #[test]
+    fn funding_unlocked_snapshot() {
+        let log = FundingUnlockedLog {
+            event_id: 2,
+            total_votes: 25,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"funding_unlocked","data":[{"event_id":2,"total_votes":25}]}"#
+        );
+    }
+
+    // Snapshot test for EventFundedLog
+    #[test]
+    fn event_funded_snapshot() {
+        let log = EventFundedLog {
+            event_id: 4,
+            funds_raised: U128(250),
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"event_funded","data":[{"event_id":4,"funds_raised":"250"}]}"#
+        );
+    }
+
+    // Snapshot test for CategoryCapWarningLog
+    #[test]
+    fn category_cap_warning_snapshot() {
+        let log = CategoryCapWarningLog {
+            event_id: 6,
+            category: "Music".to_string(),
+            cap: U128(100),
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"category_cap_warning","data":[{"event_id":6,"category":"Music","cap":"100"}]}"#
+        );
+    }
+
+    // Snapshot test for CertificationDecidedLog
+    #[test]
+    fn certification_decided_snapshot() {
+        let log = CertificationDecidedLog {
+            event_id: 6,
+            auditor: "auditor.testnet".parse().unwrap(),
+            certification: Certification::Rejected {
+                reason: "Budget not itemized".to_string(),
+            },
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"certification_decided","data":[{"event_id":6,"auditor":"auditor.testnet","certification":{"Rejected":{"reason":"Budget not itemized"}}}]}"#
+        );
+    }
+
+    // Snapshot test for EventApprovedLog
+    #[test]
+    fn event_approved_snapshot() {
+        let log = EventApprovedLog {
+            event_id: 6,
+            round: 2,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"event_approved","data":[{"event_id":6,"round":2}]}"#
+        );
+    }
+
+    // Snapshot test for VotersDedupedLog
+    #[test]
+    fn voters_deduped_snapshot() {
+        let log = VotersDedupedLog {
+            event_id: 2,
+            removed: 1,
+            total_votes: 2,
+            weighted_total: 4,
+        };
+        assert_eq!(
+            emitted(&log),
+            r#"EVENT_JSON:{"standard":"charles_contract","version":"1.0.0","event":"voters_deduped","data":[{"event_id":2,"removed":1,"total_votes":2,"weighted_total":4}]}"#
+        );
+    }
+
+    // Snapshot test for HeartbeatLog
+    #[test]
+    fn heartbeat_snapshot() {
+        let log = HeartbeatLog {
+            checksum: "ab".repeat(32),
+            event_count: 3,
+            total_votes_cast: 7,
+            total_raised: U128(500),
+            change_seq: 100,
+        };
+        assert_eq!(
+            emitted(&log),
+            format!(
+                r#"EVENT_JSON:{{"standard":"charles_contract","version":"1.0.0","event":"heartbeat","data":[{{"checksum":"{}","event_count":3,"total_votes_cast":7,"total_raised":"500","change_seq":100}}]}}"#,
+                "ab".repeat(32)
+            )
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// Import necessary items from the near_sdk crate
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, AccountId, IntoStorageKey, Timestamp};
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the enum LedgerAccount listing the internal accounts NEAR can be booked to
+pub enum LedgerAccount {
+    External,                           // Everything outside the contract; the only account that goes negative
+    EventFunding(u64),                  // Donations held for an event
+    Fees,                               // Fees kept by the platform
+    RewardPool,                         // Funds set aside for rewards
+    Escrow(u64),                        // Refundable vote fees held for an event
+    MatchEscrow(u64),                   // Unused part of a sponsor's donation match for an event
+    PendingRefund(u64, AccountId),      // Refund owed to an account for an event, not sent yet
+    StorageDeposit(AccountId),          // Storage deposit an account paid for records it saved, e.g. creator defaults
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct LedgerEntry, one booked transfer between two ledger accounts
+pub struct LedgerEntry {
+    pub id: u64,                    // Sequential ID, never reused
+    pub from: LedgerAccount,        // Account debited
+    pub to: LedgerAccount,          // Account credited
+    pub amount: U128,               // Amount moved, in yoctoNEAR
+    pub memo: String,               // Why the amount moved, e.g. "donation"
+    pub created_at: Timestamp,      // When the transfer was booked
+}
+
+// Add serialization format for Borsh
+#[near(serializers=[borsh])]
+// Define the struct Ledger, a double-entry book of every NEAR movement of the contract
+pub struct Ledger {
+    balances: LookupMap<LedgerAccount, i128>, // Balance of each account that was ever booked to
+    entries: LookupMap<u64, LedgerEntry>,     // Booked transfers by ID
+    next_entry: u64,                          // ID the next entry will get
+    held: u128,                               // Sum of every account but External, i.e. NEAR held
+}
+
+// Implement methods for the Ledger struct
+impl Ledger {
+    // Define a constructor method storing balances and entries under the given prefixes
+    pub fn new(balances_prefix: impl IntoStorageKey, entries_prefix: impl IntoStorageKey) -> Self {
+        Ledger {
+            balances: LookupMap::new(balances_prefix),
+            entries: LookupMap::new(entries_prefix),
+            next_entry: 0,
+            held: 0,
+        }
+    }
+
+    // Get the balance of an account; only External can be negative
+    pub fn balance(&self, account: &LedgerAccount) -> i128 {
+        self.balances.get(account).unwrap_or(0)
+    }
+
+    // Get up to `limit` entries starting at ID `from_id`, oldest first
+    pub fn entries(&self, from_id: u64, limit: u64) -> Vec<LedgerEntry> {
+        (from_id..self.next_entry)
+            .take(limit as usize)
+            .filter_map(|id| self.entries.get(&id))
+            .collect()
+    }
+
+    // Book `amount` from one account to another, panicking if any account but External would go negative
+    // or if the debits and credits stop balancing
+    pub fn transfer(
+        &mut self,
+        from: LedgerAccount,
+        to: LedgerAccount,
+        amount: u128,
+        memo: &str,
+    ) {
+        assert!(amount > 0, "Ledger transfers must be positive");
+        assert_ne!(from, to, "Ledger transfers need two different accounts");
+        let signed = i128::try_from(amount).expect("Ledger transfer is too large");
+
+        // Debit the source, which must cover the amount unless it is External
+        let from_balance = self.balance(&from) - signed;
+        assert!(
+            from == LedgerAccount::External || from_balance >= 0,
+            "Ledger account {:?} would go negative",
+            from
+        );
+        self.balances.insert(&from, &from_balance);
+
+        // Credit the destination
+        let to_balance = self.balance(&to) + signed;
+        self.balances.insert(&to, &to_balance);
+
+        // Keep the NEAR held in step with what crossed the contract boundary
+        if from == LedgerAccount::External {
+            self.held += amount;
+        } else if to == LedgerAccount::External {
+            self.held -= amount;
+        }
+        assert_eq!(
+            self.balance(&LedgerAccount::External) + self.held as i128,
+            0,
+            "Ledger debits and credits do not balance"
+        );
+
+        // Record the entry
+        let id = self.next_entry;
+        let entry = LedgerEntry {
+            id,
+            from,
+            to,
+            amount: U128(amount),
+            memo: memo.to_string(),
+            created_at: env::block_timestamp(),
+        };
+        self.entries.insert(&id, &entry);
+        self.next_entry += 1;
+    }
+}
+
+/* The rest of this file holds the inline tests for the ledger. */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    // Unit test to book transfers and keep every balance in step
+    #[test]
+    fn transfer_books_both_sides() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut ledger = Ledger::new(b"a", b"e");
+
+        // Take 100 in for an event, then move 30 of it to the fees
+        ledger.transfer(LedgerAccount::External, LedgerAccount::EventFunding(0), 100, "donation");
+        ledger.transfer(LedgerAccount::EventFunding(0), LedgerAccount::Fees, 30, "fee");
+
+        // Assert that the balances sum to zero
+        assert_eq!(ledger.balance(&LedgerAccount::External), -100);
+        assert_eq!(ledger.balance(&LedgerAccount::EventFunding(0)), 70);
+        assert_eq!(ledger.balance(&LedgerAccount::Fees), 30);
+
+        // Assert that both entries were recorded in order
+        let entries = ledger.entries(0, 10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].from, LedgerAccount::EventFunding(0));
+        assert_eq!(entries[1].amount, U128(30));
+        assert_eq!(ledger.entries(1, 10), entries[1..].to_vec());
+    }
+
+    // Unit test to reject a transfer that would overdraw an internal account
+    #[test]
+    #[should_panic(expected = "would go negative")]
+    fn transfer_rejects_overdraft() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut ledger = Ledger::new(b"a", b"e");
+        ledger.transfer(LedgerAccount::External, LedgerAccount::Escrow(0), 10, "vote_fee");
+        ledger.transfer(LedgerAccount::Escrow(0), LedgerAccount::External, 11, "vote_refund");
+    }
+}
@@ -1,11 +1,157 @@
 // Import necessary items from the near_sdk crate
-use near_sdk::{env, near, near_bindgen, AccountId};
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::{env, near, near_bindgen, serde_json, AccountId, BorshStorageKey};
 
-use near_sdk::{ Timestamp};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, I128, U128, U64};
+use near_sdk::{Gas, NearToken, Promise, PromiseError, Timestamp};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
 
-// Add serialization formats for Borsh and JSON, and derive Clone trait for the struct
+mod events;
+use events::{
+    emit, CategoryCapWarningLog, CertificationDecidedLog, ContractLog, DeadlineExtendedLog,
+    DonationReceivedLog, EventApprovedLog, EventCreatedLog, EventEditedLog, EventFundedLog,
+    FundingUnlockedLog, HeartbeatLog, StatusChangedLog, VoteCastLog, VoteMigratedLog,
+    VoteRemovedLog, VotersDedupedLog,
+};
+
+mod ledger;
+use ledger::{Ledger, LedgerAccount, LedgerEntry};
+
+mod migration;
+
+mod params;
+use params::{
+    ParamBound, DEFAULT_HEARTBEAT_INTERVAL, DEFAULT_MAX_BUDGET, DEFAULT_MAX_DEADLINE_EXTENSION_SECS,
+    DEFAULT_MAX_INLINE_VOTERS, DEFAULT_MAX_LINKS_IN_DESCRIPTION, DEFAULT_MAX_VOTING_PERIOD_SECS,
+    DEFAULT_MIN_BUDGET, DEFAULT_RESTORE_WINDOW_SECS, SECONDS_PER_DAY,
+};
+
+#[cfg(test)]
+mod test_support;
+
+// Length limits for user-supplied strings, counted in Unicode scalar values rather than bytes
+const MAX_TITLE_CHARS: usize = 100;
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+const MAX_TAG_CHARS: usize = 32;
+const MAX_PROPOSAL_URL_CHARS: usize = 512;
+const MAX_QUESTION_CHARS: usize = 200;
+const MAX_CHOICE_CHARS: usize = 50;
+
+// Length of the lowercase title prefixes indexed for autocomplete_titles, and the shortest prefix it accepts
+const TITLE_PREFIX_CHARS: usize = 8;
+const MIN_AUTOCOMPLETE_CHARS: usize = 2;
+
+// Limits on the owner-managed list of banned terms
+const MAX_BANNED_TERMS: usize = 100;
+const MAX_BANNED_TERM_CHARS: usize = 40;
+
+// Limits on the survey an event may ask voters to answer
+const MAX_QUESTIONS: usize = 3;
+const MAX_CHOICES: usize = 5;
+
+// Gas add_vote must stay under when no deposit is required, so limited access keys can vote
+const ADD_VOTE_GAS_BUDGET: Gas = Gas::from_tgas(10);
+
+// Most events add_votes_batch votes for in one call, keeping it within the gas suggested for it
+const MAX_BATCH_VOTES: usize = 20;
+
+// How actions are attributed to accounts, returned by attribution_policy for relayer operators
+const ATTRIBUTION_POLICY: &str = "All actions (event creation, votes, vote removal, tagging and \
+permission checks) are attributed to the predecessor account, never the signer. Under NEP-366 \
+meta transactions the predecessor is the account that signed the delegate action, so a relayer \
+can sponsor gas without votes or events being credited to the relayer.";
+
+// Version of the stored state layout; bump it with every change migrate() must convert.
+// Version 1 is the first versioned layout. State from the unversioned first deployment is read as
+// migration::BaselineContract.
+const STATE_VERSION: u32 = 1;
+
+// Version of the public API, following the package version clients see in the ABI
+const ABI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// ABI JSON embedded by `cargo near build --embed-abi --features abi-embed`, empty in other builds
+#[cfg(feature = "abi-embed")]
+const CONTRACT_ABI: &[u8] = include_bytes!(env!("CARGO_NEAR_ABI_PATH"));
+#[cfg(not(feature = "abi-embed"))]
+const CONTRACT_ABI: &[u8] = &[];
+
+// Number of budget revisions kept per event; older entries are dropped first
+const MAX_BUDGET_HISTORY: usize = 50;
+
+// Number of days covered by the daily vote counts of get_event_analytics, ending today
+const ANALYTICS_DAYS: u64 = 14;
+
+// Number of most recent votes get_event_analytics reads timestamps for, keeping the view's gas bounded
+const MAX_ANALYTICS_SCAN: usize = 500;
+
+// Number of most recent events get_event_analytics compares an event against when ranking it
+const MAX_RANK_SCAN: usize = 1000;
+
+// Number of most recent voters related_events samples, and of each one's most recent votes it reads.
+// The view reads at most 50 index records and counts at most 5,000 co-votes, however popular the event.
+const RELATED_SAMPLE_VOTERS: usize = 50;
+const RELATED_VOTES_PER_VOTER: usize = 100;
+
+// Bonus votes ranked_feed gives a brand-new event; it shrinks by one per day of age
+const FEED_RECENCY_BONUS: u64 = 10;
+
+// Number of unacknowledged notifications kept in the outbox; the oldest is dropped to make room
+const MAX_OUTBOX: u64 = 1000;
+
+// Length limits on the short codes events can be linked by
+const MIN_SHORT_CODE_CHARS: usize = 3;
+const MAX_SHORT_CODE_CHARS: usize = 12;
+
+// Maximum number of short codes the owner can reserve
+const MAX_RESERVED_CODES: usize = 100;
+
+// Maximum number of templates the owner can publish, and length limit on their categories
+const MAX_TEMPLATES: usize = 50;
+const MAX_CATEGORY_CHARS: usize = 32;
+
+// Length limit on the reason given when forcing a round through below the minimum turnout
+const MAX_FORCE_REASON_CHARS: usize = 200;
+
+// Length limit on the reason the auditor gives when rejecting an approved event's result
+const MAX_REJECT_REASON_CHARS: usize = 200;
+
+// Time an outgoing transfer must stay unresolved before the owner may reconcile it, and gas for its callback
+const TRANSFER_RECONCILE_DELAY_SECS: u64 = SECONDS_PER_DAY;
+const TRANSFER_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+// Storage layout of a newly added event, shared by estimate_creation_cost and the test measuring add_event.
+// The base is the Borsh size of the event without its creator, title and description bytes.
+const EVENT_BASE_BYTES: u64 = 175;
+const MAX_ACCOUNT_ID_BYTES: u64 = 64;
+// NEAR charges this many bytes on top of the key and value of every storage record
+const STORAGE_RECORD_OVERHEAD_BYTES: u64 = 40;
+// A first event also adds the creator's last_event_at and registered_at records, each keyed by the account
+const CREATION_INDEX_BYTES: u64 =
+    2 * (STORAGE_RECORD_OVERHEAD_BYTES + 1 + 4 + MAX_ACCOUNT_ID_BYTES + 8);
+// Event ID of outgoing transfers owed for no event, e.g. storage deposit refunds
+const NO_EVENT: u64 = u64::MAX;
+// Saved creator defaults take one record keyed by the account, holding three optional integers
+const CREATOR_DEFAULTS_BYTES: u64 =
+    STORAGE_RECORD_OVERHEAD_BYTES + 1 + 4 + MAX_ACCOUNT_ID_BYTES + 3 * 9;
+// A new title prefix bucket takes a tree node record and a value record, each holding the prefix, plus the
+// link to the node from its parent; the prefix bytes are counted separately, as they depend on the title
+const TITLE_BUCKET_BYTES: u64 =
+    2 * STORAGE_RECORD_OVERHEAD_BYTES + (2 + 8) + (8 + 4 + 1 + 1 + 8) + (2 + 4) + (4 + 8) + 8;
+
+// Depth of the Merkle tree of vote receipts kept per event; leaves past the last vote count as zero bytes
+const VOTE_MERKLE_DEPTH: usize = 32;
+
+// Options of add_event estimate_creation_cost can account for, combined as bit flags
+const CREATION_OPTION_PROPOSAL_URL: u8 = 1;
+const CREATION_OPTION_FUNDING_THRESHOLD: u8 = 2;
+const CREATION_OPTION_TEMPLATE: u8 = 4;
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
 #[near(serializers=[borsh, json])]
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 // Define the struct Event
 pub struct Event {
     // Define fields of the Event struct
@@ -17,34 +163,1086 @@ pub struct Event {
     pub total_votes: i64,                   // Total number of votes received for the event
     description: String,                    // Description of the event
     pub votes: Vec<String>,                 // List of account IDs that voted for the event
+    pub tags: Vec<String>,                  // Tags used to categorize the event
+    pub voting_deadline: Option<Timestamp>, // Time after which votes are no longer accepted, if any
+    pub deadline_extended: bool,            // Whether the creator used their one extend_deadline
+    pub votes_open_at: Timestamp,           // Time before which votes are not accepted yet
+    budget_history: Vec<(Timestamp, u128)>, // Revisions of the estimated budget, oldest first
+    pub budget_locked: bool,                // Whether edit_event rejects budget changes; set by lock_budget
+    pub loyalty_weighted: bool,             // Whether votes are multiplied by the voter's loyalty tier
+    pub weighted_total: u64,                // Sum of vote weights; equals total_votes when not loyalty-weighted
+    pub min_voter_balance: Option<u128>,    // Deposit a voter must attach as proof of holding NEAR, if any
+    pub proposal_url: Option<String>,       // Link to an off-chain governance proposal, if any
+    pub questions: Vec<SurveyQuestion>,     // Survey voters may answer when voting
+    answer_tallies: Vec<Vec<u64>>,          // Number of times each choice of each question was picked
+    pub status: EventStatus,                // Lifecycle state; only visible events appear in views
+    pub draft: bool,                        // Whether only the creator sees the event; cleared by publish_event
+    deleted_at: Option<Timestamp>,          // When the event was soft-deleted, if it is deleted
+    pub funds_raised: u128,                 // Total donated to the event, in yoctoNEAR
+    pub min_votes_for_funding: Option<u64>, // Votes needed before donations are accepted, if any
+    pub funding_unlocked: bool,             // Whether donations are accepted; stays true once reached
+    pub funding_cap: FundingCap,            // Whether donations may exceed the estimated budget
+    donors: Vec<String>,                    // Accounts that donated, in order of their first donation
+    pub short_code: Option<String>,         // Uppercase short code the event can be linked by, if claimed
+    pub funded: bool,                       // Whether the owner marked the event funded, which ends voting
+    pub template_id: Option<u64>,           // Template the event was created from, if any
+    pub approved_in_round: Option<u64>,     // Round in which finalize_round approved the event, if any
+    pub certification: Option<Certification>, // Auditor's review of the approval, if an auditor was set at the time
+    pub round: Option<u64>,                 // Latest round the event competed in, if any
+    refund_cursor: u64,                     // Number of donors process_refunds has refunded, in order
+    refunds_settled: u64,                   // Number of those refunds confirmed as arrived
+    vote_leaves: u64,                       // Number of vote receipts appended to the Merkle tree
+    vote_frontier: Vec<[u8; 32]>,           // Rightmost complete subtree at each level, enough to append
+    pub vote_merkle_root: Option<[u8; 32]>, // Merkle root of the vote receipts so far, once there are any
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Copy, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+// Define the enum EventStatus listing the lifecycle states of an event
+pub enum EventStatus {
+    Open,       // Visible and accepting changes
+    Deleted,    // Hidden by the owner; can be restored within the restore window
+    Purged,     // Permanently removed; only the ID is kept so later IDs stay valid
+}
+
+// Add serialization format for JSON, and derive Clone, Copy, Debug and PartialEq traits for the enum
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+// Define the enum EventLocation returned by get_event_state, telling apart why an ID may not be usable
+pub enum EventLocation {
+    Active,     // The event exists and accepts changes
+    Deleted,    // The owner deleted the event; it can be restored within the restore window
+    Purged,     // The event was deleted and purged; only its ID is kept
+    Unknown,    // No event was ever created with the ID
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Copy, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+// Define the enum FundingCap listing how donations beyond an event's estimated budget are handled
+pub enum FundingCap {
+    Unlimited,                      // Donations may exceed the budget, e.g. for stretch goals
+    HardCap,                        // Donations that would exceed the budget are rejected
+    HardCapWithOverflowRefund,      // The part of a donation beyond the budget is refunded
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the enum Certification listing the auditor's review states of an approved event's result
+pub enum Certification {
+    Pending,                        // Approved by a round, waiting for the auditor
+    Certified,                      // The auditor co-signed the result; the approval is final
+    Rejected { reason: String },    // The auditor rejected the result; the approval is void
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct SurveyQuestion, a multiple-choice question asked at vote time
+pub struct SurveyQuestion {
+    pub prompt: String,         // Question shown to the voter
+    pub choices: Vec<String>,   // Predefined answers, picked by index
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct SurveyResult returned by get_survey_results, one per question
+pub struct SurveyResult {
+    pub prompt: String,         // Question shown to the voter
+    pub choices: Vec<String>,   // Predefined answers, picked by index
+    pub tallies: Vec<u64>,      // Number of voters who picked each choice
 }
 
 // Implement methods for the Event struct
 impl Event {
     // Define a constructor method to create a new Event instance
     pub fn new(id: i32, title: String, estimated_budget: u128, description: String) -> Self {
+        // Get the current block timestamp
+        let created_at = env::block_timestamp();
+
         // Create a new Event instance with the provided parameters
         Event {
             // Assign values to the fields of the Event struct
             id,                                             // Assign provided id
-            creator: env::signer_account_id(),             // Set creator as the signer's account ID
-            created_at: env::block_timestamp(),           // Set creation timestamp to current block timestamp
+            creator: env::predecessor_account_id(),        // Set creator as the caller's account ID (relayer-safe)
+            created_at,                                    // Set creation timestamp to current block timestamp
             title,                                         // Assign provided title
             estimated_budget,                              // Assign provided estimated budget
             total_votes: 0,                                // Initialize total_votes to 0
             description,                                   // Assign provided description
             votes: vec![],                                 // Initialize votes vector as empty
+            tags: vec![],                                  // Initialize tags vector as empty
+            voting_deadline: None,                         // Accept votes until a deadline is set
+            deadline_extended: false,                      // Leave the creator's extension unused
+            votes_open_at: created_at,                     // Accept votes from creation
+            budget_history: vec![(created_at, estimated_budget)], // Record the initial budget
+            budget_locked: false,                          // Allow budget edits until locked
+            loyalty_weighted: false,                       // Count every vote once
+            weighted_total: 0,                             // Initialize weighted_total to 0
+            min_voter_balance: None,                       // Let any account vote
+            proposal_url: None,                            // Link no proposal until one is given
+            questions: vec![],                             // Ask no survey questions
+            answer_tallies: vec![],                        // Initialize answer tallies as empty
+            status: EventStatus::Open,                     // Start visible
+            draft: true,                                   // Start as a draft until the creator publishes it
+            deleted_at: None,                              // Not deleted
+            funds_raised: 0,                               // Initialize funds_raised to 0
+            min_votes_for_funding: None,                   // Accept donations right away
+            funding_unlocked: true,                        // Accept donations right away
+            funding_cap: FundingCap::Unlimited,            // Accept donations beyond the budget
+            donors: vec![],                                // Initialize donors vector as empty
+            short_code: None,                              // Claim no short code until one is set
+            funded: false,                                 // Not funded yet
+            template_id: None,                             // Not created from a template
+            approved_in_round: None,                       // Not approved yet
+            certification: None,                           // Nothing to certify before approval
+            round: None,                                   // Not in a round yet
+            refund_cursor: 0,                              // No donor refunded yet
+            refunds_settled: 0,                            // No refund settled yet
+            vote_leaves: 0,                                // No vote receipt yet
+            vote_frontier: vec![],                         // Initialize the Merkle frontier as empty
+            vote_merkle_root: None,                        // No root until the first vote
+        }
+    }
+
+    // Change the estimated budget, recording the revision and keeping at most MAX_BUDGET_HISTORY entries
+    fn revise_budget(&mut self, estimated_budget: u128, now: Timestamp) {
+        if estimated_budget == self.estimated_budget {
+            return;
+        }
+        self.estimated_budget = estimated_budget;
+        self.budget_history.push((now, estimated_budget));
+        if self.budget_history.len() > MAX_BUDGET_HISTORY {
+            self.budget_history.remove(0);
+        }
+    }
+
+    // Drop everything but the ID, creator and creation time, marking the event as purged
+    fn purge(&mut self) {
+        *self = Event {
+            id: self.id,
+            creator: self.creator.clone(),
+            created_at: self.created_at,
+            title: String::new(),
+            estimated_budget: 0,
+            total_votes: 0,
+            description: String::new(),
+            votes: vec![],
+            tags: vec![],
+            voting_deadline: None,
+            deadline_extended: false,
+            votes_open_at: 0,
+            budget_history: vec![],
+            budget_locked: false,
+            loyalty_weighted: false,
+            weighted_total: 0,
+            min_voter_balance: None,
+            proposal_url: None,
+            questions: vec![],
+            answer_tallies: vec![],
+            status: EventStatus::Purged,
+            draft: false,
+            deleted_at: None,
+            funds_raised: 0,
+            min_votes_for_funding: None,
+            funding_unlocked: false,
+            funding_cap: FundingCap::Unlimited,
+            donors: vec![],
+            short_code: None,
+            funded: false,
+            template_id: None,
+            approved_in_round: None,
+            certification: None,
+            round: None,
+            refund_cursor: 0,
+            refunds_settled: 0,
+            vote_leaves: 0,
+            vote_frontier: vec![],
+            vote_merkle_root: None,
+        };
+    }
+
+    // Append a vote receipt to the Merkle tree, keeping only its frontier, and refresh the root
+    fn append_vote_leaf(&mut self, leaf: [u8; 32]) {
+        // Hash in the complete left subtrees the new leaf closes, stopping at the first free level
+        let mut node = leaf;
+        let mut size = self.vote_leaves;
+        let mut level = 0;
+        while size & 1 == 1 {
+            node = merkle_parent(&self.vote_frontier[level], &node);
+            size >>= 1;
+            level += 1;
+        }
+        if level == self.vote_frontier.len() {
+            self.vote_frontier.push(node);
+        } else {
+            self.vote_frontier[level] = node;
+        }
+        self.vote_leaves += 1;
+
+        // Fold the frontier into the root, pairing partial subtrees with empty ones
+        let mut root = [0u8; 32];
+        let mut zero = [0u8; 32];
+        let mut size = self.vote_leaves;
+        for level in 0..VOTE_MERKLE_DEPTH {
+            root = if size & 1 == 1 {
+                merkle_parent(&self.vote_frontier[level], &root)
+            } else {
+                merkle_parent(&root, &zero)
+            };
+            zero = merkle_parent(&zero, &zero);
+            size >>= 1;
+        }
+        self.vote_merkle_root = Some(root);
+    }
+
+    // Check whether the event has not been deleted or purged
+    pub fn is_visible(&self) -> bool {
+        self.status == EventStatus::Open
+    }
+
+    // Donations the event can still take before reaching its budget, or None if they are not capped
+    pub fn remaining_capacity(&self) -> Option<u128> {
+        match self.funding_cap {
+            FundingCap::Unlimited => None,
+            FundingCap::HardCap | FundingCap::HardCapWithOverflowRefund => {
+                Some(self.estimated_budget.saturating_sub(self.funds_raised))
+            }
+        }
+    }
+
+    // Check whether the event still accepts votes at the given time
+    pub fn is_open(&self, now: Timestamp) -> bool {
+        self.voting_deadline.is_none_or(|deadline| now < deadline)
+    }
+
+    // Check whether process_refunds has dropped every voter record and receipt and reached the last donor
+    fn refunds_finished(&self) -> bool {
+        self.votes.is_empty()
+            && self.vote_leaves == 0
+            && self.refund_cursor == self.donors.len() as u64
+    }
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct EventView returned by get_event, with a capped list of voters
+pub struct EventView {
+    pub id: i32,                            // Unique identifier for the event
+    pub creator: AccountId,                 // Account ID of the creator of the event
+    pub created_at: Timestamp,              // Timestamp when the event was created
+    pub title: String,                      // Title of the event
+    pub estimated_budget: U128,             // Estimated budget for the event
+    pub total_votes: i64,                   // Total number of votes received for the event
+    pub weighted_total: u64,                // Sum of vote weights, shown alongside the raw count
+    pub loyalty_weighted: bool,             // Whether votes are multiplied by the voter's loyalty tier
+    pub min_voter_balance: Option<U128>,    // Deposit a voter must attach as proof of holding NEAR, if any
+    pub description: String,                // Description of the event
+    pub tags: Vec<String>,                  // Tags used to categorize the event
+    pub voting_deadline: Option<Timestamp>, // Time after which votes are no longer accepted, if any
+    pub votes_open_at: Timestamp,           // Time before which votes are not accepted yet
+    pub proposal_url: Option<String>,       // Link to an off-chain governance proposal, if any
+    pub short_code: Option<String>,         // Uppercase short code the event can be linked by, if claimed
+    pub template_id: Option<u64>,           // Template the event was created from, if any
+    pub round: Option<u64>,                 // Latest round the event competed in, if any; see get_round
+    pub certification: Option<Certification>, // Auditor's review of the approval, if one is required
+    pub questions: Vec<SurveyQuestion>,     // Survey voters may answer when voting
+    pub votes: Vec<String>,                 // Most recent voters, oldest first
+    pub voter_count: u64,                   // Total number of voters stored for the event
+    pub voters_truncated: bool,             // Whether older voters were left out; see get_voters
+    pub funding: FundingView,               // Donation state of the event
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct FundingView, the funding section of an event view
+pub struct FundingView {
+    pub funds_raised: U128,                 // Total donated to the event, in yoctoNEAR
+    pub donor_count: u64,                   // Number of distinct donors
+    pub min_votes_for_funding: Option<u64>, // Votes needed before donations are accepted, if any
+    pub funding_unlocked: bool,             // Whether donations are accepted
+    pub funding_cap: FundingCap,            // Whether donations may exceed the estimated budget
+    pub remaining_capacity: Option<U128>,   // Donations accepted before the budget is reached, if capped
+    pub funded: bool,                       // Whether the owner marked the event funded
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct EventAnalytics returned by get_event_analytics for organizer dashboards
+pub struct EventAnalytics {
+    pub event_id: u64,                      // ID of the event
+    pub votes_per_day: Vec<u64>,            // Votes cast on each of the last 14 UTC days, oldest first
+    pub votes_per_day_truncated: bool,      // Whether older votes were left out of votes_per_day
+    pub unique_voters: u64,                 // Number of distinct voters
+    pub funding_progress_percent: u64,      // Funds raised as a percentage of the estimated budget
+    pub rank: Option<u32>,                  // 1-based rank by votes among open events, if this one is open
+    pub rank_truncated: bool,               // Whether older events were left out of the rank
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct Notification, a queued copy of a significant log for an off-chain relay
+pub struct Notification {
+    pub id: u64,                            // Sequential ID, never reused
+    pub kind: String,                       // Name of the log, e.g. "funding_unlocked"
+    pub payload_json: String,               // Data of the log as JSON
+    pub created_at: Timestamp,              // When the notification was queued
+}
+
+// Implement methods for the EventView struct
+impl EventView {
+    // Build a view of the event keeping only the `max_voters` most recent voters
+    fn from_event(event: &Event, max_voters: usize) -> Self {
+        let skipped = event.votes.len().saturating_sub(max_voters);
+        EventView {
+            id: event.id,
+            creator: event.creator.clone(),
+            created_at: event.created_at,
+            title: event.title.clone(),
+            estimated_budget: U128(event.estimated_budget),
+            total_votes: event.total_votes,
+            weighted_total: event.weighted_total,
+            loyalty_weighted: event.loyalty_weighted,
+            min_voter_balance: event.min_voter_balance.map(U128),
+            description: event.description.clone(),
+            tags: event.tags.clone(),
+            voting_deadline: event.voting_deadline,
+            votes_open_at: event.votes_open_at,
+            proposal_url: event.proposal_url.clone(),
+            short_code: event.short_code.clone(),
+            template_id: event.template_id,
+            round: event.round,
+            certification: event.certification.clone(),
+            questions: event.questions.clone(),
+            votes: event.votes[skipped..].to_vec(),
+            voter_count: event.votes.len() as u64,
+            voters_truncated: skipped > 0,
+            funding: FundingView {
+                funds_raised: U128(event.funds_raised),
+                donor_count: event.donors.len() as u64,
+                min_votes_for_funding: event.min_votes_for_funding,
+                funding_unlocked: event.funding_unlocked,
+                funding_cap: event.funding_cap,
+                remaining_capacity: event.remaining_capacity().map(U128),
+                funded: event.funded,
+            },
+        }
+    }
+}
+
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the enum
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the enum VoteError listing the reasons a vote can be rejected
+pub enum VoteError {
+    EventNotFound,  // No event exists with the given ID
+    EventDeleted,   // The event was deleted by the owner
+    EventPurged,    // The event was deleted and purged
+    AlreadyVoted,   // The account has already voted for the event
+    VotingClosed,   // The voting deadline of the event has passed
+    DepositRequired, // Voting requires an attached deposit but none was attached
+    AnswersNotAccepted, // Answers were given for an event without questions
+    InvalidAnswers,  // The answers do not match the event's questions
+    AlreadyFunded,   // The owner marked the event funded, so votes no longer count
+    EventIsDraft,    // The creator has not published the event yet
+    NotOpenYet { opens_at: Timestamp }, // The event is still in its grace period after creation
+    BalanceProofRequired { min_balance: U128 }, // The attached deposit is below the event's minimum voter balance
+}
+
+// Implement methods for the VoteError enum
+impl VoteError {
+    // Human-readable reason, also used as the panic message of add_vote.
+    // Clients match on these strings (see tests/integration.rs), so treat changes as breaking.
+    pub fn message(&self) -> String {
+        match self {
+            VoteError::EventNotFound => "Event not found".to_string(),
+            VoteError::EventDeleted => "Event has been deleted".to_string(),
+            VoteError::EventPurged => "Event has been deleted and purged".to_string(),
+            VoteError::AlreadyVoted => "You have already voted for this event".to_string(),
+            VoteError::VotingClosed => "Voting has closed for this event".to_string(),
+            VoteError::DepositRequired => "Voting requires an attached deposit".to_string(),
+            VoteError::AnswersNotAccepted => "This event has no questions to answer".to_string(),
+            VoteError::InvalidAnswers => {
+                "Answers must pick one valid choice for every question".to_string()
+            }
+            VoteError::AlreadyFunded => "Event already funded".to_string(),
+            VoteError::EventIsDraft => "Event is still a draft".to_string(),
+            VoteError::NotOpenYet { opens_at } => format!("Voting opens at {}", opens_at),
+            VoteError::BalanceProofRequired { min_balance } => format!(
+                "Voting on this event requires attaching at least {} yoctoNEAR as balance proof",
+                min_balance.0
+            ),
+        }
+    }
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the enum
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the enum CreateError listing the reasons an event cannot be added
+pub enum CreateError {
+    NotAuthorized,                          // The creator is neither the owner nor whitelisted
+    TitleEmpty,                             // The title is empty after sanitization
+    TitleTooLong { max_chars: u32 },        // The title is longer than the limit
+    BudgetOutOfRange { min: U128, max: U128 }, // The budget lies outside the configured bounds
+    CooldownActive,                         // The creator added an event too recently
+    ContentPolicy { rule: ContentRule },    // The title breaks the content policy
+    InviteRequired,                         // Creation is invite-only and no invite code was given
+    InvalidInvite,                          // The invite code does not exist or was already used
+    TooManyOpenEvents { open: u64, max: u64 }, // The number of open events already reached the cap
+}
+
+// Implement methods for the CreateError enum
+impl CreateError {
+    // Human-readable reason, also used as the panic message of add_event
+    pub fn message(&self) -> String {
+        match self {
+            CreateError::NotAuthorized => "Not an authorized creator".to_string(),
+            CreateError::TitleEmpty => "Title cannot be empty".to_string(),
+            CreateError::TitleTooLong { max_chars } => {
+                format!("Title cannot exceed {} characters", max_chars)
+            }
+            CreateError::BudgetOutOfRange { min, max } => {
+                format!("Estimated budget must be between {} and {}", min.0, max.0)
+            }
+            CreateError::CooldownActive => "Creation cooldown active".to_string(),
+            CreateError::ContentPolicy { rule } => rule.message(),
+            CreateError::InviteRequired => "An invite code is required to create events".to_string(),
+            CreateError::InvalidInvite => "Invite code is invalid or already used".to_string(),
+            CreateError::TooManyOpenEvents { open, max } => {
+                format!("Too many open events: {} of at most {}", open, max)
+            }
+        }
+    }
+}
+
+// Add serialization format for JSON, and derive Clone, Debug and PartialEq traits for the enum
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the enum ContentRule naming the content policy rule a text broke, never the matched text
+pub enum ContentRule {
+    LinkInTitle,                // Links are forbidden in titles
+    TooManyLinks { max: u8 },   // The description has more links than allowed
+    BannedTerm,                 // The text contains a term on the owner's banned list
+}
+
+// Implement methods for the ContentRule enum
+impl ContentRule {
+    // Human-readable reason, also used as the panic message of the checked methods
+    pub fn message(&self) -> String {
+        match self {
+            ContentRule::LinkInTitle => "Content policy: titles cannot contain links".to_string(),
+            ContentRule::TooManyLinks { max } => {
+                format!("Content policy: descriptions cannot contain more than {} links", max)
+            }
+            ContentRule::BannedTerm => "Content policy: text contains a banned term".to_string(),
+        }
+    }
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct VoteCheck returned by dry_run_vote
+pub struct VoteCheck {
+    pub allowed: bool,                  // Whether the vote would currently be accepted
+    pub violations: Vec<VoteError>,     // Every reason the vote would be rejected, in the order checked
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct CreateCheck returned by dry_run_add_event
+pub struct CreateCheck {
+    pub allowed: bool,                  // Whether the event would currently be accepted
+    pub violations: Vec<CreateError>,   // Every reason the event would be rejected, in the order checked
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct EventPermissions returned by get_permissions, the actions an account may take on an event
+pub struct EventPermissions {
+    pub can_vote: bool,                 // Whether add_vote would accept the account's vote, deposit aside
+    pub can_edit: bool,                 // Whether the account may call edit_event
+    pub can_close: bool,                // Whether the account may set the voting deadline
+    pub can_withdraw: bool,             // Whether the account may take back its vote with remove_vote
+    pub can_comment: bool,              // Always false; events do not take comments yet
+    pub reasons: Vec<String>,           // Why each denied action is denied, prefixed with the action
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct Template, constraints the owner publishes for creators to follow
+pub struct Template {
+    pub category: String,           // Category events made from the template belong to
+    pub min_budget: u128,           // Smallest estimated budget suggested by the template
+    pub max_budget: u128,           // Largest estimated budget suggested by the template
+    pub required_tags: Vec<String>, // Tags every event made from the template carries
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct PublishedTemplate, a template as stored and listed by the contract
+pub struct PublishedTemplate {
+    pub id: u64,                    // Identifier passed to add_event
+    pub template: Template,         // Constraints of the template
+    pub active: bool,               // Whether new events may still use the template
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct CategoryCapacity returned by get_category_capacity
+pub struct CategoryCapacity {
+    pub category: String,           // Category the figures are for
+    pub cap: Option<U128>,          // Most budget a round may approve in the category, if capped
+    pub committed: U128,            // Budget approved in the category in the latest round
+    pub remaining: Option<U128>,    // Budget the cap still allows in that round, if capped
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct DonationMatch, a sponsor's pledge to match donations to an event
+pub struct DonationMatch {
+    pub sponsor: AccountId,         // Account that escrowed the match
+    pub cap: U128,                  // Most the sponsor will add, in yoctoNEAR
+    pub remaining: U128,            // Part of the cap not drawn or reclaimed yet
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct RefundProgress returned by refund_progress
+pub struct RefundProgress {
+    pub event_id: u64,              // ID of the deleted event
+    pub donor_count: u64,           // Number of distinct donors to refund
+    pub sent: u64,                  // Refunds sent so far by process_refunds
+    pub settled: u64,               // Refunds confirmed as arrived
+    pub outstanding: u64,           // Donors whose refund has not arrived yet
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Copy, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+// Define the enum TransferStatus listing the states of an outgoing transfer
+pub enum TransferStatus {
+    Pending,    // Sent, waiting for its callback
+    Completed,  // Arrived; the amount has left the ledger
+    Failed,     // Did not arrive; the amount stays owed to the recipient, who can retry it
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct PendingTransfer, the record of an outgoing NEAR transfer until it is settled
+pub struct PendingTransfer {
+    pub id: u64,                    // Sequential ID, never reused
+    pub to: AccountId,              // Recipient
+    pub amount: U128,               // Amount sent, in yoctoNEAR
+    pub purpose: String,            // Why it was sent, e.g. "vote_refund"
+    pub event_id: u64,              // Event the amount is owed for, or NO_EVENT
+    pub created_at: Timestamp,      // When the latest attempt was sent
+    pub attempt: u32,               // Number of attempts so far; callbacks of older attempts are ignored
+    pub status: TransferStatus,     // Whether the transfer is pending, completed or failed
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct RoundRecord, the archived outcome of a finalized round
+pub struct RoundRecord {
+    pub round: u64,                 // Number of the round, starting at 1
+    pub started_at: Timestamp,      // When the round opened, i.e. when the previous one was finalized
+    pub ended_at: Timestamp,        // When the round was finalized
+    pub event_ids: Vec<u64>,        // Events that competed, in ID order
+    pub winner_ids: Vec<u64>,       // Events approved, in the order they were picked
+    pub total_votes: u64,           // Votes held by the competing events at finalization
+    pub total_paid: U128,           // Sum of the estimated budgets of the approved events
+    pub tie_break: TieBreak,        // Strategy used to order events with equal votes
+    pub forced_reason: Option<String>, // Why the owner finalized the round below the minimum turnout, if they did
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct RoundTurnout returned by get_round_turnout
+pub struct RoundTurnout {
+    pub round: u64,                 // Number the round in progress will get
+    pub unique_voters: u64,         // Distinct accounts that voted on the round's events
+    pub total_votes: u64,           // Votes held by the round's events
+    pub min_round_turnout: u64,     // Unique voters needed to finalize the round
+    pub min_round_votes: u64,       // Votes needed to finalize the round
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct LoyaltyTier, a vote multiplier earned by accounts registered long enough
+pub struct LoyaltyTier {
+    pub min_age_secs: u64,  // Time since registration needed to reach the tier
+    pub multiplier: u32,    // Weight of each vote cast from this tier
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+// Define the struct Config holding the owner-tunable platform settings
+pub struct Config {
+    pub min_budget: u128,   // Smallest estimated budget accepted for an event
+    pub max_budget: u128,   // Largest estimated budget accepted for an event
+    pub max_inline_voters: u32, // Number of most recent voters included in get_event
+    pub require_vote_deposit: bool, // Whether add_vote requires an attached deposit (full access keys only)
+    pub creation_cooldown_secs: u64, // Minimum time between two events added by the same account
+    pub loyalty_tiers: Vec<LoyaltyTier>, // Vote multipliers for loyalty-weighted events, by ascending age
+    pub forbid_urls_in_title: bool, // Whether titles may contain links
+    pub max_links_in_description: u8, // Most links a description may contain
+    pub restore_window_secs: u64, // Time a deleted event can be restored for before it may be purged
+    pub vote_fee: u128,     // Charge per vote in yoctoNEAR; any deposit above it is refunded
+    pub invite_only: bool,  // Whether accounts other than the owner need a one-time invite code to add events
+    pub vote_grace_period_ns: u64, // Time after creation before an event accepts votes
+    pub grace_exempt_min_age_secs: Option<u64>, // Creators registered at least this long skip the grace period, if set
+    pub deadline_grace_period_ns: u64, // Time after a voting deadline during which votes are still accepted
+    pub tie_break: TieBreak, // How finalize_round orders events with the same number of votes
+    pub min_round_turnout: u64, // Unique voters a round needs before finalize_round accepts it
+    pub min_round_votes: u64, // Votes a round needs before finalize_round accepts it
+    pub max_open_events: Option<u64>, // Most events that may be open, drafts included, at once, if capped
+    pub max_deadline_extension_secs: u64, // Furthest a creator may push back a deadline with extend_deadline
+    pub heartbeat_interval: u64, // Number of logged state changes between heartbeat logs; 0 turns them off
+    pub max_voting_period_secs: u64, // Furthest ahead of the current time a voting deadline may be set
+    pub creation_fee: u128, // Deposit add_event must be sent exactly, in yoctoNEAR; kept by the platform
+}
+
+// Implement the default trait for the Config struct
+impl Default for Config {
+    // Define the default method
+    fn default() -> Self {
+        Self {
+            min_budget: DEFAULT_MIN_BUDGET,   // Reject zero budgets
+            max_budget: DEFAULT_MAX_BUDGET,   // Reject absurdly large budgets
+            max_inline_voters: DEFAULT_MAX_INLINE_VOTERS, // Keep get_event responses small
+            require_vote_deposit: false,      // Let function-call access keys vote
+            creation_cooldown_secs: 0,        // Do not throttle creators
+            loyalty_tiers: vec![
+                LoyaltyTier { min_age_secs: 90 * SECONDS_PER_DAY, multiplier: 2 },  // 2x after 90 days
+                LoyaltyTier { min_age_secs: 365 * SECONDS_PER_DAY, multiplier: 3 }, // 3x after a year
+            ],
+            forbid_urls_in_title: true,       // Keep spam links out of titles
+            max_links_in_description: DEFAULT_MAX_LINKS_IN_DESCRIPTION, // Allow a few reference links
+            restore_window_secs: DEFAULT_RESTORE_WINDOW_SECS, // Give moderators a month to undo deletions
+            vote_fee: 0,                      // Voting is free
+            invite_only: false,               // Do not ask for invite codes
+            vote_grace_period_ns: 0,          // Accept votes as soon as an event is created
+            grace_exempt_min_age_secs: None,  // Exempt no creators
+            deadline_grace_period_ns: 0,      // Stop accepting votes right at the deadline
+            tie_break: TieBreak::LowestId,    // Keep the historical lowest-ID rule
+            min_round_turnout: 0,             // Finalize rounds whatever the turnout
+            min_round_votes: 0,               // Finalize rounds whatever the vote count
+            max_open_events: None,            // Do not cap open events
+            max_deadline_extension_secs: DEFAULT_MAX_DEADLINE_EXTENSION_SECS, // Allow up to two weeks more
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL, // Let monitoring compare checksums regularly
+            max_voting_period_secs: DEFAULT_MAX_VOTING_PERIOD_SECS, // Keep votes open for at most 90 days
+            creation_fee: 0,                  // Adding events is free
+        }
+    }
+}
+
+// Implement methods for the Config struct
+impl Config {
+    // Panic, naming the first offending setting, unless every setting lies within its bounds in params
+    fn assert_valid(&self) {
+        assert_valid_budget_bounds(self.min_budget, self.max_budget);
+        assert_valid_loyalty_tiers(&self.loyalty_tiers);
+        params::MIN_BUDGET.check(self.min_budget);
+        params::MAX_BUDGET.check(self.max_budget);
+        params::MAX_INLINE_VOTERS.check(self.max_inline_voters);
+        params::CREATION_COOLDOWN_SECS.check(self.creation_cooldown_secs);
+        for tier in &self.loyalty_tiers {
+            params::LOYALTY_MULTIPLIER.check(tier.multiplier);
+        }
+        params::MAX_LINKS_IN_DESCRIPTION.check(self.max_links_in_description);
+        params::RESTORE_WINDOW_SECS.check(self.restore_window_secs);
+        params::VOTE_FEE.check(self.vote_fee);
+        params::VOTE_GRACE_PERIOD_NS.check(self.vote_grace_period_ns);
+        if let Some(age) = self.grace_exempt_min_age_secs {
+            params::GRACE_EXEMPT_MIN_AGE_SECS.check(age);
         }
+        params::DEADLINE_GRACE_PERIOD_NS.check(self.deadline_grace_period_ns);
+        params::MIN_ROUND_TURNOUT.check(self.min_round_turnout);
+        params::MIN_ROUND_VOTES.check(self.min_round_votes);
+        if let Some(max_open_events) = self.max_open_events {
+            params::MAX_OPEN_EVENTS.check(max_open_events);
+        }
+        params::MAX_DEADLINE_EXTENSION_SECS.check(self.max_deadline_extension_secs);
+        params::HEARTBEAT_INTERVAL.check(self.heartbeat_interval);
+        params::MAX_VOTING_PERIOD_SECS.check(self.max_voting_period_secs);
+        params::CREATION_FEE.check(self.creation_fee);
+    }
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Copy, Debug and PartialEq traits for the enum
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+// Define the enum TieBreak listing the ways finalize_round can order events with equal votes
+pub enum TieBreak {
+    LowestId,           // The event added first by ID wins
+    EarliestCreated,    // The event with the earliest creation time wins
+    MostUniqueDonors,   // The event with the most distinct donors wins
+    Random,             // A shuffle seeded by the block's random seed decides
+}
+
+// Implement methods for the TieBreak enum
+impl TieBreak {
+    // Order two events with equal votes, the one to pick first coming first. Remaining ties fall back to the lower ID.
+    fn compare(self, a: &Event, b: &Event, seed: &[u8]) -> Ordering {
+        let by_strategy = match self {
+            TieBreak::LowestId => Ordering::Equal,
+            TieBreak::EarliestCreated => a.created_at.cmp(&b.created_at),
+            TieBreak::MostUniqueDonors => b.donors.len().cmp(&a.donors.len()),
+            TieBreak::Random => {
+                let draw = |event: &Event| env::sha256(&[seed, &event.id.to_le_bytes()].concat());
+                draw(a).cmp(&draw(b))
+            }
+        };
+        by_strategy.then(a.id.cmp(&b.id))
     }
 }
 
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct BudgetTotal returned by aggregate budget views
+pub struct BudgetTotal {
+    pub total: U128,        // Sum of budgets, capped at u128::MAX
+    pub saturated: bool,    // Whether the real sum exceeded u128::MAX
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct VoteRequirements telling wallets which key type can vote
+pub struct VoteRequirements {
+    pub deposit_required: bool,     // Whether add_vote needs an attached deposit
+    pub min_deposit: NearToken,     // Smallest deposit add_vote accepts
+    pub gas_budget: Gas,            // Gas add_vote stays under when no deposit is required
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct MethodRequirement letting wallets prefill deposit and gas for a method
+pub struct MethodRequirement {
+    pub name: String,           // Name of the contract method
+    pub payable: bool,          // Whether the method accepts an attached deposit
+    pub min_deposit: U128,      // Smallest deposit the method currently accepts, in yoctoNEAR
+    pub suggested_gas: U64,     // Gas to attach when calling the method
+}
+
+// Add serialization format for Borsh, and derive Clone and Debug traits for the struct
+#[near(serializers=[borsh])]
+#[derive(Clone, Debug)]
+// Define the struct VoteReceipt, the data a leaf of an event's vote Merkle tree is hashed from
+pub struct VoteReceipt {
+    pub voter: AccountId,           // Account the vote is attributed to
+    pub cast_at: Timestamp,         // When the vote was cast
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct VoteLeaf returned by get_vote_leaf, enough to recompute the leaf hash off-chain
+pub struct VoteLeaf {
+    pub index: u64,                 // Position of the leaf in the tree, in the order votes were cast
+    pub voter: AccountId,           // Account the vote is attributed to
+    pub cast_at: Timestamp,         // When the vote was cast
+    pub hash: Base64VecU8,          // sha256(voter || event_id || cast_at), integers as little-endian u64
+}
+
+// Add serialization formats for Borsh and JSON, and derive Clone, Debug, Default and PartialEq traits for the struct
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Debug, Default, PartialEq)]
+// Define the struct CreatorDefaults, what add_event uses for the arguments a creator omits.
+// They are checked against the current settings when an event is added, not when they are saved.
+pub struct CreatorDefaults {
+    pub voting_period_secs: Option<u64>,    // Voting deadline of new events, in seconds after creation, if any
+    pub template_id: Option<u64>,           // Template, and so category, of new events given no template_id
+    pub min_votes_for_funding: Option<u64>, // Funding threshold of new events given none
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct AccountExport returned by export_account_data, one page of what is stored about an account
+pub struct AccountExport {
+    pub account_id: AccountId,              // Account the export is about
+    pub registered_at: Option<Timestamp>,   // When the account first interacted with the contract, if kept
+    pub last_event_at: Option<Timestamp>,   // When the account last added an event, if it has
+    pub is_creator: bool,                   // Whether the owner whitelisted the account to create events
+    pub created_events: Vec<u64>,           // IDs of the events the account created, within the page
+    pub votes: Vec<AccountVote>,            // Votes the account cast, within the page
+    pub donations: Vec<AccountDonation>,    // Donations the account made, within the page
+    pub next_from_event: Option<u64>,       // Event ID to request the next page from, if any remain
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct AccountVote, a vote listed in an AccountExport
+pub struct AccountVote {
+    pub event_id: u64,                      // ID of the event voted for
+    pub cast_at: Option<Timestamp>,         // When the vote was cast, if recorded
+    pub fee: U128,                          // Vote fee held for refund, in yoctoNEAR
+    pub weight: u32,                        // Weight the vote counts for
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct AccountDonation, a donation listed in an AccountExport
+pub struct AccountDonation {
+    pub event_id: u64,                      // ID of the event donated to
+    pub amount: U128,                       // Total donated to the event, in yoctoNEAR
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct StateChecksum returned by get_checksum, which heartbeat logs repeat
+pub struct StateChecksum {
+    pub checksum: String,                   // Hex rolling checksum after the latest logged state change
+    pub event_count: u64,                   // Number of events ever added, deleted and purged ones included
+    pub total_votes_cast: u64,              // Votes currently held by all events
+    pub total_raised: U128,                 // Donations currently held by all events, in yoctoNEAR
+    pub change_seq: u64,                    // Number of state changes logged so far
+}
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct CostEstimate returned by estimate_creation_cost, covering the worst case of a first event
+pub struct CostEstimate {
+    pub storage_bytes: U64,     // Bytes the event and its index records take in contract storage
+    pub storage_cost: U128,     // Price of those bytes, paid from the contract's balance, in yoctoNEAR
+    pub creation_fee: U128,     // Deposit add_event requires, in yoctoNEAR
+    pub total_deposit: U128,    // Deposit to attach to add_event, which is the creation fee alone
+    pub suggested_gas: U64,     // Gas to attach when calling add_event
+}
+
+// Define the enum Method, the registry of user-facing change methods.
+// get_method_requirements and the deposit checks inside the methods both read from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Method {
+    AddEvent,
+    AddVote,
+    TryAddVote,
+    AddVoteAs,
+    AddVotesBatch,
+    RemoveVote,
+    SetEventTags,
+    SetVotingDeadline,
+    ExtendDeadline,
+    EditEvent,
+    SetLoyaltyWeighted,
+    SetMinVoterBalance,
+    RegisterVoter,
+    SetEventQuestions,
+    Donate,
+    SetShortCode,
+    MigrateMyVote,
+    RetryTransfer,
+    CreateMatch,
+    ReclaimMatch,
+    SetCreatorDefaults,
+    RemoveCreatorDefaults,
+    SetFundingCap,
+}
+
+// Implement methods for the Method enum
+impl Method {
+    // Every registered method, in the order reported to wallets
+    const ALL: [Method; 23] = [
+        Method::AddEvent,
+        Method::AddVote,
+        Method::TryAddVote,
+        Method::AddVoteAs,
+        Method::AddVotesBatch,
+        Method::RemoveVote,
+        Method::SetEventTags,
+        Method::SetVotingDeadline,
+        Method::ExtendDeadline,
+        Method::EditEvent,
+        Method::SetLoyaltyWeighted,
+        Method::SetMinVoterBalance,
+        Method::RegisterVoter,
+        Method::SetEventQuestions,
+        Method::Donate,
+        Method::SetShortCode,
+        Method::MigrateMyVote,
+        Method::RetryTransfer,
+        Method::CreateMatch,
+        Method::ReclaimMatch,
+        Method::SetCreatorDefaults,
+        Method::RemoveCreatorDefaults,
+        Method::SetFundingCap,
+    ];
+
+    // Name of the contract method
+    fn name(self) -> &'static str {
+        match self {
+            Method::AddEvent => "add_event",
+            Method::AddVote => "add_vote",
+            Method::TryAddVote => "try_add_vote",
+            Method::AddVoteAs => "add_vote_as",
+            Method::AddVotesBatch => "add_votes_batch",
+            Method::RemoveVote => "remove_vote",
+            Method::SetEventTags => "set_event_tags",
+            Method::SetVotingDeadline => "set_voting_deadline",
+            Method::ExtendDeadline => "extend_deadline",
+            Method::EditEvent => "edit_event",
+            Method::SetLoyaltyWeighted => "set_loyalty_weighted",
+            Method::SetMinVoterBalance => "set_min_voter_balance",
+            Method::RegisterVoter => "register_voter",
+            Method::SetEventQuestions => "set_event_questions",
+            Method::Donate => "donate",
+            Method::SetShortCode => "set_short_code",
+            Method::MigrateMyVote => "migrate_my_vote",
+            Method::RetryTransfer => "retry_transfer",
+            Method::CreateMatch => "create_match",
+            Method::ReclaimMatch => "reclaim_match",
+            Method::SetCreatorDefaults => "set_creator_defaults",
+            Method::RemoveCreatorDefaults => "remove_creator_defaults",
+            Method::SetFundingCap => "set_funding_cap",
+        }
+    }
+
+    // Whether the method is marked #[payable]
+    fn payable(self) -> bool {
+        matches!(
+            self,
+            Method::AddEvent
+                | Method::AddVote
+                | Method::TryAddVote
+                | Method::AddVoteAs
+                | Method::Donate
+                | Method::CreateMatch
+                | Method::SetCreatorDefaults
+        )
+    }
+
+    // Gas to attach when calling the method
+    fn suggested_gas(self) -> Gas {
+        match self {
+            Method::AddVote | Method::TryAddVote | Method::AddVoteAs => ADD_VOTE_GAS_BUDGET,
+            Method::AddVotesBatch => Gas::from_tgas(200),
+            Method::RemoveVote
+            | Method::MigrateMyVote
+            | Method::RetryTransfer
+            | Method::ReclaimMatch
+            | Method::RemoveCreatorDefaults => Gas::from_tgas(15),
+            Method::AddEvent
+            | Method::SetEventTags
+            | Method::SetVotingDeadline
+            | Method::ExtendDeadline
+            | Method::EditEvent
+            | Method::SetLoyaltyWeighted
+            | Method::SetMinVoterBalance
+            | Method::RegisterVoter
+            | Method::SetEventQuestions
+            | Method::Donate
+            | Method::SetShortCode
+            | Method::CreateMatch
+            | Method::SetCreatorDefaults
+            | Method::SetFundingCap => Gas::from_tgas(10),
+        }
+    }
+}
 
+// Define the enum StorageKey naming the prefix of every persistent collection, so prefixes stay unique.
+// Each variant serializes to its discriminant, the single byte the collection always used; never reuse one.
+#[derive(BorshSerialize, BorshStorageKey, Clone, Copy, Debug, PartialEq)]
+#[borsh(crate = "near_sdk::borsh", use_discriminant = true)]
+#[repr(u8)]
+enum StorageKey {
+    VoteFees = b'f',
+    Creators = b'c',
+    LastEventAt = b'l',
+    RegisteredAt = b'r',
+    VoteWeights = b'w',
+    Donations = b'd',
+    VotedAt = b't',
+    Outbox = b'o',
+    ShortCodes = b's',
+    Invites = b'i',
+    CategoryCaps = b'k',
+    CategoryCommitted = b'm',
+    Rounds = b'h',
+    LedgerBalances = b'a',
+    LedgerEntries = b'e',
+    Transfers = b'x',
+    DonationMatches = b'n',
+    VoteReceipts = b'v',
+    CreatorDefaults = b'g',
+    AccountVotes = b'y',
+    TitlePrefixes = b'p',
+}
 
 // Define the contract structure
 #[near(contract_state)]
 pub struct Contract {
     owner: AccountId,       // Account ID of the contract owner
     events: Vec<Event>,     // Vector to store Event instances
+    config: Config,         // Owner-tunable platform settings
+    vote_fees: LookupMap<(u64, AccountId), u128>, // Deposit paid by each voter per event, refunded on removal
+    creators: UnorderedSet<AccountId>, // Accounts trusted by the owner to create events
+    last_event_at: LookupMap<AccountId, Timestamp>, // When each account last added an event
+    registered_at: LookupMap<AccountId, Timestamp>, // When each account first interacted with the contract
+    vote_weights: LookupMap<(u64, AccountId), u32>, // Weight of each loyalty-boosted vote, subtracted on removal
+    banned_terms: Vec<String>, // Lowercase terms rejected in titles and descriptions
+    donations: LookupMap<(u64, AccountId), u128>, // Total donated by each donor per event
+    voted_at: LookupMap<(u64, AccountId), Timestamp>, // When each vote was cast
+    keeper: Option<AccountId>, // Relay account allowed to acknowledge outbox notifications
+    auditor: Option<AccountId>, // Account that must certify round approvals, if the owner appointed one
+    pending_owner: Option<AccountId>, // Account the owner proposed to hand the contract to, until it accepts
+    title_prefixes: TreeMap<String, Vec<u64>>, // IDs of the visible events by lowercase title prefix, sorted
+    outbox: LookupMap<u64, Notification>, // Queued notifications by ID, from outbox_head up to outbox_next
+    outbox_head: u64,          // ID of the oldest notification still queued
+    outbox_next: u64,          // ID the next notification will get
+    outbox_dropped: u64,       // Number of notifications evicted before being acknowledged
+    short_codes: LookupMap<String, u64>, // Event ID claimed by each uppercase short code
+    reserved_codes: Vec<String>, // Uppercase short codes no event may claim
+    templates: Vec<PublishedTemplate>, // Templates published by the owner, indexed by ID
+    invites: LookupMap<Vec<u8>, Timestamp>, // Issue time of each unused invite code, keyed by its SHA-256 hash
+    invites_issued: u64,       // Number of invite codes ever issued, mixed into each new code
+    invites_outstanding: u64,  // Number of issued invite codes not used yet
+    category_caps: LookupMap<String, u128>, // Most budget a round may approve per template category
+    category_committed: LookupMap<(u64, String), u128>, // Budget approved per round and category
+    round: u64,                // Number of rounds finalized so far
+    round_started_at: Timestamp, // When the current round opened
+    rounds: LookupMap<u64, RoundRecord>, // Archived record of each finalized round
+    ledger: Ledger,            // Double-entry book of every NEAR movement; see transfer_internal
+    transfers: LookupMap<u64, PendingTransfer>, // Every outgoing transfer by ID
+    next_transfer_id: u64,     // ID the next outgoing transfer will get
+    open_transfers: Vec<u64>,  // IDs of the outgoing transfers not completed yet
+    donation_matches: LookupMap<u64, DonationMatch>, // Sponsor match of each event, if one was created
+    vote_receipts: LookupMap<(u64, u64), VoteReceipt>, // Receipt behind each leaf of each event's vote Merkle tree
+    creator_defaults: LookupMap<AccountId, CreatorDefaults>, // Defaults each creator saved for add_event
+    account_votes: LookupMap<AccountId, Vec<u64>>, // IDs of the events each account has a vote on, oldest first
+    change_seq: u64,           // Number of state changes logged so far; see record_change
+    checksum: [u8; 32],        // Rolling sha256 over the counters after each logged state change
+    total_votes_cast: u64,     // Votes held by all events, kept in step so record_change need not count them
+    total_raised: u128,        // Donations held by all events, kept in step like total_votes_cast
+    state_version: u32, // Layout version of this state, checked by migrate; kept last so older state reads as 0
 }
 
 // Implement the default trait for the Contract struct
@@ -55,6 +1253,47 @@ impl Default for Contract {
         Self {
             owner: env::current_account_id(),   // Set the owner as the current account ID
             events: Vec::new(),                 // Initialize events vector as empty
+            config: Config::default(),          // Start with the default settings
+            vote_fees: LookupMap::new(StorageKey::VoteFees), // Initialize the paid vote fees map
+            creators: UnorderedSet::new(StorageKey::Creators), // Initialize the creator whitelist as empty
+            last_event_at: LookupMap::new(StorageKey::LastEventAt), // Initialize the creation times map
+            registered_at: LookupMap::new(StorageKey::RegisteredAt), // Initialize the registration times map
+            vote_weights: LookupMap::new(StorageKey::VoteWeights), // Initialize the boosted vote weights map
+            banned_terms: Vec::new(),            // Initialize the banned terms as empty
+            donations: LookupMap::new(StorageKey::Donations), // Initialize the donations map
+            voted_at: LookupMap::new(StorageKey::VotedAt), // Initialize the vote times map
+            keeper: None,                        // No relay until the owner sets one
+            auditor: None,                       // Approvals are final without certification
+            pending_owner: None,                 // No ownership transfer proposed
+            title_prefixes: TreeMap::new(StorageKey::TitlePrefixes), // Initialize the title prefix index as empty
+            outbox: LookupMap::new(StorageKey::Outbox), // Initialize the outbox as empty
+            outbox_head: 0,                      // Initialize the outbox head to 0
+            outbox_next: 0,                      // Initialize the next notification ID to 0
+            outbox_dropped: 0,                   // Initialize the dropped count to 0
+            short_codes: LookupMap::new(StorageKey::ShortCodes), // Initialize the short codes map
+            reserved_codes: Vec::new(),          // Initialize the reserved codes as empty
+            templates: Vec::new(),               // Initialize the templates as empty
+            invites: LookupMap::new(StorageKey::Invites), // Initialize the invites map
+            invites_issued: 0,                   // Initialize the issued invites count to 0
+            invites_outstanding: 0,              // Initialize the outstanding invites count to 0
+            category_caps: LookupMap::new(StorageKey::CategoryCaps), // Initialize the category caps map
+            category_committed: LookupMap::new(StorageKey::CategoryCommitted), // Initialize the committed budgets map
+            round: 0,                            // No round finalized yet
+            round_started_at: env::block_timestamp(), // Open the first round now
+            rounds: LookupMap::new(StorageKey::Rounds), // Initialize the round history as empty
+            ledger: Ledger::new(StorageKey::LedgerBalances, StorageKey::LedgerEntries), // Initialize the ledger as empty
+            transfers: LookupMap::new(StorageKey::Transfers), // Initialize the outgoing transfers map
+            next_transfer_id: 0,                 // Initialize the next transfer ID to 0
+            open_transfers: Vec::new(),          // No transfer in flight
+            donation_matches: LookupMap::new(StorageKey::DonationMatches), // Initialize the donation matches map
+            vote_receipts: LookupMap::new(StorageKey::VoteReceipts), // Initialize the vote receipts map
+            creator_defaults: LookupMap::new(StorageKey::CreatorDefaults), // Initialize the creator defaults map
+            account_votes: LookupMap::new(StorageKey::AccountVotes), // Initialize the per-account vote index
+            change_seq: 0,                       // No state change logged yet
+            checksum: [0; 32],                   // Start the rolling checksum from zero bytes
+            total_votes_cast: 0,                 // No vote cast yet
+            total_raised: 0,                     // Nothing donated yet
+            state_version: STATE_VERSION,        // Start at the current layout
         }
     }
 }
@@ -62,139 +1301,9672 @@ impl Default for Contract {
 // Implement the Contract structure
 #[near_bindgen]
 impl Contract {
-    // Initialize method for creating a new instance of the Contract
+    // Initialize method for creating a new instance of the Contract with the default settings
     #[init]
     pub fn new(owner: AccountId) -> Self {
+        Self::with_config(owner, Config::default())
+    }
+
+    // Initialize method for creating a new instance of the Contract with its fees and open events cap set up front.
+    // Every other setting starts at its default; the owner can change any of them later with set_config.
+    #[init]
+    pub fn init_with_config(
+        owner: AccountId,
+        creation_fee: U128,
+        vote_fee: U128,
+        max_events: u32,
+    ) -> Self {
+        let config = Config {
+            creation_fee: creation_fee.0,
+            vote_fee: vote_fee.0,
+            max_open_events: Some(max_events as u64),
+            ..Config::default()
+        };
+        Self::with_config(owner, config)
+    }
+
+    // Create a new instance of the Contract with the given settings, checked the same way set_config checks them
+    fn with_config(owner: AccountId, config: Config) -> Self {
+        config.assert_valid();
+
         // Initialize events vector as empty
         let events: Vec<Event> = Vec::new();
 
         // Create and return a new instance of Contract with provided parameters
-        Contract { owner, events }
+        Contract {
+            owner,
+            events,
+            config,
+            vote_fees: LookupMap::new(StorageKey::VoteFees),
+            creators: UnorderedSet::new(StorageKey::Creators),
+            last_event_at: LookupMap::new(StorageKey::LastEventAt),
+            registered_at: LookupMap::new(StorageKey::RegisteredAt),
+            vote_weights: LookupMap::new(StorageKey::VoteWeights),
+            banned_terms: Vec::new(),
+            donations: LookupMap::new(StorageKey::Donations),
+            voted_at: LookupMap::new(StorageKey::VotedAt),
+            keeper: None,
+            auditor: None,
+            pending_owner: None,
+            title_prefixes: TreeMap::new(StorageKey::TitlePrefixes),
+            outbox: LookupMap::new(StorageKey::Outbox),
+            outbox_head: 0,
+            outbox_next: 0,
+            outbox_dropped: 0,
+            short_codes: LookupMap::new(StorageKey::ShortCodes),
+            reserved_codes: Vec::new(),
+            templates: Vec::new(),
+            invites: LookupMap::new(StorageKey::Invites),
+            invites_issued: 0,
+            invites_outstanding: 0,
+            category_caps: LookupMap::new(StorageKey::CategoryCaps),
+            category_committed: LookupMap::new(StorageKey::CategoryCommitted),
+            round: 0,
+            round_started_at: env::block_timestamp(),
+            rounds: LookupMap::new(StorageKey::Rounds),
+            ledger: Ledger::new(StorageKey::LedgerBalances, StorageKey::LedgerEntries),
+            transfers: LookupMap::new(StorageKey::Transfers),
+            next_transfer_id: 0,
+            open_transfers: Vec::new(),
+            donation_matches: LookupMap::new(StorageKey::DonationMatches),
+            vote_receipts: LookupMap::new(StorageKey::VoteReceipts),
+            creator_defaults: LookupMap::new(StorageKey::CreatorDefaults),
+            account_votes: LookupMap::new(StorageKey::AccountVotes),
+            change_seq: 0,
+            checksum: [0; 32],
+            total_votes_cast: 0,
+            total_raised: 0,
+            state_version: STATE_VERSION,
+        }
+    }
+
+    // Method to bring the stored state up to STATE_VERSION after deploying new code, only callable by the contract.
+    // Older state is read with the frozen layout of its version in the migration module and converted from it.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let bytes = env::storage_read(b"STATE").expect("Contract is not initialized");
+
+        // Running it again on migrated state would apply the same conversion twice
+        if let Ok(contract) = Contract::try_from_slice(&bytes) {
+            env::panic_str(&format!(
+                "Contract state is already at version {}",
+                contract.state_version
+            ));
+        }
+        migration::convert_legacy_state(&bytes)
     }
 
-    // Method to add a new event to the contract
-    pub fn add_event(&mut self, title: String, estimated_budget: u128, description: String) {
+    // Method to add a new event to the contract, attaching exactly the creation fee (none by default)
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn add_event(
+        &mut self,
+        title: String,
+        estimated_budget: u128,
+        description: String,
+        proposal_url: Option<String>,
+        min_votes_for_funding: Option<u64>,
+        template_id: Option<u64>,
+        invite_code: Option<String>,
+    ) {
+        // Reject the event for the first violation, using the same checks as dry_run_add_event
+        let caller = env::predecessor_account_id();
+        let violations =
+            self.add_event_violations(&caller, &title, estimated_budget, invite_code.as_deref());
+        if let Some(error) = violations.first() {
+            env::panic_str(&error.message());
+        }
+
+        // The creation fee must be attached exactly, so a mistyped amount is never kept
+        let creation_fee = self.required_deposit(Method::AddEvent);
+        if env::attached_deposit().as_yoctonear() != creation_fee {
+            env::panic_str(&format!(
+                "Attach exactly the creation fee of {} yoctoNEAR",
+                creation_fee
+            ));
+        }
+
+        // Fill omitted arguments from the caller's saved defaults, which must still fit the current settings
+        let defaults = self.creator_defaults.get(&caller).unwrap_or_default();
+        let template_id = template_id.or(defaults.template_id);
+        let min_votes_for_funding = min_votes_for_funding.or(defaults.min_votes_for_funding);
+        let voting_deadline = defaults.voting_period_secs.map(|seconds| {
+            assert!(
+                seconds <= self.config.max_voting_period_secs,
+                "Default voting period of {} seconds exceeds the maximum of {}",
+                seconds,
+                self.config.max_voting_period_secs
+            );
+            env::block_timestamp() + seconds * 1_000_000_000
+        });
+
+        // Events made from a template must fit its budget range, and start with its required tags
+        let required_tags = template_id.map(|template_id| {
+            let template = &self.active_template(template_id).template;
+            assert!(
+                (template.min_budget..=template.max_budget).contains(&estimated_budget),
+                "Budget must be between {} and {} for this template",
+                template.min_budget,
+                template.max_budget
+            );
+            template.required_tags.clone()
+        });
+
+        // Clean up the user-supplied strings before storing them
+        let title = clean_line(&title);
+        let description = sanitize_text("Description", &description, MAX_DESCRIPTION_CHARS);
+        self.assert_description_policy(&description);
+        let proposal_url = proposal_url.map(|url| validate_proposal_url(&url));
+
         // Generate a unique ID for the event
         let id = self.events.len() as i32;
 
         // Create a new Event instance and push it to the events vector
-        self.events.push(Event::new(id, title, estimated_budget, description));
+        let votes_open_at = self.votes_open_at(&caller);
+        let mut event = Event::new(id, title.clone(), estimated_budget, description);
+        event.votes_open_at = votes_open_at;
+        event.proposal_url = proposal_url;
+        event.min_votes_for_funding = min_votes_for_funding;
+        event.funding_unlocked = min_votes_for_funding.unwrap_or(0) == 0;
+        event.template_id = template_id;
+        event.tags = required_tags.unwrap_or_default();
+        event.voting_deadline = voting_deadline;
+        self.events.push(event);
+        self.index_title(id as u64, &title);
+
+        // Use up the invite code; it was checked above, and a failure anywhere in this call undoes this too
+        if let Some(code) = invite_code.filter(|_| self.invite_required(&caller)) {
+            self.invites.remove(&env::sha256(code.as_bytes()));
+            self.invites_outstanding -= 1;
+        }
+
+        // Book the creation fee to the platform
+        if creation_fee > 0 {
+            let fees = LedgerAccount::Fees;
+            self.transfer_internal(LedgerAccount::External, fees, creation_fee, "creation_fee");
+        }
 
-        // Log a message indicating that a new event has been added
-        env::log_str("Added a new event!");
+        // Start the caller's creation cooldown and register them if this is their first interaction
+        self.last_event_at.insert(&caller, &env::block_timestamp());
+        self.internal_register(&caller);
+
+        // Log the new event for indexers
+        self.log_change(&EventCreatedLog {
+            event_id: id as u64,
+            creator: caller,
+            title,
+            estimated_budget: U128(estimated_budget),
+        });
+
+        // Warn, without rejecting, when the budget alone exceeds its category's cap, as no round could approve it
+        if let Some(category) = self.category_of(&self.events[id as usize]) {
+            let cap = self.category_caps.get(&category);
+            if let Some(cap) = cap.filter(|&cap| estimated_budget > cap) {
+                emit(&CategoryCapWarningLog {
+                    event_id: id as u64,
+                    category,
+                    cap: U128(cap),
+                });
+            }
+        }
     }
 
-    // Method to retrieve a list of all events
+    // Method to retrieve a list of all published events
     pub fn list_events(&self) -> Vec<Event> {
-        // Return a copy of the events vector
-        self.events.to_vec()
+        // Return a copy of every published event that has not been deleted
+        self.visible_events().cloned().collect()
     }
 
-    // Method to get the count of events
-    pub fn event_count(&mut self) -> usize {
-        // Return the length of the events vector
-        self.events.len()
+    // Method to export the published events as CSV for spreadsheets, one row per event after a header
+    pub fn export_csv(&self) -> String {
+        let mut csv = String::from("id,creator,title,total_votes,estimated_budget\n");
+        for event in self.visible_events() {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                event.id,
+                event.creator,
+                csv_field(&event.title),
+                event.total_votes,
+                event.estimated_budget
+            ));
+        }
+        csv
     }
 
-    // Method to add a vote for a specific event
-    pub fn add_vote(&mut self, id: usize) {
-        // Get a mutable reference to the event by its ID
-        let event: &mut Event = self.events.get_mut(id).unwrap();
+    // Method to list the caller's events that are still drafts. Like get_my_event it is a change method,
+    // as a view call has no caller to check.
+    pub fn list_my_drafts(&mut self) -> Vec<Event> {
+        let caller = env::predecessor_account_id();
+        self.active_events()
+            .filter(|event| event.draft && event.creator == caller)
+            .cloned()
+            .collect()
+    }
 
-        // Get the account ID of the caller
-        let voter = env::predecessor_account_id();
+    // Method to list the caller's own events, drafts included. Like get_my_event it is a change method,
+    // as a view call has no caller to check.
+    pub fn my_events(&mut self) -> Vec<Event> {
+        let caller = env::predecessor_account_id();
+        self.active_events()
+            .filter(|event| event.creator == caller)
+            .cloned()
+            .collect()
+    }
 
-        // Increment the total votes for the event
-        event.total_votes += 1;
+    // Method to publish a draft event, only callable by its creator; votes are accepted from then on
+    pub fn publish_event(&mut self, id: usize) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
 
-        // Log a message indicating that the vote has been successfully submitted
-        env::log_str("Vote submitted successfully for this event!");
+        // Only the creator of the event may publish it
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can publish the event"
+        );
+        assert!(event.draft, "Event is already published");
 
-        // Add the voter's account ID to the list of votes for the event
-        event.votes.push(voter.to_string());
+        event.draft = false;
+        self.record_change();
     }
 
-    // Method to get the total number of votes for a specific event
-    pub fn get_total_votes(&mut self, id: usize) -> u64 {
-        // Get a mutable reference to the event by its ID
-        let event: &mut Event = self.events.get_mut(id).unwrap();
+    // Method to get a single published event, with only the most recent voters inline. Creators
+    // read their drafts through get_my_event, as a view call has no caller to check.
+    pub fn get_event(&self, id: usize) -> EventView {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+        if event.draft {
+            env::panic_str(&VoteError::EventIsDraft.message());
+        }
 
-        // Return the total number of votes for the event
-        event.total_votes.try_into().unwrap()
+        // Build the view with the configured number of inline voters
+        EventView::from_event(event, self.config.max_inline_voters as usize)
     }
-}
 
-/*
- * The rest of this file holds the inline tests for the code above
- * Learn more about Rust tests: https://doc.rust-lang.org/book/ch11-01-writing-tests.html
- */
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::testing_env;
+    // Method for a creator to read one of their own events, drafts included. It is a change method
+    // so the caller can be checked against the creator.
+    pub fn get_my_event(&mut self, id: usize) -> EventView {
+        let event: &Event = self.event(id);
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can read the event through get_my_event"
+        );
+        EventView::from_event(event, self.config.max_inline_voters as usize)
+    }
 
-    // Function to set up the testing context and unit test environment
-    fn get_context(predecessor: AccountId) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder.predecessor_account_id(predecessor);
-        builder
+    // Method to tell whether an ID belongs to a usable, deleted, purged or never created event.
+    // IDs are positions in the event list, which only grows, so an ID is never given to another event.
+    pub fn get_event_state(&self, id: usize) -> EventLocation {
+        match self.events.get(id).map(|event| event.status) {
+            Some(EventStatus::Open) => EventLocation::Active,
+            Some(EventStatus::Deleted) => EventLocation::Deleted,
+            Some(EventStatus::Purged) => EventLocation::Purged,
+            None => EventLocation::Unknown,
+        }
     }
 
-    // Unit test to add a project
-    #[test]
+    // Method to check cheaply whether an event exists; deleted and purged events do not
+    pub fn event_exists(&self, id: usize) -> bool {
+        self.get_event_state(id) == EventLocation::Active
+    }
+
+    // Method to get the off-chain governance proposal an event links to, if any
+    pub fn get_proposal_url(&self, id: usize) -> Option<String> {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        event.proposal_url.clone()
+    }
+
+    // Method to page through all voters of an event, oldest first
+    pub fn get_voters(&self, id: usize, from_index: u64, limit: u64) -> Vec<String> {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        // Return the requested page of voters
+        event
+            .votes
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Method to get the Merkle root of an event's vote receipts, if it has any. Leaves are appended as votes
+    // are cast and never removed, so once voting closes the root covers every vote the event received.
+    pub fn get_vote_merkle_root(&self, id: usize) -> Option<Base64VecU8> {
+        let event: &Event = self.event(id);
+        event.vote_merkle_root.map(|root| Base64VecU8(root.to_vec()))
+    }
+
+    // Method to get a leaf of an event's vote Merkle tree, for building inclusion proofs off-chain
+    pub fn get_vote_leaf(&self, id: usize, index: u64) -> Option<VoteLeaf> {
+        // Deleted and purged events have no leaves to show
+        self.event(id);
+        let receipt = self.vote_receipts.get(&(id as u64, index))?;
+        let hash = vote_leaf_hash(&receipt.voter, id as u64, receipt.cast_at);
+        Some(VoteLeaf {
+            index,
+            voter: receipt.voter,
+            cast_at: receipt.cast_at,
+            hash: Base64VecU8(hash.to_vec()),
+        })
+    }
+
+    // Method to get the most recently created event, if any
+    pub fn latest_event(&self) -> Option<Event> {
+        // Order by creation time, breaking ties in the same block by the higher ID
+        self.visible_events()
+            .max_by_key(|event| (event.created_at, event.id))
+            .cloned()
+    }
+
+    // Method to get up to `limit` events ordered by votes plus a bonus for recent creation.
+    // Each event scores total_votes + FEED_RECENCY_BONUS minus its age in whole days (never below 0).
+    pub fn ranked_feed(&self, limit: usize) -> Vec<Event> {
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let now = env::block_timestamp();
+        let score = |event: &Event| {
+            let age_days = now.saturating_sub(event.created_at) / day;
+            event.total_votes.max(0) as u64 + FEED_RECENCY_BONUS.saturating_sub(age_days)
+        };
+
+        // Highest score first, breaking ties by the newer event and then the higher ID
+        let mut feed: Vec<&Event> = self.visible_events().collect();
+        feed.sort_by_key(|event| std::cmp::Reverse((score(event), event.created_at, event.id)));
+        feed.into_iter().take(limit).cloned().collect()
+    }
+
+    // Method to get the count of published events that have not been deleted
+    pub fn event_count(&mut self) -> usize {
+        self.visible_events().count()
+    }
+
+    // Method to add a vote for a specific event, optionally answering its survey questions.
+    // `answers` holds one choice index per question, in question order.
+    #[payable]
+    pub fn add_vote(&mut self, id: usize, answers: Option<Vec<u8>>) {
+        // Cast the vote for the caller, panicking with the reason if it is rejected
+        let voter = env::predecessor_account_id();
+        if let Err(error) = self.internal_add_vote(id, voter, Method::AddVote, answers) {
+            env::panic_str(&error.message());
+        }
+    }
+
+    // Method to add a vote for a specific event, returning the reason instead of panicking.
+    // On-chain an Err still fails the call with the reason; Rust callers get the value.
+    #[payable]
+    #[handle_result]
+    pub fn try_add_vote(&mut self, id: usize) -> Result<(), String> {
+        self.internal_add_vote(id, env::predecessor_account_id(), Method::TryAddVote, None)
+            .map_err(|error| error.message())
+    }
+
+    // Method for a sub-account to vote on behalf of its parent account (e.g. alice.dao.near for dao.near).
+    // The vote, duplicate check and any fee refund are all attributed to the parent account.
+    #[payable]
+    pub fn add_vote_as(&mut self, id: usize, on_behalf_of: AccountId) {
+        // Get the account ID of the caller
+        let caller = env::predecessor_account_id();
+
+        // Only a direct sub-account may vote for its parent
+        assert!(
+            is_direct_sub_account(&caller, &on_behalf_of),
+            "Caller is not a direct sub-account of the account it votes for"
+        );
+
+        // Cast the vote for the parent, panicking with the reason if it is rejected.
+        // The logged vote names both the attributed parent and the acting sub-account.
+        if let Err(error) = self.internal_add_vote(id, on_behalf_of, Method::AddVoteAs, None) {
+            env::panic_str(&error.message());
+        }
+    }
+
+    // Method to vote for several events at once, returning whether the vote on each was cast.
+    // Events the caller cannot vote for, e.g. because they already did, are skipped rather than failing the batch.
+    pub fn add_votes_batch(&mut self, ids: Vec<usize>) -> Vec<(usize, bool)> {
+        assert!(
+            ids.len() <= MAX_BATCH_VOTES,
+            "Cannot vote for more than {} events at once",
+            MAX_BATCH_VOTES
+        );
+
+        // A deposit cannot be split across the votes, so batches only work while voting is free
+        assert_eq!(
+            self.required_deposit(Method::AddVote),
+            0,
+            "Batch voting is unavailable while votes require a deposit"
+        );
+
+        // Cast each vote for the caller, recording whether it was accepted
+        let voter = env::predecessor_account_id();
+        ids.into_iter()
+            .map(|id| {
+                let cast = self
+                    .internal_add_vote(id, voter.clone(), Method::AddVotesBatch, None)
+                    .is_ok();
+                (id, cast)
+            })
+            .collect()
+    }
+
+    // Method to remove the caller's vote from an event and refund the fee they paid
+    pub fn remove_vote(&mut self, id: usize) {
+        // Get the account ID of the caller
+        let voter = env::predecessor_account_id();
+
+        // Only a vote cast on an event still taking votes can be removed
+        if let Some(reason) = self.remove_vote_violations(id, &voter).first() {
+            env::panic_str(reason);
+        }
+
+        // Find the caller's vote in the list of votes for the event
+        let position = self.events[id]
+            .votes
+            .iter()
+            .position(|v| is_same_voter(v, &voter))
+            .expect("You have not voted for this event");
+
+        // Remove the vote and decrement the total votes for the event by its recorded weight
+        let storage_before = env::storage_usage();
+        self.voted_at.remove(&(id as u64, voter.clone()));
+        self.unindex_vote(&voter, id as u64);
+        let weight = self.vote_weights.remove(&(id as u64, voter.clone())).unwrap_or(1);
+        let event: &mut Event = self.event_mut(id);
+        let removed = event.votes.remove(position);
+        event.total_votes -= 1;
+        event.weighted_total = event.weighted_total.saturating_sub(weight as u64);
+        self.total_votes_cast = self.total_votes_cast.saturating_sub(1);
+
+        // Clear the fee record before refunding so it can never be paid out twice
+        let refund = self.vote_fees.remove(&(id as u64, voter.clone())).unwrap_or(0);
+
+        // The records removed above free their bytes right away, but the root state only shrinks when it is
+        // written back at the end of the call, so add the Borsh size of the dropped voter entry
+        let entry_bytes = 4 + removed.len() as u64;
+        let storage_freed_bytes = storage_before.saturating_sub(env::storage_usage()) + entry_bytes;
+
+        // Log the removed vote for indexers
+        self.log_change(&VoteRemovedLog {
+            event_id: id as u64,
+            voter: voter.clone(),
+            refund: U128(refund),
+            storage_freed_bytes,
+        });
+
+        // Refund the fee the voter paid, if any
+        if refund > 0 {
+            let escrow = LedgerAccount::Escrow(id as u64);
+            self.send_transfer(voter, refund, escrow, id as u64, "vote_refund");
+        }
+    }
+
+    // Method for the caller to move their vote from one event to another, e.g. after voting on the wrong one.
+    // The target must accept the caller's vote right now; any fee paid moves with the vote.
+    pub fn migrate_my_vote(&mut self, from_id: usize, to_id: usize) {
+        // Get the account ID of the caller
+        let voter = env::predecessor_account_id();
+        assert_ne!(from_id, to_id, "The vote is already on this event");
+
+        // Find the caller's vote on the source event
+        let position = self
+            .event(from_id)
+            .votes
+            .iter()
+            .position(|v| is_same_voter(v, &voter))
+            .expect("You have not voted for this event");
+        self.assert_votes_changeable(from_id);
+
+        // The target event must accept a new vote from the caller
+        if let Some(error) = self.vote_violations(to_id, &voter).first() {
+            env::panic_str(&error.message());
+        }
+
+        // Moving attaches nothing, so it cannot prove the balance a target event asks of its voters
+        assert!(
+            self.event(to_id).min_voter_balance.is_none(),
+            "Votes cannot be moved onto an event with a minimum voter balance; vote on it directly"
+        );
+
+        // Take the vote and its records off the source event
+        let from_key = (from_id as u64, voter.clone());
+        let from_weight = self.vote_weights.remove(&from_key).unwrap_or(1);
+        let fee = self.vote_fees.remove(&from_key).unwrap_or(0);
+        self.voted_at.remove(&from_key);
+        self.unindex_vote(&voter, from_id as u64);
+        let source: &mut Event = self.event_mut(from_id);
+        source.votes.remove(position);
+        source.total_votes -= 1;
+        source.weighted_total = source.weighted_total.saturating_sub(from_weight as u64);
+
+        // Add it to the target event, weighed by the target's own rules
+        let now = env::block_timestamp();
+        let weight = if self.event(to_id).loyalty_weighted {
+            self.loyalty_multiplier(&voter, now)
+        } else {
+            1
+        };
+        let target: &mut Event = self.event_mut(to_id);
+        target.votes.push(voter.to_string());
+        target.total_votes += 1;
+        target.weighted_total += weight as u64;
+        self.unlock_funding_if_reached(to_id);
+
+        // Move the records along with the vote
+        let to_key = (to_id as u64, voter.clone());
+        if fee > 0 {
+            self.vote_fees.insert(&to_key, &fee);
+            self.transfer_internal(
+                LedgerAccount::Escrow(from_id as u64),
+                LedgerAccount::Escrow(to_id as u64),
+                fee,
+                "vote_migrated",
+            );
+        }
+        if weight > 1 {
+            self.vote_weights.insert(&to_key, &weight);
+        }
+        self.voted_at.insert(&to_key, &now);
+        self.index_vote(&voter, to_id as u64);
+        self.record_vote_receipt(to_id, &voter, now);
+
+        // Log the move for indexers
+        self.log_change(&VoteMigratedLog {
+            voter,
+            from_event_id: from_id as u64,
+            to_event_id: to_id as u64,
+            weight,
+        });
+    }
+
+    // Method to donate the attached deposit to an event once its funding is unlocked
+    #[payable]
+    pub fn donate(&mut self, id: usize) {
+        // Get the donor and the amount they attached
+        let donor = env::predecessor_account_id();
+        let amount = env::attached_deposit().as_yoctonear();
+        assert!(
+            amount >= self.required_deposit(Method::Donate),
+            "Donation must be positive"
+        );
+
+        // Look up what the donor has already given to this event
+        let key = (id as u64, donor.clone());
+        let previous = self.donations.get(&key).unwrap_or(0);
+
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Donations wait until the event has enough votes
+        if !event.funding_unlocked {
+            let needed = event
+                .min_votes_for_funding
+                .unwrap_or(0)
+                .saturating_sub(event.total_votes.max(0) as u64);
+            env::panic_str(&format!("Funding opens after {} more votes", needed));
+        }
+
+        // A capped event takes no more than its budget; the overflow mode refunds the rest below
+        let capacity = event.remaining_capacity();
+        let overflow = match (event.funding_cap, capacity) {
+            (FundingCap::HardCapWithOverflowRefund, Some(remaining)) if remaining > 0 => {
+                amount.saturating_sub(remaining)
+            }
+            (_, Some(remaining)) => {
+                assert!(
+                    amount <= remaining,
+                    "Donation exceeds the remaining capacity of {} yoctoNEAR",
+                    remaining
+                );
+                0
+            }
+            (_, None) => 0,
+        };
+        let amount = amount - overflow;
+
+        // Draw the sponsor's match, never more than the donation, what is left of the match, or the event's room
+        let mut sponsor_match = self.donation_matches.get(&(id as u64));
+        let room = capacity.map_or(u128::MAX, |remaining| remaining - amount);
+        let matched = sponsor_match.as_ref().map_or(0, |m| amount.min(m.remaining.0).min(room));
+
+        // Add the donation and the match to the event and the donor's running total
+        let event: &mut Event = self.event_mut(id);
+        let was_funded = event.funds_raised >= event.estimated_budget;
+        event.funds_raised += amount + matched;
+        if previous == 0 {
+            event.donors.push(donor.to_string());
+        }
+        let funded = (!was_funded && event.funds_raised >= event.estimated_budget)
+            .then_some(EventFundedLog {
+                event_id: id as u64,
+                funds_raised: U128(event.funds_raised),
+            });
+        self.total_raised += amount + matched;
+        self.donations.insert(&key, &(previous + amount));
+        self.transfer_internal(
+            LedgerAccount::External,
+            LedgerAccount::EventFunding(id as u64),
+            amount,
+            "donation",
+        );
+
+        // Credit the match to the sponsor as their own donation, so refunds return it to them
+        let matched_by = match sponsor_match.as_mut() {
+            Some(sponsor_match) if matched > 0 => {
+                sponsor_match.remaining = U128(sponsor_match.remaining.0 - matched);
+                self.donation_matches.insert(&(id as u64), sponsor_match);
+                let sponsor = sponsor_match.sponsor.clone();
+                let sponsor_key = (id as u64, sponsor.clone());
+                let sponsored = self.donations.get(&sponsor_key).unwrap_or(0);
+                if sponsored == 0 {
+                    self.events[id].donors.push(sponsor.to_string());
+                }
+                self.donations.insert(&sponsor_key, &(sponsored + matched));
+                self.transfer_internal(
+                    LedgerAccount::MatchEscrow(id as u64),
+                    LedgerAccount::EventFunding(id as u64),
+                    matched,
+                    "donation_match",
+                );
+                Some(sponsor)
+            }
+            _ => None,
+        };
+
+        // Return the part of the donation beyond the budget to the donor
+        if overflow > 0 {
+            let source = LedgerAccount::External;
+            self.send_transfer(donor.clone(), overflow, source, id as u64, "donation_overflow");
+        }
+
+        // Log the donation for indexers
+        self.log_change(&DonationReceivedLog {
+            event_id: id as u64,
+            donor,
+            amount: U128(amount),
+            matched: U128(matched),
+            matched_by,
+        });
+
+        // Notify the relay when this donation completes the budget
+        if let Some(log) = funded {
+            self.notify(&log);
+        }
+    }
+
+    // Method for a sponsor to match donations to an event one-to-one, escrowing the attached deposit as the cap
+    #[payable]
+    pub fn create_match(&mut self, id: usize, cap: U128) {
+        let sponsor = env::predecessor_account_id();
+        assert!(
+            cap.0 >= self.required_deposit(Method::CreateMatch),
+            "Match cap must be positive"
+        );
+        assert_eq!(
+            env::attached_deposit().as_yoctonear(),
+            cap.0,
+            "Attach exactly the match cap"
+        );
+
+        // The event must still be taking donations and have no match yet
+        let now = env::block_timestamp();
+        let event: &Event = self.event(id);
+        assert!(event.is_open(now) && !event.funded, "Event is closed");
+        assert!(
+            self.donation_matches.get(&(id as u64)).is_none(),
+            "Event already has a donation match"
+        );
+
+        self.transfer_internal(
+            LedgerAccount::External,
+            LedgerAccount::MatchEscrow(id as u64),
+            cap.0,
+            "match_escrow",
+        );
+        let donation_match = DonationMatch {
+            sponsor,
+            cap,
+            remaining: cap,
+        };
+        self.donation_matches.insert(&(id as u64), &donation_match);
+        self.record_change();
+    }
+
+    // Method to get the donation match of an event, if a sponsor created one
+    pub fn get_match(&self, id: usize) -> Option<DonationMatch> {
+        self.donation_matches.get(&(id as u64))
+    }
+
+    // Method for the sponsor to take back the unused part of their match once the event is closed,
+    // i.e. past its deadline, funded or deleted
+    pub fn reclaim_match(&mut self, id: usize) {
+        let mut donation_match = self
+            .donation_matches
+            .get(&(id as u64))
+            .expect("Event has no donation match");
+        assert_eq!(
+            donation_match.sponsor,
+            env::predecessor_account_id(),
+            "Only the sponsor can reclaim the match"
+        );
+        let event: &Event = self.events.get(id).expect("Event not found");
+        let now = env::block_timestamp();
+        let closed = !event.is_visible() || !event.is_open(now) || event.funded;
+        assert!(closed, "Event is still open");
+
+        let remaining = donation_match.remaining.0;
+        assert!(remaining > 0, "Nothing left to reclaim");
+        donation_match.remaining = U128(0);
+        self.donation_matches.insert(&(id as u64), &donation_match);
+        let escrow = LedgerAccount::MatchEscrow(id as u64);
+        let sponsor = donation_match.sponsor;
+        self.send_transfer(sponsor, remaining, escrow, id as u64, "match_reclaim");
+        self.record_change();
+    }
+
+    // Method to report every reason `account` could not vote for an event right now, without voting.
+    // The deposit is not checked; wallets read it from get_vote_requirements and attach it.
+    pub fn dry_run_vote(&self, id: usize, account: AccountId) -> VoteCheck {
+        let violations = self.vote_violations(id, &account);
+        VoteCheck {
+            allowed: violations.is_empty(),
+            violations,
+        }
+    }
+
+    // Method to report which actions `account` may take on an event now, so clients need not copy the rules.
+    // Each flag comes from the same checks the matching method runs; attached deposits are not considered.
+    pub fn get_permissions(&self, id: usize, account: AccountId) -> EventPermissions {
+        let mut reasons = Vec::new();
+        let mut check = |action: &str, violations: Vec<String>| {
+            let allowed = violations.is_empty();
+            reasons.extend(violations.into_iter().map(|reason| format!("{}: {}", action, reason)));
+            allowed
+        };
+        let vote_violations = self.vote_violations(id, &account);
+        let can_vote = check("vote", vote_violations.iter().map(VoteError::message).collect());
+        let can_edit = check("edit", self.edit_violations(id, &account));
+        let can_close = check("close", self.deadline_violations(id, &account));
+        let can_withdraw = check("withdraw", self.remove_vote_violations(id, &account));
+        let can_comment = check("comment", vec!["Events do not take comments".to_string()]);
+        EventPermissions {
+            can_vote,
+            can_edit,
+            can_close,
+            can_withdraw,
+            can_comment,
+            reasons,
+        }
+    }
+
+    // Method to report every reason `creator` could not add an event right now, without adding it
+    pub fn dry_run_add_event(
+        &self,
+        creator: AccountId,
+        title: String,
+        estimated_budget: U128,
+        invite_code: Option<String>,
+    ) -> CreateCheck {
+        let violations = self.add_event_violations(
+            &creator,
+            &title,
+            estimated_budget.0,
+            invite_code.as_deref(),
+        );
+        CreateCheck {
+            allowed: violations.is_empty(),
+            violations,
+        }
+    }
+
+    // Method to get the total number of votes for a specific event
+    pub fn get_total_votes(&mut self, id: usize) -> u64 {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Return the total number of votes for the event
+        event.total_votes.try_into().unwrap()
+    }
+
+    // Method to edit an event, only callable by its creator; omitted fields are left unchanged
+    pub fn edit_event(
+        &mut self,
+        id: usize,
+        title: Option<String>,
+        estimated_budget: Option<U128>,
+        description: Option<String>,
+    ) {
+        // Validate the new values before touching the event
+        let estimated_budget = estimated_budget.map(|budget| budget.0);
+        let title = title.map(|t| sanitize_line("Title", &t, MAX_TITLE_CHARS));
+        if let Some(rule) = title.as_ref().and_then(|t| self.title_policy_violation(t)) {
+            env::panic_str(&rule.message());
+        }
+        let description =
+            description.map(|d| sanitize_text("Description", &d, MAX_DESCRIPTION_CHARS));
+        if let Some(description) = description.as_ref() {
+            self.assert_description_policy(description);
+        }
+        if let Some(estimated_budget) = estimated_budget {
+            self.assert_valid_budget(estimated_budget);
+        }
+
+        // Only the creator of the event may edit it, and never its budget once locked
+        if let Some(reason) = self.edit_violations(id, &env::predecessor_account_id()).first() {
+            env::panic_str(reason);
+        }
+
+        // Events made from a template must keep their budget in its range, even if it was deactivated since
+        let template_id = self.events[id].template_id;
+        let template = template_id.and_then(|template_id| self.templates.get(template_id as usize));
+        if let (Some(template), Some(estimated_budget)) = (template, estimated_budget) {
+            let template = &template.template;
+            assert!(
+                (template.min_budget..=template.max_budget).contains(&estimated_budget),
+                "Budget must be between {} and {} for this template",
+                template.min_budget,
+                template.max_budget
+            );
+        }
+
+        let event: &mut Event = self.event_mut(id);
+        assert!(
+            estimated_budget.is_none() || !event.budget_locked,
+            "Budget is locked for this event"
+        );
+
+        // Apply the provided changes, keeping the replaced title to re-index the event below
+        let retitled = title.map(|title| {
+            let previous = std::mem::replace(&mut event.title, title.clone());
+            (previous, title)
+        });
+        if let Some(description) = description {
+            event.description = description;
+        }
+        if let Some(estimated_budget) = estimated_budget {
+            event.revise_budget(estimated_budget, env::block_timestamp());
+        }
+
+        // Log the edit for indexers
+        let log = EventEditedLog {
+            event_id: id as u64,
+            editor: event.creator.clone(),
+            estimated_budget: U128(event.estimated_budget),
+        };
+        if let Some((previous, title)) = retitled {
+            self.unindex_title(id as u64, &previous);
+            self.index_title(id as u64, &title);
+        }
+        self.log_change(&log);
+    }
+
+    // Method for the creator or the owner to lock an event's budget, e.g. once funding is committed.
+    // Other fields stay editable; the lock cannot be lifted.
+    pub fn lock_budget(&mut self, id: usize) {
+        let caller = env::predecessor_account_id();
+        let owner = self.owner.clone();
+
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event or the owner may lock its budget
+        assert!(
+            caller == event.creator || caller == owner,
+            "Only the event creator or the owner can lock the budget"
+        );
+
+        event.budget_locked = true;
+        self.record_change();
+    }
+
+    // Method to get the budget revisions of an event, oldest first
+    pub fn get_budget_history(&self, id: usize) -> Vec<(U64, U128)> {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        event
+            .budget_history
+            .iter()
+            .map(|&(at, budget)| (U64(at), U128(budget)))
+            .collect()
+    }
+
+    // Method to turn loyalty weighting on or off for an event, only callable by its creator.
+    // Votes already cast keep the weight they were cast with.
+    pub fn set_loyalty_weighted(&mut self, id: usize, loyalty_weighted: bool) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may change its weighting
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set loyalty weighting"
+        );
+
+        event.loyalty_weighted = loyalty_weighted;
+        self.record_change();
+    }
+
+    // Method for the creator to restrict voting to accounts holding at least `min_voter_balance` yoctoNEAR.
+    // Voters prove it by attaching that much to add_vote, which is refunded in the same call.
+    pub fn set_min_voter_balance(&mut self, id: usize, min_voter_balance: Option<U128>) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may restrict who votes on it
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set the minimum voter balance"
+        );
+
+        event.min_voter_balance = min_voter_balance.map(|balance| balance.0);
+        self.record_change();
+    }
+
+    // Method for the creator to choose whether donations may exceed the estimated budget
+    pub fn set_funding_cap(&mut self, id: usize, funding_cap: FundingCap) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may cap its donations
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set the funding cap"
+        );
+
+        event.funding_cap = funding_cap;
+        self.record_change();
+    }
+
+    // Method for the creator to claim a unique short code for linking to their event, e.g. ART24.
+    // Codes are case-insensitive and stored uppercase; claiming a new code releases the previous one.
+    pub fn set_short_code(&mut self, id: usize, code: String) {
+        let code = normalize_short_code(&code);
+        assert!(!self.reserved_codes.contains(&code), "Short code is reserved");
+        if let Some(owner) = self.short_codes.get(&code) {
+            assert_eq!(owner, id as u64, "Short code is already taken");
+        }
+
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may link it
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set the short code"
+        );
+
+        // Swap the event's code, then update the index
+        let previous = event.short_code.replace(code.clone());
+        if let Some(previous) = previous.filter(|previous| *previous != code) {
+            self.short_codes.remove(&previous);
+        }
+        self.short_codes.insert(&code, &(id as u64));
+        self.record_change();
+    }
+
+    // Method to look up the event a short code links to, ignoring case
+    pub fn resolve_short_code(&self, code: String) -> Option<u64> {
+        self.short_codes.get(&code.trim().to_uppercase())
+    }
+
+    // Method for the caller to register explicitly, starting their loyalty clock.
+    // Returns the registration time, which is unchanged if they were already registered.
+    pub fn register_voter(&mut self) -> Timestamp {
+        let caller = env::predecessor_account_id();
+        let new = !self.registered_at.contains_key(&caller);
+        let registered_at = self.internal_register(&caller);
+        if new {
+            self.record_change();
+        }
+        registered_at
+    }
+
+    // Method to get when an account first interacted with the contract, if it has
+    pub fn get_registered_at(&self, account_id: AccountId) -> Option<Timestamp> {
+        self.registered_at.get(&account_id)
+    }
+
+    // Method to export what the contract stores about an account, for data access requests.
+    // Per-event records are paged by event ID: pass next_from_event back as from_event until it is None.
+    pub fn export_account_data(
+        &self,
+        account: AccountId,
+        from_event: u64,
+        limit: u64,
+    ) -> AccountExport {
+        // Work out the page of events to scan, clamped to the events that exist
+        let start = (from_event as usize).min(self.events.len());
+        let end = start.saturating_add(limit as usize).min(self.events.len());
+
+        let mut export = AccountExport {
+            account_id: account.clone(),
+            registered_at: self.registered_at.get(&account),
+            last_event_at: self.last_event_at.get(&account),
+            is_creator: self.creators.contains(&account),
+            created_events: vec![],
+            votes: vec![],
+            donations: vec![],
+            next_from_event: (end < self.events.len()).then_some(end as u64),
+        };
+
+        // Collect the account's records on each event of the page, deleted events included
+        for event in &self.events[start..end] {
+            let event_id = event.id as u64;
+            let key = (event_id, account.clone());
+            if event.creator == account {
+                export.created_events.push(event_id);
+            }
+            if event.votes.iter().any(|v| is_same_voter(v, &account)) {
+                export.votes.push(AccountVote {
+                    event_id,
+                    cast_at: self.voted_at.get(&key),
+                    fee: U128(self.vote_fees.get(&key).unwrap_or(0)),
+                    weight: self.vote_weights.get(&key).unwrap_or(1),
+                });
+            }
+            if let Some(amount) = self.donations.get(&key) {
+                export.donations.push(AccountDonation {
+                    event_id,
+                    amount: U128(amount),
+                });
+            }
+        }
+        export
+    }
+
+    // Method for the caller to save the defaults add_event uses for the arguments they omit.
+    // Saving them for the first time costs the storage of their record, held until they are removed;
+    // the rest of the deposit is refunded.
+    #[payable]
+    pub fn set_creator_defaults(&mut self, defaults: CreatorDefaults) {
+        let caller = env::predecessor_account_id();
+        let storage_cost = match self.creator_defaults.contains_key(&caller) {
+            true => 0,
+            false => self.required_deposit(Method::SetCreatorDefaults),
+        };
+        let deposit = env::attached_deposit().as_yoctonear();
+        assert!(
+            deposit >= storage_cost,
+            "Attach at least {} yoctoNEAR to cover storage",
+            storage_cost
+        );
+        self.creator_defaults.insert(&caller, &defaults);
+        if storage_cost > 0 {
+            let held = LedgerAccount::StorageDeposit(caller.clone());
+            let source = LedgerAccount::External;
+            self.transfer_internal(source, held, storage_cost, "storage_deposit");
+        }
+        if deposit > storage_cost {
+            let surplus = deposit - storage_cost;
+            let source = LedgerAccount::External;
+            self.send_transfer(caller, surplus, source, NO_EVENT, "storage_overpayment");
+        }
+        self.record_change();
+    }
+
+    // Method for the caller to delete their saved defaults, refunding the storage deposit held for them.
+    // Returns whether there were defaults to delete.
+    pub fn remove_creator_defaults(&mut self) -> bool {
+        let removed = self.internal_remove_creator_defaults(&env::predecessor_account_id());
+        if removed {
+            self.record_change();
+        }
+        removed
+    }
+
+    // Method to get the defaults an account saved for add_event, if any
+    pub fn get_creator_defaults(&self, account: AccountId) -> Option<CreatorDefaults> {
+        self.creator_defaults.get(&account)
+    }
+
+    // Method for the caller to erase the personal records that nothing else depends on: their registration
+    // time, so loyalty tiers count again from their next interaction, and their creator defaults, whose storage
+    // deposit is refunded. Votes, donations, vote fees and the events they created are kept, since tallies,
+    // refunds and audits depend on them, as is the time of their last event, which enforces the creation
+    // cooldown. Returns whether anything was erased.
+    pub fn erase_profile(&mut self) -> bool {
+        let caller = env::predecessor_account_id();
+        let registered = self.registered_at.remove(&caller).is_some();
+        let defaults = self.internal_remove_creator_defaults(&caller);
+        let erased = registered || defaults;
+        if erased {
+            self.record_change();
+        }
+        erased
+    }
+
+    // Method to replace the tags of an event, only callable by its creator
+    pub fn set_event_tags(&mut self, id: usize, tags: Vec<String>) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may change its tags
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set tags"
+        );
+
+        // Replace the tags of the event with their cleaned up versions
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|tag| sanitize_line("Tag", tag, MAX_TAG_CHARS))
+            .collect();
+
+        // Events made from a template must keep its required tags, even if it was deactivated since
+        if let Some(template) = event.template_id.and_then(|id| self.templates.get(id as usize)) {
+            assert!(
+                template.template.required_tags.iter().all(|tag| tags.contains(tag)),
+                "Tags must include the template's required tags"
+            );
+        }
+
+        self.event_mut(id).tags = tags;
+        self.record_change();
+    }
+
+    // Method to define the survey questions of an event, only callable by its creator before anyone votes
+    pub fn set_event_questions(&mut self, id: usize, questions: Vec<SurveyQuestion>) {
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator of the event may change its questions
+        assert_eq!(
+            event.creator,
+            env::predecessor_account_id(),
+            "Only the event creator can set questions"
+        );
+
+        // Existing tallies would no longer match the new questions
+        assert!(
+            event.votes.is_empty(),
+            "Questions cannot change after voting has started"
+        );
+
+        // Keep the survey small and its strings clean
+        assert!(
+            questions.len() <= MAX_QUESTIONS,
+            "An event cannot have more than {} questions",
+            MAX_QUESTIONS
+        );
+        let questions: Vec<SurveyQuestion> = questions
+            .iter()
+            .map(|question| {
+                assert!(
+                    !question.choices.is_empty() && question.choices.len() <= MAX_CHOICES,
+                    "A question must have between 1 and {} choices",
+                    MAX_CHOICES
+                );
+                SurveyQuestion {
+                    prompt: sanitize_line("Question", &question.prompt, MAX_QUESTION_CHARS),
+                    choices: question
+                        .choices
+                        .iter()
+                        .map(|choice| sanitize_line("Choice", choice, MAX_CHOICE_CHARS))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        // Start every choice of every question at zero
+        event.answer_tallies = questions.iter().map(|q| vec![0; q.choices.len()]).collect();
+        event.questions = questions;
+        self.record_change();
+    }
+
+    // Method to get the aggregate answers to an event's survey questions.
+    // Only totals are stored, so removing a vote does not remove its answers.
+    pub fn get_survey_results(&self, id: usize) -> Vec<SurveyResult> {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        event
+            .questions
+            .iter()
+            .zip(event.answer_tallies.iter())
+            .map(|(question, tallies)| SurveyResult {
+                prompt: question.prompt.clone(),
+                choices: question.choices.clone(),
+                tallies: tallies.clone(),
+            })
+            .collect()
+    }
+
+    // Method to set the voting deadline of an event still taking votes. The creator may set it once;
+    // only the owner may replace a deadline already set.
+    pub fn set_voting_deadline(&mut self, id: usize, voting_deadline: Timestamp) {
+        let max_period = self.config.max_voting_period_secs;
+
+        // Only the creator or the owner may set the deadline, and never on a closed event
+        if let Some(reason) = self.deadline_violations(id, &env::predecessor_account_id()).first() {
+            env::panic_str(reason);
+        }
+        let event: &mut Event = self.event_mut(id);
+
+        // A deadline in the past would close the event immediately
+        assert!(
+            voting_deadline > env::block_timestamp(),
+            "Voting deadline must be in the future"
+        );
+        assert!(
+            voting_deadline - env::block_timestamp() <= max_period.saturating_mul(1_000_000_000),
+            "Voting deadline must be at most {} seconds away",
+            max_period
+        );
+
+        event.voting_deadline = Some(voting_deadline);
+        self.record_change();
+    }
+
+    // Method to push back the voting deadline of an event still taking votes, by at most the configured maximum.
+    // The creator may extend it once; the owner may extend it again.
+    pub fn extend_deadline(&mut self, id: usize, new_deadline: Timestamp) {
+        let caller = env::predecessor_account_id();
+        let is_owner = caller == self.owner;
+        let max_extension = self.config.max_deadline_extension_secs;
+        let now = env::block_timestamp().saturating_sub(self.config.deadline_grace_period_ns);
+
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.event_mut(id);
+
+        // Only the creator, or the owner, may extend a deadline that is still ahead
+        assert!(
+            is_owner || event.creator == caller,
+            "Only the event creator or the owner can extend the deadline"
+        );
+        let deadline = event.voting_deadline.expect("Event has no voting deadline to extend");
+        if !event.is_open(now) || event.funded {
+            env::panic_str(&VoteError::VotingClosed.message());
+        }
+
+        // The new deadline must be later, but not by more than the maximum extension
+        assert!(new_deadline > deadline, "New deadline must be later than the current one");
+        assert!(
+            new_deadline - deadline <= max_extension.saturating_mul(1_000_000_000),
+            "Deadline cannot be extended by more than {} seconds",
+            max_extension
+        );
+
+        // Creators get a single extension
+        assert!(
+            is_owner || !event.deadline_extended,
+            "Deadline has already been extended once"
+        );
+
+        event.voting_deadline = Some(new_deadline);
+        if !is_owner {
+            event.deadline_extended = true;
+        }
+        self.log_change(&DeadlineExtendedLog {
+            event_id: id as u64,
+            extended_by: caller,
+            previous_deadline: deadline,
+            voting_deadline: new_deadline,
+        });
+    }
+
+    // Method to list open events whose voting deadline falls within the next `seconds` seconds
+    pub fn events_closing_within(&self, seconds: u64) -> Vec<Event> {
+        // Compute the end of the window in nanoseconds
+        let now = env::block_timestamp();
+        let window_end = now.saturating_add(seconds.saturating_mul(1_000_000_000));
+
+        // Keep open events whose deadline is inside the window
+        self.visible_events()
+            .filter(|event| event.is_open(now))
+            .filter(|event| event.voting_deadline.is_some_and(|deadline| deadline <= window_end))
+            .cloned()
+            .collect()
+    }
+
+    // Method to find the visible event whose voting deadline passed longest ago, if any
+    pub fn most_overdue_event(&self) -> Option<Event> {
+        let now = env::block_timestamp();
+        self.visible_events()
+            .filter(|event| event.voting_deadline.is_some_and(|deadline| deadline < now))
+            .min_by_key(|event| event.voting_deadline)
+            .cloned()
+    }
+
+    // Method to list the IDs of events whose creator no longer passes account ID validation, e.g. after an import
+    pub fn events_with_invalid_creator(&self) -> Vec<i32> {
+        self.events
+            .iter()
+            .filter(|event| AccountId::validate(event.creator.as_str()).is_err())
+            .map(|event| event.id)
+            .collect()
+    }
+
+    // Method to list the events created since the start of the current UTC day
+    pub fn events_today(&self) -> Vec<Event> {
+        // Block timestamps count nanoseconds since the Unix epoch, which starts at UTC midnight
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let start_of_day = env::block_timestamp() / day * day;
+
+        self.visible_events()
+            .filter(|event| event.created_at >= start_of_day)
+            .cloned()
+            .collect()
+    }
+
+    // Method for the owner to soft-delete an event, hiding it until it is restored or purged
+    pub fn delete_event(&mut self, id: usize) {
+        self.assert_owner();
+        let event: &mut Event = self.event_mut(id);
+        event.status = EventStatus::Deleted;
+        event.deleted_at = Some(env::block_timestamp());
+        let title = event.title.clone();
+
+        // Release the short code so another event can claim it; a restored event must claim one again
+        if let Some(code) = event.short_code.take() {
+            self.short_codes.remove(&code);
+        }
+        self.unindex_title(id as u64, &title);
+
+        self.notify(&StatusChangedLog {
+            event_id: id as u64,
+            status: EventStatus::Deleted,
+        });
+    }
+
+    // Method for the owner to mark an event funded, which stops it accepting votes
+    pub fn mark_funded(&mut self, id: usize) {
+        self.assert_owner();
+        let event: &mut Event = self.event_mut(id);
+
+        // Funds for an approval the auditor has not certified cannot be released
+        assert!(
+            matches!(event.certification, None | Some(Certification::Certified)),
+            "Event result is not certified"
+        );
+
+        event.funded = true;
+        let log = EventFundedLog {
+            event_id: id as u64,
+            funds_raised: U128(event.funds_raised),
+        };
+        self.notify(&log);
+    }
+
+    // Method for the owner to drop repeated voter entries left by legacy data on an event, keeping the first ones.
+    // Entries naming the same account in another case or with spaces count as repeats.
+    // The weighted total is recomputed from the recorded weight of each voter left.
+    pub fn dedup_voters(&mut self, id: usize) {
+        self.assert_owner();
+        let event: &mut Event = self.event_mut(id);
+
+        // Keep the first entry of each voter
+        let mut seen: HashSet<String> = HashSet::new();
+        let before = event.votes.len();
+        event.votes.retain(|v| seen.insert(voter_key(v)));
+        let removed = (before - event.votes.len()) as u64;
+        if removed == 0 {
+            return;
+        }
+
+        // Count what is left, each voter with the weight their vote was recorded with
+        let votes = &self.events[id].votes;
+        let weighted_total: u64 = votes
+            .iter()
+            .map(|v| match normalize_voter(v) {
+                Some(voter) => self.vote_weights.get(&(id as u64, voter)).unwrap_or(1) as u64,
+                None => 1,
+            })
+            .sum();
+        let total_votes = votes.len() as u64;
+        let event: &mut Event = &mut self.events[id];
+        let counted = event.total_votes.max(0) as u64;
+        event.total_votes = total_votes as i64;
+        event.weighted_total = weighted_total;
+        self.total_votes_cast = (self.total_votes_cast + total_votes).saturating_sub(counted);
+        self.log_change(&VotersDedupedLog {
+            event_id: id as u64,
+            removed,
+            total_votes,
+            weighted_total,
+        });
+    }
+
+    // Method for the owner to bring back a deleted event within the restore window
+    pub fn restore_event(&mut self, id: usize) {
+        self.assert_owner();
+        if let Some(error) = self.open_events_violation() {
+            env::panic_str(&error.message());
+        }
+        let restore_window = self.config.restore_window_secs.saturating_mul(1_000_000_000);
+        let event: &mut Event = self.events.get_mut(id).expect("Event not found");
+        assert_eq!(event.status, EventStatus::Deleted, "Event is not deleted");
+        assert_eq!(event.refund_cursor, 0, "Refunds have started for this event");
+        let deleted_at = event.deleted_at.unwrap_or_default();
+        assert!(
+            env::block_timestamp() < deleted_at.saturating_add(restore_window),
+            "Restore window has expired"
+        );
+        event.status = EventStatus::Open;
+        event.deleted_at = None;
+        let title = event.title.clone();
+        self.index_title(id as u64, &title);
+        self.notify(&StatusChangedLog {
+            event_id: id as u64,
+            status: EventStatus::Open,
+        });
+    }
+
+    // Method for anyone to settle up to `limit` more records of a deleted event whose restore window has expired,
+    // resuming where the last call stopped: voters first, refunding any fees and dropping their records and vote
+    // receipts, then donors after the cursor. Each refund is tracked like any outgoing transfer, so a failed one
+    // can be retried. Returns the number of records handled; purge_deleted waits until this returns 0.
+    pub fn process_refunds(&mut self, id: usize, limit: u64) -> u64 {
+        let restore_window = self.config.restore_window_secs.saturating_mul(1_000_000_000);
+        let event: &Event = self.events.get(id).expect("Event not found");
+        assert_eq!(
+            event.status,
+            EventStatus::Deleted,
+            "Refunds are only processed for deleted events"
+        );
+
+        // Inside the window the owner may still restore the event, so nothing is refunded yet
+        let deleted_at = event.deleted_at.unwrap_or_default();
+        assert!(
+            env::block_timestamp() >= deleted_at.saturating_add(restore_window),
+            "Refunds start once the restore window has expired"
+        );
+        let mut budget = limit;
+
+        // Drop voter records from the end, refunding any fees paid
+        while budget > 0 {
+            let Some(voter) = self.events[id].votes.pop() else {
+                break;
+            };
+            budget -= 1;
+            let Ok(voter) = voter.parse::<AccountId>() else {
+                continue;
+            };
+            let key = (id as u64, voter.clone());
+            self.vote_weights.remove(&key);
+            self.voted_at.remove(&key);
+            self.unindex_vote(&voter, id as u64);
+            if let Some(fee) = self.vote_fees.remove(&key) {
+                let escrow = LedgerAccount::Escrow(id as u64);
+                self.send_transfer(voter, fee, escrow, id as u64, "vote_refund");
+            }
+        }
+
+        // Drop the vote receipts behind the Merkle tree, newest first
+        while budget > 0 && self.events[id].vote_leaves > 0 {
+            budget -= 1;
+            let event: &mut Event = &mut self.events[id];
+            event.vote_leaves -= 1;
+            self.vote_receipts.remove(&(id as u64, event.vote_leaves));
+        }
+
+        // Take the next slice of donors after the cursor
+        let event: &Event = &self.events[id];
+        let start = event.refund_cursor as usize;
+        let end = event.donors.len().min(start.saturating_add(budget as usize));
+        let donors: Vec<String> = event.donors[start..end].to_vec();
+        self.events[id].refund_cursor = end as u64;
+        budget -= (end - start) as u64;
+
+        for donor in donors.iter().filter_map(|d| d.parse::<AccountId>().ok()) {
+            if let Some(amount) = self.donations.remove(&(id as u64, donor.clone())) {
+                let event: &mut Event = &mut self.events[id];
+                event.funds_raised = event.funds_raised.saturating_sub(amount);
+                self.total_raised = self.total_raised.saturating_sub(amount);
+                let funding = LedgerAccount::EventFunding(id as u64);
+                self.send_transfer(donor, amount, funding, id as u64, "donation_refund");
+            }
+        }
+
+        // The refunds and dropped records change state the checksum covers
+        let handled = limit - budget;
+        if handled > 0 {
+            self.record_change();
+        }
+        handled
+    }
+
+    // Method to show how far the refunds of a deleted event have got
+    pub fn refund_progress(&self, id: usize) -> RefundProgress {
+        let event: &Event = self.events.get(id).expect("Event not found");
+        let donor_count = event.donors.len() as u64;
+        RefundProgress {
+            event_id: id as u64,
+            donor_count,
+            sent: event.refund_cursor,
+            settled: event.refunds_settled,
+            outstanding: donor_count - event.refunds_settled,
+        }
+    }
+
+    // Method for anyone to permanently remove up to `limit` deleted events whose restore window has expired
+    // and whose refunds process_refunds has finished. Purged events keep their slot so IDs stay stable and
+    // their contents are dropped. Returns the number of events purged.
+    pub fn purge_deleted(&mut self, limit: u32) -> u32 {
+        let now = env::block_timestamp();
+        let restore_window = self.config.restore_window_secs.saturating_mul(1_000_000_000);
+        let expired: Vec<usize> = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.status == EventStatus::Deleted)
+            .filter(|(_, event)| {
+                now >= event.deleted_at.unwrap_or_default().saturating_add(restore_window)
+            })
+            .filter(|(_, event)| event.refunds_finished())
+            .map(|(id, _)| id)
+            .take(limit as usize)
+            .collect();
+
+        for &id in expired.iter() {
+            // Take its votes and donations off the running totals before clearing them
+            let event = &self.events[id];
+            let votes = event.total_votes.max(0) as u64;
+            self.total_votes_cast = self.total_votes_cast.saturating_sub(votes);
+            self.total_raised = self.total_raised.saturating_sub(event.funds_raised);
+            self.events[id].purge();
+            self.notify(&StatusChangedLog {
+                event_id: id as u64,
+                status: EventStatus::Purged,
+            });
+        }
+
+        expired.len() as u32
+    }
+
+    // Method for the owner to set (or clear) the relay account that acknowledges outbox notifications
+    pub fn set_keeper(&mut self, keeper: Option<AccountId>) {
+        self.assert_owner();
+        self.keeper = keeper;
+        self.record_change();
+    }
+
+    // Method for the relay to read up to `limit` queued notifications, starting at `from_id`
+    pub fn peek_outbox(&self, from_id: u64, limit: u64) -> Vec<Notification> {
+        (from_id.max(self.outbox_head)..self.outbox_next)
+            .take(limit as usize)
+            .filter_map(|id| self.outbox.get(&id))
+            .collect()
+    }
+
+    // Method for the keeper to drop every notification up to and including `up_to_id` once relayed.
+    // Returns the number of notifications removed.
+    pub fn ack_outbox(&mut self, up_to_id: u64) -> u64 {
+        assert_eq!(
+            Some(env::predecessor_account_id()),
+            self.keeper,
+            "Only the keeper can acknowledge notifications"
+        );
+        let end = up_to_id.saturating_add(1).min(self.outbox_next);
+        let start = self.outbox_head;
+        for id in start..end {
+            self.outbox.remove(&id);
+        }
+        self.outbox_head = self.outbox_head.max(end);
+        let acknowledged = end.saturating_sub(start);
+        if acknowledged > 0 {
+            self.record_change();
+        }
+        acknowledged
+    }
+
+    // Method to get the number of notifications evicted from a full outbox before being acknowledged
+    pub fn outbox_dropped(&self) -> u64 {
+        self.outbox_dropped
+    }
+
+    // Method for the owner to list the deleted events that can still be restored or purged. It is a
+    // change method, as a view call has no predecessor to check against the owner.
+    pub fn list_deleted(&mut self) -> Vec<Event> {
+        self.assert_owner();
+        self.events
+            .iter()
+            .filter(|event| event.status == EventStatus::Deleted)
+            .cloned()
+            .collect()
+    }
+
+    // Method to search events, combining all provided filters with AND semantics
+    pub fn query_events(
+        &self,
+        creator: Option<AccountId>,
+        tag: Option<String>,
+        min_votes: Option<u64>,
+    ) -> Vec<Event> {
+        // Keep only the events matching every filter that was provided
+        self.visible_events()
+            .filter(|event| creator.as_ref().is_none_or(|c| &event.creator == c))
+            .filter(|event| tag.as_ref().is_none_or(|t| event.tags.contains(t)))
+            .filter(|event| min_votes.is_none_or(|m| event.total_votes >= m as i64))
+            .cloned()
+            .collect()
+    }
+
+    // Method to list a creator's events carrying the given tag
+    pub fn events_by_creator_and_tag(&self, creator: AccountId, tag: String) -> Vec<Event> {
+        self.query_events(Some(creator), Some(tag), None)
+    }
+
+    // Method to get the current platform settings
+    pub fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+
+    // Method to get the default and the hard bounds of every bounded setting, which set_config enforces
+    pub fn get_param_bounds(&self) -> Vec<ParamBound> {
+        params::ALL.iter().map(|param| param.bound()).collect()
+    }
+
+    // Method for the owner to replace all platform settings at once, each checked against its bounds
+    pub fn set_config(&mut self, config: Config) {
+        self.assert_owner();
+        config.assert_valid();
+        self.config = config;
+        self.record_change();
+    }
+
+    // Method for the owner to set how long new events wait before accepting votes, and which creators skip the wait.
+    // Creators registered for at least `exempt_min_age_secs` are exempt; None exempts nobody.
+    pub fn set_vote_grace_period(
+        &mut self,
+        grace_period_ns: u64,
+        exempt_min_age_secs: Option<u64>,
+    ) {
+        self.assert_owner();
+        params::VOTE_GRACE_PERIOD_NS.check(grace_period_ns);
+        if let Some(age) = exempt_min_age_secs {
+            params::GRACE_EXEMPT_MIN_AGE_SECS.check(age);
+        }
+        self.config.vote_grace_period_ns = grace_period_ns;
+        self.config.grace_exempt_min_age_secs = exempt_min_age_secs;
+        self.record_change();
+    }
+
+    // Method for the owner to keep accepting votes for a short time after each voting deadline,
+    // so votes sent just before it are not lost to block-time jitter
+    pub fn set_deadline_grace_period(&mut self, grace_period_ns: u64) {
+        self.assert_owner();
+        params::DEADLINE_GRACE_PERIOD_NS.check(grace_period_ns);
+        self.config.deadline_grace_period_ns = grace_period_ns;
+        self.record_change();
+    }
+
+    // Method for the owner to set how many logged state changes pass between heartbeat logs; 0 turns them off
+    pub fn set_heartbeat_interval(&mut self, interval: u64) {
+        self.assert_owner();
+        params::HEARTBEAT_INTERVAL.check(interval);
+        self.config.heartbeat_interval = interval;
+        self.record_change();
+    }
+
+    // Method for the owner to set how far ahead of the current time a voting deadline may be set
+    pub fn set_max_voting_period(&mut self, seconds: u64) {
+        self.assert_owner();
+        params::MAX_VOTING_PERIOD_SECS.check(seconds);
+        self.config.max_voting_period_secs = seconds;
+        self.record_change();
+    }
+
+    // Method for the owner to set how far a creator may push back a deadline with extend_deadline
+    pub fn set_max_deadline_extension(&mut self, seconds: u64) {
+        self.assert_owner();
+        params::MAX_DEADLINE_EXTENSION_SECS.check(seconds);
+        self.config.max_deadline_extension_secs = seconds;
+        self.record_change();
+    }
+
+    // Method for the owner to set the unique voters and votes a round needs before it can be finalized
+    pub fn set_round_minimums(&mut self, min_round_turnout: u64, min_round_votes: u64) {
+        self.assert_owner();
+        params::MIN_ROUND_TURNOUT.check(min_round_turnout);
+        params::MIN_ROUND_VOTES.check(min_round_votes);
+        self.config.min_round_turnout = min_round_turnout;
+        self.config.min_round_votes = min_round_votes;
+        self.record_change();
+    }
+
+    // Method for the owner to choose how finalize_round orders events with equal votes
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.assert_owner();
+        self.config.tie_break = tie_break;
+        self.record_change();
+    }
+
+    // Method for the owner to require (or stop requiring) invite codes to add events
+    pub fn set_invite_only(&mut self, invite_only: bool) {
+        self.assert_owner();
+        self.config.invite_only = invite_only;
+        self.record_change();
+    }
+
+    // Method for the owner to issue a one-time invite code for adding an event.
+    // Only a hash of the code is stored, so the returned code is the only copy.
+    pub fn issue_invite(&mut self) -> String {
+        self.assert_owner();
+
+        // Mix the block's random seed with a counter so codes issued in the same block differ
+        let mut seed = env::random_seed();
+        seed.extend(self.invites_issued.to_le_bytes());
+        let code: String = env::sha256(&seed)[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        self.invites.insert(&env::sha256(code.as_bytes()), &env::block_timestamp());
+        self.invites_issued += 1;
+        self.invites_outstanding += 1;
+        self.record_change();
+        code
+    }
+
+    // Method to count the invite codes issued but not used yet; the codes themselves are never returned
+    pub fn invites_outstanding(&self) -> u64 {
+        self.invites_outstanding
+    }
+
+    // Method for the owner to allow an account to create events
+    pub fn add_creator(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.creators.insert(&account_id);
+        self.record_change();
+    }
+
+    // Method for the owner to stop an account from creating events
+    pub fn remove_creator(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.creators.remove(&account_id);
+        self.record_change();
+    }
+
+    // Method to list the accounts allowed to create events besides the owner
+    pub fn list_creators(&self) -> Vec<AccountId> {
+        self.creators.to_vec()
+    }
+
+    // Method for the owner to set the accepted range of estimated budgets
+    pub fn set_budget_bounds(&mut self, min_budget: U128, max_budget: U128) {
+        self.assert_owner();
+        let (min_budget, max_budget) = (min_budget.0, max_budget.0);
+        assert_valid_budget_bounds(min_budget, max_budget);
+        params::MIN_BUDGET.check(min_budget);
+        params::MAX_BUDGET.check(max_budget);
+        self.config.min_budget = min_budget;
+        self.config.max_budget = max_budget;
+        self.record_change();
+    }
+
+    // Method for the owner to set how many recent voters get_event returns inline
+    pub fn set_max_inline_voters(&mut self, max_inline_voters: u32) {
+        self.assert_owner();
+        params::MAX_INLINE_VOTERS.check(max_inline_voters);
+        self.config.max_inline_voters = max_inline_voters;
+        self.record_change();
+    }
+
+    // Method for the owner to cap how many events may be open at once, or lift the cap with None.
+    // Lowering it below the current count closes nothing; it only blocks new events until enough close.
+    pub fn set_max_open_events(&mut self, max_open_events: Option<u64>) {
+        self.assert_owner();
+        if let Some(max_open_events) = max_open_events {
+            params::MAX_OPEN_EVENTS.check(max_open_events);
+        }
+        self.config.max_open_events = max_open_events;
+        self.record_change();
+    }
+
+    // Method for the owner to set the minimum time between two events added by the same account
+    pub fn set_creation_cooldown(&mut self, seconds: u64) {
+        self.assert_owner();
+        params::CREATION_COOLDOWN_SECS.check(seconds);
+        self.config.creation_cooldown_secs = seconds;
+        self.record_change();
+    }
+
+    // Method for the owner to set the loyalty tiers, ordered by strictly ascending age
+    pub fn set_loyalty_tiers(&mut self, loyalty_tiers: Vec<LoyaltyTier>) {
+        self.assert_owner();
+        assert_valid_loyalty_tiers(&loyalty_tiers);
+        for tier in &loyalty_tiers {
+            params::LOYALTY_MULTIPLIER.check(tier.multiplier);
+        }
+        self.config.loyalty_tiers = loyalty_tiers;
+        self.record_change();
+    }
+
+    // Method for the owner to publish a template for creators to pick from; returns its ID
+    pub fn create_template(&mut self, template: Template) -> u64 {
+        self.assert_owner();
+        assert!(
+            self.templates.len() < MAX_TEMPLATES,
+            "Cannot publish more than {} templates",
+            MAX_TEMPLATES
+        );
+        let id = self.templates.len() as u64;
+        self.templates.push(PublishedTemplate {
+            id,
+            template: clean_template(template),
+            active: true,
+        });
+        self.record_change();
+        id
+    }
+
+    // Method for the owner to change a template; events already made from it are left as they are
+    pub fn update_template(&mut self, id: u64, template: Template) {
+        self.assert_owner();
+        let template = clean_template(template);
+        self.templates
+            .get_mut(id as usize)
+            .expect("Template not found")
+            .template = template;
+        self.record_change();
+    }
+
+    // Method for the owner to stop new events from using a template
+    pub fn deactivate_template(&mut self, id: u64) {
+        self.assert_owner();
+        self.templates
+            .get_mut(id as usize)
+            .expect("Template not found")
+            .active = false;
+        self.record_change();
+    }
+
+    // Method to list every published template, including deactivated ones
+    pub fn list_templates(&self) -> Vec<PublishedTemplate> {
+        self.templates.clone()
+    }
+
+    // Method for the owner to cap the budget a round may approve in a template category
+    pub fn set_category_cap(&mut self, category: String, cap: U128) {
+        self.assert_owner();
+        let category = sanitize_line("Category", &category, MAX_CATEGORY_CHARS);
+        self.category_caps.insert(&category, &cap.0);
+        self.record_change();
+    }
+
+    // Method to show a category's cap, the budget approved in it in the latest round, and what is left
+    pub fn get_category_capacity(&self, category: String) -> CategoryCapacity {
+        let cap = self.category_caps.get(&category);
+        let committed = self
+            .category_committed
+            .get(&(self.round, category.clone()))
+            .unwrap_or(0);
+        CategoryCapacity {
+            category,
+            cap: cap.map(U128),
+            committed: U128(committed),
+            remaining: cap.map(|cap| U128(cap.saturating_sub(committed))),
+        }
+    }
+
+    // Method for the owner to finalize a funding round, approving up to `max_approvals` events by votes.
+    // An event whose budget would push its category's approved total for the round over the cap is skipped
+    // and the next-highest-voted event is considered instead. The round must reach the configured minimum
+    // turnout and votes. The outcome is archived as a RoundRecord. Returns the IDs approved.
+    pub fn finalize_round(&mut self, max_approvals: u32) -> Vec<u64> {
+        self.assert_owner();
+        let turnout = self.get_round_turnout();
+        assert!(
+            turnout.unique_voters >= self.config.min_round_turnout
+                && turnout.total_votes >= self.config.min_round_votes,
+            "Round needs {} unique voters and {} votes, it has {} and {}",
+            self.config.min_round_turnout,
+            self.config.min_round_votes,
+            turnout.unique_voters,
+            turnout.total_votes
+        );
+        self.internal_finalize_round(max_approvals, None)
+    }
+
+    // Method for the owner to finalize a round that misses the minimum turnout, giving the reason on record
+    pub fn finalize_round_force(&mut self, max_approvals: u32, reason: String) -> Vec<u64> {
+        self.assert_owner();
+        let reason = sanitize_line("Reason", &reason, MAX_FORCE_REASON_CHARS);
+        self.internal_finalize_round(max_approvals, Some(reason))
+    }
+
+    // Method for the owner to propose handing the contract to another account, which must accept it.
+    // Proposing again replaces the pending proposal.
+    pub fn propose_ownership_transfer(&mut self, new_owner: AccountId) {
+        self.assert_owner();
+        self.pending_owner = Some(new_owner);
+        self.record_change();
+    }
+
+    // Method for the proposed owner to take over the contract
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert_eq!(
+            self.pending_owner.as_ref(),
+            Some(&caller),
+            "Only the proposed owner can accept ownership"
+        );
+        self.owner = caller;
+        self.pending_owner = None;
+        self.record_change();
+    }
+
+    // Method to get the account a proposed ownership transfer is waiting on, if any
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
+    // Method for the owner to appoint (or remove) the auditor who must certify events approved from now on.
+    // A new auditor takes over pending certifications, but the auditor cannot be removed while any is pending.
+    pub fn set_auditor(&mut self, auditor: Option<AccountId>) {
+        self.assert_owner();
+        if auditor.is_none() {
+            let pending = self
+                .events
+                .iter()
+                .filter(|event| event.certification == Some(Certification::Pending))
+                .count();
+            assert!(
+                pending == 0,
+                "Cannot remove the auditor while {} results are pending certification",
+                pending
+            );
+        }
+        self.auditor = auditor;
+        self.record_change();
+    }
+
+    // Method to get the auditor who certifies approvals, if one is appointed
+    pub fn get_auditor(&self) -> Option<AccountId> {
+        self.auditor.clone()
+    }
+
+    // Method for the auditor to co-sign the result of an approved event, making its approval final
+    pub fn certify_result(&mut self, id: usize) {
+        self.decide_certification(id, Certification::Certified);
+    }
+
+    // Method for the auditor to reject the result of an approved event, giving the reason on record.
+    // The approval is void and the event does not compete in later rounds.
+    pub fn reject_result(&mut self, id: usize, reason: String) {
+        let reason = sanitize_line("Reason", &reason, MAX_REJECT_REASON_CHARS);
+        self.decide_certification(id, Certification::Rejected { reason });
+    }
+
+    // Method to show the turnout of the round in progress, counting each voter once across its events
+    pub fn get_round_turnout(&self) -> RoundTurnout {
+        let candidates = || {
+            self.visible_events()
+                .filter(|event| event.approved_in_round.is_none())
+        };
+        let unique_voters: HashSet<String> = candidates()
+            .flat_map(|event| event.votes.iter().map(|v| voter_key(v)))
+            .collect();
+        RoundTurnout {
+            round: self.round + 1,
+            unique_voters: unique_voters.len() as u64,
+            total_votes: candidates().map(|event| event.total_votes.max(0) as u64).sum(),
+            min_round_turnout: self.config.min_round_turnout,
+            min_round_votes: self.config.min_round_votes,
+        }
+    }
+
+    // Method to list up to `limit` finalized rounds, starting at round `from` (rounds are numbered from 1)
+    pub fn list_rounds(&self, from: u64, limit: u64) -> Vec<RoundRecord> {
+        (from.max(1)..=self.round)
+            .take(limit as usize)
+            .filter_map(|round| self.rounds.get(&round))
+            .collect()
+    }
+
+    // Method to get the record of a finalized round, if it exists
+    pub fn get_round(&self, round: u64) -> Option<RoundRecord> {
+        self.rounds.get(&round)
+    }
+
+    // Method to get the balance of a ledger account; External is negative by what the contract holds
+    pub fn get_ledger_balance(&self, account: LedgerAccount) -> I128 {
+        I128(self.ledger.balance(&account))
+    }
+
+    // Method to list up to `limit` ledger entries starting at ID `from_id`, oldest first
+    pub fn get_ledger_entries(&self, from_id: u64, limit: u64) -> Vec<LedgerEntry> {
+        self.ledger.entries(from_id, limit)
+    }
+
+    // Method to list the outgoing transfers not completed yet, oldest first
+    pub fn list_pending_transfers(&self) -> Vec<PendingTransfer> {
+        self.open_transfers
+            .iter()
+            .filter_map(|id| self.transfers.get(id))
+            .collect()
+    }
+
+    // Callback settling an outgoing transfer once its promise resolves.
+    // Results for a transfer that was reconciled or retried in the meantime are ignored.
+    #[private]
+    pub fn on_transfer_complete(
+        &mut self,
+        id: u64,
+        attempt: u32,
+        #[callback_result] result: Result<(), PromiseError>,
+    ) {
+        let Some(transfer) = self.transfers.get(&id) else {
+            return;
+        };
+        if transfer.status != TransferStatus::Pending || transfer.attempt != attempt {
+            return;
+        }
+        let status = if result.is_ok() {
+            TransferStatus::Completed
+        } else {
+            TransferStatus::Failed
+        };
+        self.settle_transfer(transfer, status);
+    }
+
+    // Method for the owner to settle a transfer whose callback never arrived, once the timelock has passed.
+    // Completed books it out of the ledger; Failed leaves the amount owed so the recipient can retry.
+    pub fn reconcile_transfer(&mut self, id: u64, outcome: TransferStatus) {
+        self.assert_owner();
+        let transfer = self.transfers.get(&id).expect("Transfer not found");
+        assert_eq!(
+            transfer.status,
+            TransferStatus::Pending,
+            "Only pending transfers can be reconciled"
+        );
+        assert_ne!(outcome, TransferStatus::Pending, "Outcome must be Completed or Failed");
+        let unlocks_at = transfer
+            .created_at
+            .saturating_add(TRANSFER_RECONCILE_DELAY_SECS * 1_000_000_000);
+        assert!(
+            env::block_timestamp() >= unlocks_at,
+            "Transfer can be reconciled from {}",
+            unlocks_at
+        );
+        self.settle_transfer(transfer, outcome);
+    }
+
+    // Method for the recipient of a failed transfer, or the owner, to send it again
+    pub fn retry_transfer(&mut self, id: u64) {
+        let mut transfer = self.transfers.get(&id).expect("Transfer not found");
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == transfer.to || caller == self.owner,
+            "Only the recipient or the owner can retry a transfer"
+        );
+        assert_eq!(
+            transfer.status,
+            TransferStatus::Failed,
+            "Only failed transfers can be retried"
+        );
+        transfer.attempt += 1;
+        transfer.created_at = env::block_timestamp();
+        transfer.status = TransferStatus::Pending;
+        self.transfers.insert(&id, &transfer);
+        dispatch_transfer(&transfer);
+        self.record_change();
+    }
+
+    // Method for the owner to set the link rules of the content policy
+    pub fn set_link_policy(&mut self, forbid_urls_in_title: bool, max_links_in_description: u8) {
+        self.assert_owner();
+        params::MAX_LINKS_IN_DESCRIPTION.check(max_links_in_description);
+        self.config.forbid_urls_in_title = forbid_urls_in_title;
+        self.config.max_links_in_description = max_links_in_description;
+        self.record_change();
+    }
+
+    // Method for the owner to ban a term in titles and descriptions, matched case-insensitively
+    pub fn add_banned_term(&mut self, term: String) {
+        self.assert_owner();
+        let term = sanitize_line("Banned term", &term, MAX_BANNED_TERM_CHARS).to_lowercase();
+        if self.banned_terms.contains(&term) {
+            return;
+        }
+        assert!(
+            self.banned_terms.len() < MAX_BANNED_TERMS,
+            "Cannot ban more than {} terms",
+            MAX_BANNED_TERMS
+        );
+        self.banned_terms.push(term);
+        self.record_change();
+    }
+
+    // Method for the owner to lift the ban on a term
+    pub fn remove_banned_term(&mut self, term: String) {
+        self.assert_owner();
+        let term = term.trim().to_lowercase();
+        let position = self
+            .banned_terms
+            .iter()
+            .position(|t| *t == term)
+            .expect("Term is not banned");
+        self.banned_terms.remove(position);
+        self.record_change();
+    }
+
+    // Method to list the banned terms, for the owner to review
+    pub fn list_banned_terms(&self) -> Vec<String> {
+        self.banned_terms.clone()
+    }
+
+    // Method for the owner to reserve a short code so no event can claim it
+    pub fn add_reserved_code(&mut self, code: String) {
+        self.assert_owner();
+        let code = normalize_short_code(&code);
+        if self.reserved_codes.contains(&code) {
+            return;
+        }
+        assert!(
+            self.reserved_codes.len() < MAX_RESERVED_CODES,
+            "Cannot reserve more than {} codes",
+            MAX_RESERVED_CODES
+        );
+        self.reserved_codes.push(code);
+        self.record_change();
+    }
+
+    // Method for the owner to release a reserved short code
+    pub fn remove_reserved_code(&mut self, code: String) {
+        self.assert_owner();
+        let code = code.trim().to_uppercase();
+        let position = self
+            .reserved_codes
+            .iter()
+            .position(|c| *c == code)
+            .expect("Code is not reserved");
+        self.reserved_codes.remove(position);
+        self.record_change();
+    }
+
+    // Method to list the reserved short codes, for the owner to review
+    pub fn list_reserved_codes(&self) -> Vec<String> {
+        self.reserved_codes.clone()
+    }
+
+    // Method for the owner to set the charge per vote, in yoctoNEAR
+    pub fn set_vote_fee(&mut self, vote_fee: U128) {
+        self.assert_owner();
+        params::VOTE_FEE.check(vote_fee.0);
+        self.config.vote_fee = vote_fee.0;
+        self.record_change();
+    }
+
+    // Method for the owner to require (or stop requiring) a deposit when voting.
+    // Function-call access keys cannot attach deposits, so turning this on limits voting to full access keys.
+    pub fn set_require_vote_deposit(&mut self, require_vote_deposit: bool) {
+        self.assert_owner();
+        self.config.require_vote_deposit = require_vote_deposit;
+        self.record_change();
+    }
+
+    // Method describing how actions are attributed, for relayer operators
+    pub fn attribution_policy(&self) -> String {
+        ATTRIBUTION_POLICY.to_string()
+    }
+
+    // Method to get the version of the API this deployment speaks
+    pub fn abi_version(&self) -> String {
+        ABI_VERSION.to_string()
+    }
+
+    // Method to get the hex sha256 of the embedded ABI JSON, so clients can detect schema changes.
+    // Builds without an embedded ABI report the hash of no bytes.
+    pub fn schema_hash(&self) -> String {
+        to_hex(&env::sha256(CONTRACT_ABI))
+    }
+
+    // Method to get the rolling checksum and the counters it last covered, for monitoring to compare with heartbeats
+    pub fn get_checksum(&self) -> StateChecksum {
+        let (event_count, total_votes_cast, total_raised) = self.checksum_counters();
+        StateChecksum {
+            checksum: to_hex(&self.checksum),
+            event_count,
+            total_votes_cast,
+            total_raised: U128(total_raised),
+            change_seq: self.change_seq,
+        }
+    }
+
+    // Method to get the layout version of the stored state
+    pub fn state_version(&self) -> u32 {
+        self.state_version
+    }
+
+    // Method for wallets to decide which kind of key can be used to vote
+    pub fn get_vote_requirements(&self) -> VoteRequirements {
+        VoteRequirements {
+            deposit_required: self.required_deposit(Method::AddVote) > 0,
+            min_deposit: NearToken::from_yoctonear(self.required_deposit(Method::AddVote)),
+            gas_budget: ADD_VOTE_GAS_BUDGET,
+        }
+    }
+
+    // Method listing the deposit and gas each user-facing change method needs
+    pub fn get_method_requirements(&self) -> Vec<MethodRequirement> {
+        Method::ALL
+            .iter()
+            .map(|method| MethodRequirement {
+                name: method.name().to_string(),
+                payable: method.payable(),
+                min_deposit: U128(self.required_deposit(*method)),
+                suggested_gas: U64(method.suggested_gas().as_gas()),
+            })
+            .collect()
+    }
+
+    // Method to estimate what adding an event costs, given the byte lengths of its title and description.
+    // `options` combines the CREATION_OPTION_* flags for the optional arguments add_event will be given.
+    // The contract pays for the storage, so the deposit to attach is the creation fee alone.
+    pub fn estimate_creation_cost(
+        &self,
+        title_len: u32,
+        description_len: u32,
+        options: u8,
+    ) -> CostEstimate {
+        let storage_bytes = creation_storage_bytes(title_len, description_len, options);
+        let storage_cost = env::storage_byte_cost().as_yoctonear() * storage_bytes as u128;
+        let creation_fee = self.required_deposit(Method::AddEvent);
+        CostEstimate {
+            storage_bytes: U64(storage_bytes),
+            storage_cost: U128(storage_cost),
+            creation_fee: U128(creation_fee),
+            total_deposit: U128(creation_fee),
+            suggested_gas: U64(Method::AddEvent.suggested_gas().as_gas()),
+        }
+    }
+
+    // Method to get the Borsh size in bytes of the contract's root state.
+    // Entries of the LookupMap and UnorderedSet collections live under their own keys and are not counted.
+    pub fn serialized_size(&self) -> u64 {
+        near_sdk::borsh::to_vec(self).map_or(0, |bytes| bytes.len() as u64)
+    }
+
+    // Method to get the sum of all estimated budgets without overflowing
+    pub fn total_requested_budget(&self) -> BudgetTotal {
+        // Add budgets with checked math, capping at u128::MAX and flagging the overflow
+        let mut total: u128 = 0;
+        for event in self.visible_events() {
+            match total.checked_add(event.estimated_budget) {
+                Some(sum) => total = sum,
+                None => {
+                    return BudgetTotal {
+                        total: U128(u128::MAX),
+                        saturated: true,
+                    }
+                }
+            }
+        }
+
+        BudgetTotal {
+            total: U128(total),
+            saturated: false,
+        }
+    }
+
+    // Method to get the average number of votes per event, rounded down
+    pub fn average_votes(&self) -> u64 {
+        // Avoid dividing by zero when there are no events
+        let count = self.visible_events().count() as u64;
+        if count == 0 {
+            return 0;
+        }
+
+        // Sum the votes of all events and divide by the number of events
+        let total: u64 = self.visible_events().map(|e| e.total_votes.max(0) as u64).sum();
+        total / count
+    }
+
+    // Method to get the first quartile, median and third quartile of the events' vote counts.
+    // Each is the sorted value at index (count - 1) * k / 4, rounded down, so it is a count some event has.
+    pub fn vote_quartiles(&self) -> (u64, u64, u64) {
+        // Sort the vote counts, returning zeros when there are no events
+        let mut votes: Vec<u64> =
+            self.visible_events().map(|e| e.total_votes.max(0) as u64).collect();
+        if votes.is_empty() {
+            return (0, 0, 0);
+        }
+        votes.sort_unstable();
+
+        let quartile = |k: usize| votes[(votes.len() - 1) * k / 4];
+        (quartile(1), quartile(2), quartile(3))
+    }
+
+    // Method to get the difference between the most and least voted events' vote counts, 0 with fewer than two
+    pub fn vote_spread(&self) -> u64 {
+        let votes = || self.visible_events().map(|e| e.total_votes.max(0) as u64);
+        match (votes().max(), votes().min()) {
+            (Some(max), Some(min)) => max - min,
+            _ => 0,
+        }
+    }
+
+    // Method to get the events sharing the highest vote count when two or more tie for first, otherwise none
+    pub fn top_tie(&self) -> Vec<Event> {
+        let Some(max) = self.visible_events().map(|e| e.total_votes).max() else {
+            return vec![];
+        };
+        let tied: Vec<Event> =
+            self.visible_events().filter(|e| e.total_votes == max).cloned().collect();
+        if tied.len() < 2 {
+            return vec![];
+        }
+        tied
+    }
+
+    // Method to get the share of events with at least one vote, in basis points (10000 = all of them)
+    pub fn voted_event_ratio(&self) -> u32 {
+        // Avoid dividing by zero when there are no events
+        let count = self.visible_events().count() as u64;
+        if count == 0 {
+            return 0;
+        }
+
+        // Count the events with votes and scale by 10000 before dividing, rounding down
+        let voted = self.visible_events().filter(|e| e.total_votes > 0).count() as u64;
+        (voted * 10_000 / count) as u32
+    }
+
+    // Method to count the accounts that voted on two or more events
+    pub fn multi_event_voter_count(&self) -> u32 {
+        // Tally how many events each account voted on
+        let mut participation: HashMap<String, u32> = HashMap::new();
+        for event in self.visible_events() {
+            for voter in event.votes.iter() {
+                *participation.entry(voter_key(voter)).or_insert(0) += 1;
+            }
+        }
+
+        // Count the accounts appearing in more than one event
+        participation.values().filter(|&&count| count >= 2).count() as u32
+    }
+
+    // Method to count the events the caller has voted for. Like get_my_event it is a change method,
+    // as a view call has no caller to check.
+    pub fn my_vote_count(&mut self) -> u32 {
+        let caller = env::predecessor_account_id();
+        self.visible_events()
+            .filter(|event| event.votes.iter().any(|v| is_same_voter(v, &caller)))
+            .count() as u32
+    }
+
+    // Method to sum the votes received across each creator's events, most votes first
+    pub fn creator_vote_totals(&self) -> Vec<(AccountId, u64)> {
+        // Tally the votes of every event under its creator
+        let mut totals: HashMap<&AccountId, u64> = HashMap::new();
+        for event in self.visible_events() {
+            *totals.entry(&event.creator).or_insert(0) += event.total_votes.max(0) as u64;
+        }
+
+        // Order by total, breaking ties alphabetically so the result is deterministic
+        let mut totals: Vec<(AccountId, u64)> = totals
+            .into_iter()
+            .map(|(creator, total)| (creator.clone(), total))
+            .collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        totals
+    }
+
+    // Method to record the current block time with each visible event's vote count, in ID order
+    pub fn snapshot_standings(&self) -> (Timestamp, Vec<(i32, u64)>) {
+        let standings = self
+            .visible_events()
+            .map(|event| (event.id, event.total_votes.max(0) as u64))
+            .collect();
+        (env::block_timestamp(), standings)
+    }
+
+    // Method to get the votes per unit of budget, scaled by 1,000,000 to keep precision
+    pub fn votes_per_budget(&self, id: usize) -> u128 {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        // A zero budget would divide by zero
+        assert!(event.estimated_budget > 0, "Event has no budget");
+
+        event.total_votes.max(0) as u128 * 1_000_000 / event.estimated_budget
+    }
+
+    // Method to count the distinct accounts in an event's voter list, ignoring legacy duplicates
+    pub fn unique_votes(&self, id: usize) -> u64 {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        event.votes.iter().map(|v| voter_key(v)).collect::<HashSet<String>>().len() as u64
+    }
+
+    // Method to rank published events by distinct voters, most first, returning up to `limit` (id, count) pairs.
+    // Events with the same count keep their ID order.
+    pub fn events_by_unique_voters(&self, limit: usize) -> Vec<(i32, usize)> {
+        let mut ranked: Vec<(i32, usize)> = self
+            .visible_events()
+            .map(|event| {
+                let voters: HashSet<String> = event.votes.iter().map(|v| voter_key(v)).collect();
+                (event.id, voters.len())
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        ranked.truncate(limit);
+        ranked
+    }
+
+    // Method to suggest up to `limit` published events whose title starts with `prefix`, ignoring case.
+    // The prefix needs at least MIN_AUTOCOMPLETE_CHARS characters, so a query never walks the whole index.
+    pub fn autocomplete_titles(&self, prefix: String, limit: u64) -> Vec<(u64, String)> {
+        let prefix = prefix.to_lowercase();
+        assert!(
+            prefix.chars().count() >= MIN_AUTOCOMPLETE_CHARS,
+            "Prefix must be at least {} characters",
+            MIN_AUTOCOMPLETE_CHARS
+        );
+
+        // Walk the buckets starting with the prefix; longer prefixes share one bucket and are checked in full
+        let bucket = title_prefix(&prefix);
+        self.title_prefixes
+            .range((Bound::Included(bucket.clone()), Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(&bucket))
+            .flat_map(|(_, ids)| ids)
+            .map(|id| &self.events[id as usize])
+            .filter(|event| !event.draft && event.title.to_lowercase().starts_with(&prefix))
+            .take(limit as usize)
+            .map(|event| (event.id as u64, event.title.clone()))
+            .collect()
+    }
+
+    // Method to suggest up to `limit` events sharing voters with an event, as (id, co-voters) pairs, most first.
+    // Only the most recent RELATED_SAMPLE_VOTERS voters and RELATED_VOTES_PER_VOTER votes of each are read,
+    // so gas stays bounded however many votes the event has; ties are listed in ID order.
+    // Closed, funded, deleted and draft events are left out.
+    pub fn related_events(&self, id: usize, limit: u64) -> Vec<(u64, u64)> {
+        let now = env::block_timestamp();
+        let event: &Event = self.event(id);
+
+        // Count how many sampled voters also voted on each other event
+        let mut co_votes: HashMap<u64, u64> = HashMap::new();
+        let sample = event.votes.iter().rev().take(RELATED_SAMPLE_VOTERS);
+        for voter in sample.filter_map(|v| normalize_voter(v)) {
+            let ids = self.account_votes.get(&voter).unwrap_or_default();
+            for &other in ids.iter().rev().take(RELATED_VOTES_PER_VOTER) {
+                if other != id as u64 {
+                    *co_votes.entry(other).or_insert(0) += 1;
+                }
+            }
+        }
+
+        // Keep the events still taking votes, ranking by co-voters and then by ID
+        let mut related: Vec<(u64, u64)> = co_votes
+            .into_iter()
+            .filter(|&(other, _)| {
+                let candidate = &self.events[other as usize];
+                candidate.is_visible()
+                    && !candidate.draft
+                    && !candidate.funded
+                    && candidate.is_open(now)
+            })
+            .collect();
+        related.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        related.truncate(limit as usize);
+        related
+    }
+
+    // Method summarizing how an event is doing, for its organizer's dashboard.
+    // Daily counts only read the timestamps of the most recent MAX_ANALYTICS_SCAN votes,
+    // and the rank only compares against the most recent MAX_RANK_SCAN events.
+    pub fn get_event_analytics(&self, id: usize) -> EventAnalytics {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        // Spread the UTC days of the most recent votes over the last ANALYTICS_DAYS days, oldest first
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let today = env::block_timestamp() / day;
+        let mut votes_per_day = vec![0; ANALYTICS_DAYS as usize];
+        let (days, truncated) = self.vote_days(id, 0);
+        for (vote_day, count) in days {
+            let age = today.saturating_sub(vote_day);
+            if age < ANALYTICS_DAYS {
+                votes_per_day[(ANALYTICS_DAYS - 1 - age) as usize] += count;
+            }
+        }
+
+        // Rank open events by raw votes; ties share the better rank
+        let now = env::block_timestamp();
+        let rank = event.is_open(now).then(|| {
+            let ahead = self
+                .events
+                .iter()
+                .rev()
+                .take(MAX_RANK_SCAN)
+                .filter(|other| other.is_visible() && !other.draft)
+                .filter(|other| other.is_open(now) && other.total_votes > event.total_votes)
+                .count();
+            ahead as u32 + 1
+        });
+
+        EventAnalytics {
+            event_id: id as u64,
+            votes_per_day,
+            votes_per_day_truncated: truncated,
+            unique_voters: self.unique_votes(id),
+            funding_progress_percent: event
+                .funds_raised
+                .saturating_mul(100)
+                .checked_div(event.estimated_budget)
+                .map_or(0, |percent| percent.min(u64::MAX as u128) as u64),
+            rank,
+            rank_truncated: self.events.len() > MAX_RANK_SCAN,
+        }
+    }
+
+    // Method to count an event's votes by day since its creation, as (day_index, count) pairs for days with votes.
+    // Like get_event_analytics it only reads the most recent MAX_ANALYTICS_SCAN votes, and skips votes without a time.
+    pub fn votes_per_day(&self, id: usize) -> Vec<(u64, u64)> {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        // Bucket each vote by whole days between the event's creation and the vote
+        let (histogram, _) = self.vote_days(id, event.created_at);
+        histogram.into_iter().collect()
+    }
+
+    // Method to count events by month of creation, as (month_index, count) pairs for months with events.
+    // Months are approximated as 30-day periods since the Unix epoch, so month 0 starts on 1 January 1970.
+    pub fn events_per_month(&self) -> Vec<(u64, u32)> {
+        let month = 30 * SECONDS_PER_DAY * 1_000_000_000;
+        let mut histogram: BTreeMap<u64, u32> = BTreeMap::new();
+        for event in self.visible_events() {
+            *histogram.entry(event.created_at / month).or_insert(0) += 1;
+        }
+
+        histogram.into_iter().collect()
+    }
+
+    // Method to check that an event's vote counter matches its list of voters
+    pub fn is_event_consistent(&self, id: usize) -> bool {
+        // Get a reference to the event by its ID
+        let event: &Event = self.event(id);
+
+        // Every unweighted vote increments the counter once and records one voter
+        event.total_votes >= 0 && event.total_votes as usize == event.votes.len()
+    }
+}
+
+// Internal helpers that are not exposed as contract methods
+impl Contract {
+    // Record a vote by `voter` for the event, along with any fee they attached and survey answers they gave
+    fn internal_add_vote(
+        &mut self,
+        id: usize,
+        voter: AccountId,
+        method: Method,
+        answers: Option<Vec<u8>>,
+    ) -> Result<(), VoteError> {
+        // Reject the vote for the first violation, using the same checks as dry_run_vote
+        if let Some(error) = self.vote_violations(id, &voter).into_iter().next() {
+            return Err(error);
+        }
+
+        // Enforce the deposit requirement, if any
+        let deposit = env::attached_deposit().as_yoctonear();
+        if deposit < self.required_deposit(method) {
+            return Err(VoteError::DepositRequired);
+        }
+
+        // Events with a minimum voter balance also want that much attached on top, as proof of holding it
+        let proof = self.events[id].min_voter_balance.unwrap_or(0);
+        if deposit < self.required_deposit(method).saturating_add(proof) {
+            return Err(VoteError::BalanceProofRequired {
+                min_balance: U128(proof),
+            });
+        }
+
+        // Only the required deposit is kept as the fee. Anything above it, the balance proof
+        // included, goes back to the sender.
+        let fee = self.required_deposit(method);
+        let excess = deposit - fee;
+
+        // Answers must pick exactly one valid choice per question
+        if let Some(answers) = answers.as_ref() {
+            let questions = &self.events[id].questions;
+            if questions.is_empty() {
+                return Err(VoteError::AnswersNotAccepted);
+            }
+            let valid = answers.len() == questions.len()
+                && answers
+                    .iter()
+                    .zip(questions.iter())
+                    .all(|(&answer, question)| (answer as usize) < question.choices.len());
+            if !valid {
+                return Err(VoteError::InvalidAnswers);
+            }
+        }
+
+        // Register the voter on first interaction, then weigh the vote by their loyalty tier if the event asks for it
+        let now = env::block_timestamp();
+        self.internal_register(&voter);
+        let weight = match self.events.get(id) {
+            Some(event) if event.loyalty_weighted => self.loyalty_multiplier(&voter, now),
+            _ => 1,
+        };
+
+        // Get a mutable reference to the event by its ID
+        let event: &mut Event = self.events.get_mut(id).ok_or(VoteError::EventNotFound)?;
+
+        // Increment the raw and weighted totals for the event
+        event.total_votes += 1;
+        event.weighted_total += weight as u64;
+        self.total_votes_cast += 1;
+
+        // Add the voter's account ID to the list of votes for the event
+        event.votes.push(voter.to_string());
+
+        // Count the answers without recording who gave them
+        for (question, &answer) in answers.iter().flatten().enumerate() {
+            event.answer_tallies[question][answer as usize] += 1;
+        }
+
+        // Open donations if this vote reached the threshold
+        self.unlock_funding_if_reached(id);
+
+        // Log the vote for indexers, naming both the attributed voter and the sender
+        self.log_change(&VoteCastLog {
+            event_id: id as u64,
+            voter: voter.clone(),
+            cast_by: env::predecessor_account_id(),
+            fee: U128(fee),
+            weight,
+        });
+
+        // Remember any fee paid so it can be refunded if the vote is removed
+        if fee > 0 {
+            self.vote_fees.insert(&(id as u64, voter.clone()), &fee);
+            self.transfer_internal(
+                LedgerAccount::External,
+                LedgerAccount::Escrow(id as u64),
+                fee,
+                "vote_fee",
+            );
+        }
+
+        // Return the overpayment to whoever attached it
+        if excess > 0 {
+            let sender = env::predecessor_account_id();
+            let source = LedgerAccount::External;
+            self.send_transfer(sender, excess, source, id as u64, "vote_overpayment");
+        }
+
+        // Remember when the vote was cast, for get_event_analytics, and add its receipt to the Merkle tree
+        self.voted_at.insert(&(id as u64, voter.clone()), &now);
+        self.index_vote(&voter, id as u64);
+        self.record_vote_receipt(id, &voter, now);
+
+        // Remember boosted weights so removing the vote subtracts the right amount
+        if weight > 1 {
+            self.vote_weights.insert(&(id as u64, voter), &weight);
+        }
+
+        Ok(())
+    }
+
+    // Record when an account first interacts with the contract, returning its registration time
+    fn internal_register(&mut self, account_id: &AccountId) -> Timestamp {
+        if let Some(registered_at) = self.registered_at.get(account_id) {
+            return registered_at;
+        }
+        let now = env::block_timestamp();
+        self.registered_at.insert(account_id, &now);
+        now
+    }
+
+    // Delete an account's creator defaults, sending back the storage deposit held for them
+    fn internal_remove_creator_defaults(&mut self, account_id: &AccountId) -> bool {
+        if self.creator_defaults.remove(account_id).is_none() {
+            return false;
+        }
+        let held = LedgerAccount::StorageDeposit(account_id.clone());
+        let deposit = self.ledger.balance(&held).max(0) as u128;
+        if deposit > 0 {
+            let to = account_id.clone();
+            self.send_transfer(to, deposit, held, NO_EVENT, "storage_refund");
+        }
+        true
+    }
+
+    // Store a receipt of `voter`'s vote and append its leaf to the event's Merkle tree
+    fn record_vote_receipt(&mut self, id: usize, voter: &AccountId, cast_at: Timestamp) {
+        let event: &mut Event = &mut self.events[id];
+        let index = event.vote_leaves;
+        event.append_vote_leaf(vote_leaf_hash(voter, id as u64, cast_at));
+        let receipt = VoteReceipt {
+            voter: voter.clone(),
+            cast_at,
+        };
+        self.vote_receipts.insert(&(id as u64, index), &receipt);
+    }
+
+    // Vote multiplier of the highest loyalty tier the account has reached, or 1 if none
+    fn loyalty_multiplier(&self, account_id: &AccountId, now: Timestamp) -> u32 {
+        let Some(registered_at) = self.registered_at.get(account_id) else {
+            return 1;
+        };
+        let age_secs = now.saturating_sub(registered_at) / 1_000_000_000;
+        self.config
+            .loyalty_tiers
+            .iter()
+            .rev()
+            .find(|tier| age_secs >= tier.min_age_secs)
+            .map_or(1, |tier| tier.multiplier)
+    }
+
+    // Every reason `voter` may not vote for the event right now, shared by add_vote and dry_run_vote
+    fn vote_violations(&self, id: usize, voter: &AccountId) -> Vec<VoteError> {
+        // Nothing else can be checked without the event
+        if let Some(error) = self.missing_event_error(id) {
+            return vec![error];
+        }
+        let event = &self.events[id];
+
+        let mut violations = Vec::new();
+
+        // Votes are only accepted after the grace period and before the deadline, plus any jitter allowance
+        let now = env::block_timestamp();
+        if now < event.votes_open_at {
+            violations.push(VoteError::NotOpenYet {
+                opens_at: event.votes_open_at,
+            });
+        }
+        if !event.is_open(now.saturating_sub(self.config.deadline_grace_period_ns)) {
+            violations.push(VoteError::VotingClosed);
+        }
+
+        // Funded events no longer take votes, and drafts do not take them yet
+        if event.funded {
+            violations.push(VoteError::AlreadyFunded);
+        }
+        if event.draft {
+            violations.push(VoteError::EventIsDraft);
+        }
+
+        // Each account may only vote once per event
+        if event.votes.iter().any(|v| is_same_voter(v, voter)) {
+            violations.push(VoteError::AlreadyVoted);
+        }
+
+        violations
+    }
+
+    // Every reason `creator` may not add the event right now, shared by add_event and dry_run_add_event
+    fn add_event_violations(
+        &self,
+        creator: &AccountId,
+        title: &str,
+        estimated_budget: u128,
+        invite_code: Option<&str>,
+    ) -> Vec<CreateError> {
+        let mut violations = Vec::new();
+
+        // Only the owner and whitelisted creators may add events
+        if *creator != self.owner && !self.creators.contains(creator) {
+            violations.push(CreateError::NotAuthorized);
+        }
+
+        // While creation is invite-only, everyone but the owner must bring an unused invite code
+        if self.invite_required(creator) {
+            match invite_code {
+                None => violations.push(CreateError::InviteRequired),
+                Some(code) if !self.invites.contains_key(&env::sha256(code.as_bytes())) => {
+                    violations.push(CreateError::InvalidInvite)
+                }
+                Some(_) => {}
+            }
+        }
+
+        // The title is measured after the same clean-up add_event applies before storing it
+        let title = clean_line(title);
+        if title.is_empty() {
+            violations.push(CreateError::TitleEmpty);
+        } else if title.chars().count() > MAX_TITLE_CHARS {
+            violations.push(CreateError::TitleTooLong {
+                max_chars: MAX_TITLE_CHARS as u32,
+            });
+        }
+
+        // The title must follow the content policy
+        if let Some(rule) = self.title_policy_violation(&title) {
+            violations.push(CreateError::ContentPolicy { rule });
+        }
+
+        // Reject budgets outside the configured bounds
+        if estimated_budget < self.config.min_budget || estimated_budget > self.config.max_budget {
+            violations.push(CreateError::BudgetOutOfRange {
+                min: U128(self.config.min_budget),
+                max: U128(self.config.max_budget),
+            });
+        }
+
+        // The number of open events is capped, if the owner set a cap
+        if let Some(error) = self.open_events_violation() {
+            violations.push(error);
+        }
+
+        // Each account must wait out the cooldown, if any, after its previous event
+        let cooldown = self.config.creation_cooldown_secs.saturating_mul(1_000_000_000);
+        if let Some(last_event_at) = self.last_event_at.get(creator).filter(|_| cooldown > 0) {
+            if env::block_timestamp() < last_event_at.saturating_add(cooldown) {
+                violations.push(CreateError::CooldownActive);
+            }
+        }
+
+        violations
+    }
+
+    // When an event `creator` adds now starts accepting votes: after the grace period,
+    // unless the creator has been registered long enough to be exempt
+    fn votes_open_at(&self, creator: &AccountId) -> Timestamp {
+        let now = env::block_timestamp();
+        let exempt = self.config.grace_exempt_min_age_secs.is_some_and(|min_age_secs| {
+            self.registered_at.get(creator).is_some_and(|registered_at| {
+                now.saturating_sub(registered_at) / 1_000_000_000 >= min_age_secs
+            })
+        });
+        if exempt {
+            now
+        } else {
+            now.saturating_add(self.config.vote_grace_period_ns)
+        }
+    }
+
+    // Whether `creator` must redeem an invite code to add an event
+    fn invite_required(&self, creator: &AccountId) -> bool {
+        self.config.invite_only && *creator != self.owner
+    }
+
+    // The content policy rule a title breaks, if any
+    fn title_policy_violation(&self, title: &str) -> Option<ContentRule> {
+        if self.config.forbid_urls_in_title && count_links(title) > 0 {
+            return Some(ContentRule::LinkInTitle);
+        }
+        self.banned_term_violation(title)
+    }
+
+    // Panic naming the content policy rule a description breaks, if any
+    fn assert_description_policy(&self, description: &str) {
+        let max = self.config.max_links_in_description;
+        let rule = if count_links(description) > max as usize {
+            Some(ContentRule::TooManyLinks { max })
+        } else {
+            self.banned_term_violation(description)
+        };
+        if let Some(rule) = rule {
+            env::panic_str(&rule.message());
+        }
+    }
+
+    // Whether the text contains a banned term, ignoring case
+    fn banned_term_violation(&self, text: &str) -> Option<ContentRule> {
+        let text = text.to_lowercase();
+        self.banned_terms
+            .iter()
+            .any(|term| text.contains(term.as_str()))
+            .then_some(ContentRule::BannedTerm)
+    }
+
+    // Get a visible event by its ID, panicking with why it cannot be used otherwise
+    fn event(&self, id: usize) -> &Event {
+        self.assert_active(id);
+        &self.events[id]
+    }
+
+    // Get a visible event by its ID for changing it; deleted and purged events reject changes
+    fn event_mut(&mut self, id: usize) -> &mut Event {
+        self.assert_active(id);
+        &mut self.events[id]
+    }
+
+    // Panic, telling apart unknown, deleted and purged events, unless the event is visible
+    fn assert_active(&self, id: usize) {
+        if let Some(error) = self.missing_event_error(id) {
+            env::panic_str(&error.message());
+        }
+    }
+
+    // Why the event cannot be used, or None if it is visible
+    fn missing_event_error(&self, id: usize) -> Option<VoteError> {
+        match self.get_event_state(id) {
+            EventLocation::Active => None,
+            EventLocation::Deleted => Some(VoteError::EventDeleted),
+            EventLocation::Purged => Some(VoteError::EventPurged),
+            EventLocation::Unknown => Some(VoteError::EventNotFound),
+        }
+    }
+
+    // Count an event's most recent MAX_ANALYTICS_SCAN votes by whole days since `origin`, skipping votes without a time.
+    // Also returns whether older votes were left out.
+    fn vote_days(&self, id: usize, origin: Timestamp) -> (BTreeMap<u64, u64>, bool) {
+        let event: &Event = self.event(id);
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut days: BTreeMap<u64, u64> = BTreeMap::new();
+        let skipped = event.votes.len().saturating_sub(MAX_ANALYTICS_SCAN);
+        for voter in event.votes[skipped..].iter() {
+            let Ok(voter) = voter.parse::<AccountId>() else {
+                continue;
+            };
+            if let Some(cast_at) = self.voted_at.get(&(id as u64, voter)) {
+                let day_index = cast_at.saturating_sub(origin) / day;
+                *days.entry(day_index).or_insert(0) += 1;
+            }
+        }
+        (days, skipped > 0)
+    }
+
+    // Iterate over the published events that views and round finalization may return
+    fn visible_events(&self) -> impl Iterator<Item = &Event> {
+        self.active_events().filter(|event| !event.draft)
+    }
+
+    // Iterate over the events that have not been deleted, drafts included
+    fn active_events(&self) -> impl Iterator<Item = &Event> {
+        self.events.iter().filter(|event| event.is_visible())
+    }
+
+    // Reason another event cannot be opened, if open events are capped and the cap is reached.
+    // Events still taking votes and not yet approved count, drafts included, so a creator cannot
+    // park drafts past the cap. An event frees its slot once its deadline passes or it is approved.
+    fn open_events_violation(&self) -> Option<CreateError> {
+        let max = self.config.max_open_events?;
+        let now = env::block_timestamp();
+        let open = self
+            .active_events()
+            .filter(|event| event.is_open(now) && event.approved_in_round.is_none())
+            .count() as u64;
+        (open >= max).then_some(CreateError::TooManyOpenEvents { open, max })
+    }
+
+    // Smallest deposit a method accepts, in yoctoNEAR; both enforcement and get_method_requirements read this
+    fn required_deposit(&self, method: Method) -> u128 {
+        match method {
+            Method::AddEvent => self.config.creation_fee,
+            Method::AddVote | Method::TryAddVote | Method::AddVoteAs => {
+                let key_check = u128::from(self.config.require_vote_deposit);
+                self.config.vote_fee.max(key_check)
+            }
+            Method::Donate | Method::CreateMatch => 1,
+            Method::SetCreatorDefaults => {
+                env::storage_byte_cost().as_yoctonear() * CREATOR_DEFAULTS_BYTES as u128
+            }
+            _ => 0,
+        }
+    }
+
+    // Record the auditor's decision on an approved event waiting for certification
+    fn decide_certification(&mut self, id: usize, certification: Certification) {
+        let auditor = env::predecessor_account_id();
+        assert_eq!(
+            self.auditor.as_ref(),
+            Some(&auditor),
+            "Only the auditor can certify or reject results"
+        );
+        let event: &mut Event = self.event_mut(id);
+        assert_eq!(
+            event.certification,
+            Some(Certification::Pending),
+            "Event result is not pending certification"
+        );
+        event.certification = Some(certification.clone());
+        self.notify(&CertificationDecidedLog {
+            event_id: id as u64,
+            auditor,
+            certification,
+        });
+    }
+
+    // Panic unless the event still takes votes, so votes are never taken back once voting closed or it was funded
+    fn assert_votes_changeable(&self, id: usize) {
+        if let Some(error) = self.votes_change_violations(id).first() {
+            env::panic_str(&error.message());
+        }
+    }
+
+    // Every reason the votes on an event can no longer change, shared by the vote-moving methods
+    fn votes_change_violations(&self, id: usize) -> Vec<VoteError> {
+        let event: &Event = self.event(id);
+        let now = env::block_timestamp().saturating_sub(self.config.deadline_grace_period_ns);
+        let mut violations = Vec::new();
+        if !event.is_open(now) {
+            violations.push(VoteError::VotingClosed);
+        }
+        if event.funded {
+            violations.push(VoteError::AlreadyFunded);
+        }
+        violations
+    }
+
+    // Every reason `voter` may not remove their vote from the event, shared by remove_vote
+    fn remove_vote_violations(&self, id: usize, voter: &AccountId) -> Vec<String> {
+        if let Some(error) = self.missing_event_error(id) {
+            return vec![error.message()];
+        }
+        let mut violations = Vec::new();
+        if !self.events[id].votes.iter().any(|v| is_same_voter(v, voter)) {
+            violations.push("You have not voted for this event".to_string());
+        }
+        violations.extend(self.votes_change_violations(id).iter().map(VoteError::message));
+        violations
+    }
+
+    // Every reason `account` may not edit the event, shared by edit_event
+    fn edit_violations(&self, id: usize, account: &AccountId) -> Vec<String> {
+        if let Some(error) = self.missing_event_error(id) {
+            return vec![error.message()];
+        }
+        if self.events[id].creator != *account {
+            return vec!["Only the event creator can edit the event".to_string()];
+        }
+        vec![]
+    }
+
+    // Every reason `account` may not set the event's voting deadline, shared by set_voting_deadline.
+    // Once a deadline is set, the creator changes it only through the capped extend_deadline.
+    fn deadline_violations(&self, id: usize, account: &AccountId) -> Vec<String> {
+        if let Some(error) = self.missing_event_error(id) {
+            return vec![error.message()];
+        }
+        let event: &Event = &self.events[id];
+        let is_owner = *account == self.owner;
+        if !is_owner && event.creator != *account {
+            let reason = "Only the event creator or the owner can set the voting deadline";
+            return vec![reason.to_string()];
+        }
+        let mut violations = Vec::new();
+        if !is_owner && event.voting_deadline.is_some() {
+            let reason = "Voting deadline is already set; use extend_deadline to change it";
+            violations.push(reason.to_string());
+        }
+        let now = env::block_timestamp().saturating_sub(self.config.deadline_grace_period_ns);
+        if !event.is_open(now) {
+            violations.push(VoteError::VotingClosed.message());
+        }
+        violations
+    }
+
+    // Open donations once the raw vote count reaches the event's threshold, notifying the relay.
+    // Removing votes later does not close them again, so donors are never turned away from an event they already backed.
+    fn unlock_funding_if_reached(&mut self, id: usize) {
+        let event: &mut Event = &mut self.events[id];
+        let reached = event
+            .min_votes_for_funding
+            .is_some_and(|min| event.total_votes as u64 >= min);
+        if event.funding_unlocked || !reached {
+            return;
+        }
+        event.funding_unlocked = true;
+        let log = FundingUnlockedLog {
+            event_id: id as u64,
+            total_votes: event.total_votes as u64,
+        };
+        self.notify(&log);
+    }
+
+    // Category of an event, taken from the template it was created from
+    fn category_of(&self, event: &Event) -> Option<String> {
+        event
+            .template_id
+            .and_then(|id| self.templates.get(id as usize))
+            .map(|template| template.template.category.clone())
+    }
+
+    // Get a template new events may use, panicking if it does not exist or was deactivated
+    fn active_template(&self, id: u64) -> &PublishedTemplate {
+        let template = self.templates.get(id as usize).expect("Template not found");
+        assert!(template.active, "Template is not active");
+        template
+    }
+
+    // Book a movement of NEAR in the ledger; every method that takes, holds or sends NEAR must go through it
+    fn transfer_internal(
+        &mut self,
+        from: LedgerAccount,
+        to: LedgerAccount,
+        amount: u128,
+        memo: &str,
+    ) {
+        self.ledger.transfer(from, to, amount, memo);
+    }
+
+    // Finalize the round in progress, see finalize_round; `forced_reason` is set when the turnout check was skipped
+    fn internal_finalize_round(
+        &mut self,
+        max_approvals: u32,
+        forced_reason: Option<String>,
+    ) -> Vec<u64> {
+        self.round += 1;
+
+        // Rank the events not approved yet by votes, breaking ties with the configured strategy
+        let mut candidates: Vec<(usize, i64, u128, Option<String>)> = self
+            .visible_events()
+            .filter(|event| event.approved_in_round.is_none())
+            .map(|event| {
+                let id = event.id as usize;
+                (id, event.total_votes, event.estimated_budget, self.category_of(event))
+            })
+            .collect();
+        let mut event_ids: Vec<u64> = candidates.iter().map(|&(id, ..)| id as u64).collect();
+        event_ids.sort_unstable();
+        let total_votes = candidates.iter().map(|&(_, votes, ..)| votes.max(0) as u64).sum();
+        for &id in &event_ids {
+            self.events[id as usize].round = Some(self.round);
+        }
+        let tie_break = self.config.tie_break;
+        let seed = env::random_seed();
+        candidates.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| tie_break.compare(&self.events[a.0], &self.events[b.0], &seed))
+        });
+
+        let mut approved = Vec::new();
+        let mut total_paid: u128 = 0;
+        for (id, _, budget, category) in candidates {
+            if approved.len() >= max_approvals as usize {
+                break;
+            }
+
+            // Skip events their category cannot fit this round
+            if let Some(category) = category {
+                let key = (self.round, category.clone());
+                let committed = self.category_committed.get(&key).unwrap_or(0);
+                let cap = self.category_caps.get(&category).unwrap_or(u128::MAX);
+                if committed.saturating_add(budget) > cap {
+                    continue;
+                }
+                self.category_committed.insert(&key, &(committed + budget));
+            }
+
+            self.events[id].approved_in_round = Some(self.round);
+            let certification = self.auditor.is_some().then_some(Certification::Pending);
+            self.events[id].certification = certification;
+            self.notify(&EventApprovedLog {
+                event_id: id as u64,
+                round: self.round,
+            });
+            approved.push(id as u64);
+            total_paid = total_paid.saturating_add(budget);
+        }
+
+        // Archive the round and open the next one
+        let now = env::block_timestamp();
+        let record = RoundRecord {
+            round: self.round,
+            started_at: self.round_started_at,
+            ended_at: now,
+            event_ids,
+            winner_ids: approved.clone(),
+            total_votes,
+            total_paid: U128(total_paid),
+            tie_break,
+            forced_reason,
+        };
+        self.rounds.insert(&self.round, &record);
+        self.round_started_at = now;
+        self.record_change();
+
+        approved
+    }
+
+    // Send NEAR out of the contract, holding it as owed to the recipient until the transfer is settled.
+    // Every outgoing transfer must go through here so a lost callback can be reconciled.
+    fn send_transfer(
+        &mut self,
+        to: AccountId,
+        amount: u128,
+        source: LedgerAccount,
+        event_id: u64,
+        purpose: &str,
+    ) {
+        let owed = LedgerAccount::PendingRefund(event_id, to.clone());
+        self.transfer_internal(source, owed, amount, purpose);
+
+        let id = self.next_transfer_id;
+        self.next_transfer_id += 1;
+        let transfer = PendingTransfer {
+            id,
+            to,
+            amount: U128(amount),
+            purpose: purpose.to_string(),
+            event_id,
+            created_at: env::block_timestamp(),
+            attempt: 1,
+            status: TransferStatus::Pending,
+        };
+        self.transfers.insert(&id, &transfer);
+        self.open_transfers.push(id);
+        dispatch_transfer(&transfer);
+    }
+
+    // Record the outcome of a pending transfer, booking completed ones out of the ledger
+    fn settle_transfer(&mut self, mut transfer: PendingTransfer, status: TransferStatus) {
+        if status == TransferStatus::Completed {
+            // Count donation refunds towards the progress of the deleted event they came from
+            if let Some(event) = self.events.get_mut(transfer.event_id as usize) {
+                if event.status == EventStatus::Deleted && transfer.purpose == "donation_refund" {
+                    event.refunds_settled += 1;
+                }
+            }
+            let owed = LedgerAccount::PendingRefund(transfer.event_id, transfer.to.clone());
+            let amount = transfer.amount.0;
+            self.transfer_internal(owed, LedgerAccount::External, amount, &transfer.purpose);
+            self.open_transfers.retain(|&id| id != transfer.id);
+        }
+        transfer.status = status;
+        self.transfers.insert(&transfer.id, &transfer);
+        self.record_change();
+    }
+
+    // Add a visible event to the title prefix index
+    fn index_title(&mut self, id: u64, title: &str) {
+        let prefix = title_prefix(title);
+        let mut ids = self.title_prefixes.get(&prefix).unwrap_or_default();
+        ids.push(id);
+        self.title_prefixes.insert(&prefix, &ids);
+    }
+
+    // Remove an event from the title prefix index, dropping its bucket once empty
+    fn unindex_title(&mut self, id: u64, title: &str) {
+        let prefix = title_prefix(title);
+        if let Some(mut ids) = self.title_prefixes.get(&prefix) {
+            ids.retain(|&other| other != id);
+            if ids.is_empty() {
+                self.title_prefixes.remove(&prefix);
+            } else {
+                self.title_prefixes.insert(&prefix, &ids);
+            }
+        }
+    }
+
+    // Add an event to the IDs the account has a vote on
+    fn index_vote(&mut self, voter: &AccountId, id: u64) {
+        let mut ids = self.account_votes.get(voter).unwrap_or_default();
+        ids.push(id);
+        self.account_votes.insert(voter, &ids);
+    }
+
+    // Remove an event from the IDs the account has a vote on, dropping the record once empty
+    fn unindex_vote(&mut self, voter: &AccountId, id: u64) {
+        if let Some(mut ids) = self.account_votes.get(voter) {
+            ids.retain(|&other| other != id);
+            if ids.is_empty() {
+                self.account_votes.remove(voter);
+            } else {
+                self.account_votes.insert(voter, &ids);
+            }
+        }
+    }
+
+    // Emit a log of a state change and fold the change into the rolling checksum
+    fn log_change<T: ContractLog>(&mut self, log: &T) {
+        emit(log);
+        self.record_change();
+    }
+
+    // Advance the change sequence and checksum, emitting a heartbeat every `heartbeat_interval` changes.
+    // Logged changes come through log_change; every other change method calls this once it is done.
+    fn record_change(&mut self) {
+        self.change_seq += 1;
+        let (event_count, total_votes_cast, total_raised) = self.checksum_counters();
+        let mut input = self.checksum.to_vec();
+        input.extend_from_slice(&event_count.to_le_bytes());
+        input.extend_from_slice(&total_votes_cast.to_le_bytes());
+        input.extend_from_slice(&total_raised.to_le_bytes());
+        input.extend_from_slice(&self.change_seq.to_le_bytes());
+        self.checksum = env::sha256_array(&input);
+
+        let interval = self.config.heartbeat_interval;
+        if interval > 0 && self.change_seq % interval == 0 {
+            emit(&HeartbeatLog {
+                checksum: to_hex(&self.checksum),
+                event_count,
+                total_votes_cast,
+                total_raised: U128(total_raised),
+                change_seq: self.change_seq,
+            });
+        }
+    }
+
+    // Number of events ever added, votes held by all events and donations held by all events
+    fn checksum_counters(&self) -> (u64, u64, u128) {
+        let event_count = self.events.len() as u64;
+        (event_count, self.total_votes_cast, self.total_raised)
+    }
+
+    // Emit a log and queue a copy in the outbox, evicting the oldest notification when it is full
+    fn notify<T: ContractLog>(&mut self, log: &T) {
+        self.log_change(log);
+        if self.outbox_next - self.outbox_head >= MAX_OUTBOX {
+            self.outbox.remove(&self.outbox_head);
+            self.outbox_head += 1;
+            self.outbox_dropped += 1;
+        }
+        let notification = Notification {
+            id: self.outbox_next,
+            kind: T::EVENT.to_string(),
+            payload_json: serde_json::to_string(log).expect("Log must serialize to JSON"),
+            created_at: env::block_timestamp(),
+        };
+        self.outbox.insert(&notification.id, &notification);
+        self.outbox_next += 1;
+    }
+
+    // Panic unless the caller is the contract owner
+    fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner,
+            "Only the owner can call this method"
+        );
+    }
+
+    // Panic unless the budget lies within the configured bounds
+    fn assert_valid_budget(&self, estimated_budget: u128) {
+        assert!(
+            estimated_budget >= self.config.min_budget
+                && estimated_budget <= self.config.max_budget,
+            "Estimated budget must be between {} and {}",
+            self.config.min_budget,
+            self.config.max_budget
+        );
+    }
+}
+
+// Check whether `child` is exactly one label below `parent`, respecting the `.` boundary
+fn is_direct_sub_account(child: &AccountId, parent: &AccountId) -> bool {
+    child
+        .as_str()
+        .strip_suffix(parent.as_str())
+        .and_then(|prefix| prefix.strip_suffix('.'))
+        .is_some_and(|label| !label.is_empty() && !label.contains('.'))
+}
+
+// Clean up a single-line string: trim it, collapse internal whitespace runs and strip control characters
+fn sanitize_line(field: &str, value: &str, max_chars: usize) -> String {
+    check_sanitized(field, clean_line(value), max_chars)
+}
+
+// The clean-up half of sanitize_line, without the length checks
+fn clean_line(value: &str) -> String {
+    let cleaned: String = value.chars().filter(|c| !c.is_control() || c.is_whitespace()).collect();
+    cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Clean up a multi-line string: trim it and strip control characters, keeping line breaks
+fn sanitize_text(field: &str, value: &str, max_chars: usize) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter_map(|c| match c {
+            '\n' => Some(c),
+            c if c.is_control() && c.is_whitespace() => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect();
+    check_sanitized(field, cleaned.trim().to_string(), max_chars)
+}
+
+// Reject strings that are empty after sanitization or longer than the limit
+fn check_sanitized(field: &str, cleaned: String, max_chars: usize) -> String {
+    assert!(!cleaned.is_empty(), "{} cannot be empty", field);
+    assert!(
+        cleaned.chars().count() <= max_chars,
+        "{} cannot exceed {} characters",
+        field,
+        max_chars
+    );
+    cleaned
+}
+
+// Count the words that look like links: anything with a scheme or starting with www.
+fn count_links(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| {
+            let word = word.to_lowercase();
+            word.contains("://") || word.starts_with("www.")
+        })
+        .count()
+}
+
+// Reject proposal links that are not https, contain whitespace or control characters, or are too long
+fn validate_proposal_url(url: &str) -> String {
+    let host = url
+        .strip_prefix("https://")
+        .unwrap_or_else(|| env::panic_str("Proposal URL must start with https://"));
+    assert!(
+        !host.is_empty() && !host.starts_with('/'),
+        "Proposal URL must include a host"
+    );
+    assert!(
+        !url.chars().any(|c| c.is_whitespace() || c.is_control()),
+        "Proposal URL cannot contain whitespace or control characters"
+    );
+    assert!(
+        url.chars().count() < MAX_PROPOSAL_URL_CHARS,
+        "Proposal URL must be shorter than {} characters",
+        MAX_PROPOSAL_URL_CHARS
+    );
+    url.to_string()
+}
+
+// Storage bytes a first event by the longest possible account takes, including its index records.
+// A proposal URL is counted at its longest, the title is assumed to open a new prefix bucket, and a
+// template's required tags are not counted.
+fn creation_storage_bytes(title_len: u32, description_len: u32, options: u8) -> u64 {
+    let known =
+        CREATION_OPTION_PROPOSAL_URL | CREATION_OPTION_FUNDING_THRESHOLD | CREATION_OPTION_TEMPLATE;
+    assert_eq!(options & !known, 0, "Unknown creation options");
+
+    let mut bytes = EVENT_BASE_BYTES + MAX_ACCOUNT_ID_BYTES;
+    bytes += title_len as u64 + description_len as u64;
+    if options & CREATION_OPTION_PROPOSAL_URL != 0 {
+        bytes += 4 + MAX_PROPOSAL_URL_CHARS as u64;
+    }
+    if options & CREATION_OPTION_FUNDING_THRESHOLD != 0 {
+        bytes += 8;
+    }
+    if options & CREATION_OPTION_TEMPLATE != 0 {
+        bytes += 8;
+    }
+    let prefix_len = (title_len as u64).min(4 * TITLE_PREFIX_CHARS as u64);
+    bytes + CREATION_INDEX_BYTES + TITLE_BUCKET_BYTES + 2 * prefix_len
+}
+
+// Leaf of an event's vote Merkle tree: sha256(voter || event_id || cast_at), integers as little-endian u64
+fn vote_leaf_hash(voter: &AccountId, event_id: u64, cast_at: Timestamp) -> [u8; 32] {
+    let preimage = [voter.as_bytes(), &event_id.to_le_bytes(), &cast_at.to_le_bytes()].concat();
+    env::sha256_array(&preimage)
+}
+
+// Parent of two sibling nodes of a vote Merkle tree: sha256(left || right)
+fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    env::sha256_array(&[&left[..], &right[..]].concat())
+}
+
+// Bucket of a title in the prefix index: its first TITLE_PREFIX_CHARS characters, lowercased
+fn title_prefix(title: &str) -> String {
+    title.to_lowercase().chars().take(TITLE_PREFIX_CHARS).collect()
+}
+
+// Lowercase hex encoding of bytes
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Quote a CSV field if it contains a comma, quote or line break, doubling any quotes inside
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Panic unless the budget bounds are usable
+fn assert_valid_budget_bounds(min_budget: u128, max_budget: u128) {
+    // A zero minimum would let division-by-budget math blow up again
+    assert!(min_budget > 0, "Minimum budget must be greater than zero");
+    assert!(
+        min_budget <= max_budget,
+        "Minimum budget cannot exceed maximum budget"
+    );
+}
+
+// Panic unless the loyalty tiers are sorted by strictly ascending age and never reduce a vote
+fn assert_valid_loyalty_tiers(loyalty_tiers: &[LoyaltyTier]) {
+    assert!(
+        loyalty_tiers.windows(2).all(|w| w[0].min_age_secs < w[1].min_age_secs),
+        "Loyalty tiers must be sorted by ascending age"
+    );
+    assert!(
+        loyalty_tiers.iter().all(|tier| tier.multiplier >= 1),
+        "Loyalty multipliers must be at least 1"
+    );
+}
+
+// Clean up the strings of a template and check that its budget range is not empty
+fn clean_template(template: Template) -> Template {
+    assert!(
+        template.min_budget <= template.max_budget,
+        "Template minimum budget cannot exceed its maximum"
+    );
+    Template {
+        category: sanitize_line("Category", &template.category, MAX_CATEGORY_CHARS),
+        required_tags: template
+            .required_tags
+            .iter()
+            .map(|tag| sanitize_line("Tag", tag, MAX_TAG_CHARS))
+            .collect(),
+        ..template
+    }
+}
+
+// Send a transfer's amount to its recipient, with a callback to settle the latest attempt
+fn dispatch_transfer(transfer: &PendingTransfer) {
+    Promise::new(transfer.to.clone())
+        .transfer(NearToken::from_yoctonear(transfer.amount.0))
+        .then(
+            Contract::ext(env::current_account_id())
+                .with_static_gas(TRANSFER_CALLBACK_GAS)
+                .on_transfer_complete(transfer.id, transfer.attempt),
+        );
+}
+
+// Parse a stored voter string as an account ID, lowercasing and trimming it first so legacy entries
+// with odd casing or whitespace still match the account they name
+fn normalize_voter(voter: &str) -> Option<AccountId> {
+    voter.trim().to_ascii_lowercase().parse().ok()
+}
+
+// Check whether a stored voter string names the given account
+fn is_same_voter(stored: &str, voter: &AccountId) -> bool {
+    normalize_voter(stored).as_ref() == Some(voter)
+}
+
+// Key a stored voter string for deduplication, keeping unparseable legacy entries as they are
+fn voter_key(voter: &str) -> String {
+    normalize_voter(voter).map_or_else(|| voter.to_string(), String::from)
+}
+
+// Uppercase a short code, rejecting anything but 3 to 12 ASCII letters and digits
+fn normalize_short_code(code: &str) -> String {
+    let code = code.trim();
+    assert!(
+        (MIN_SHORT_CODE_CHARS..=MAX_SHORT_CODE_CHARS).contains(&code.len())
+            && code.chars().all(|c| c.is_ascii_alphanumeric()),
+        "Short code must be {} to {} letters or digits",
+        MIN_SHORT_CODE_CHARS,
+        MAX_SHORT_CODE_CHARS
+    );
+    code.to_ascii_uppercase()
+}
+
+/*
+ * The rest of this file holds the inline tests for the code above
+ * Learn more about Rust tests: https://doc.rust-lang.org/book/ch11-01-writing-tests.html
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::IntoStorageKey;
+    use near_sdk::testing_env;
+
+    use crate::test_support::{account, contract_with_events, ctx, Scenario, ALICE, BOB, CAROL};
+    use proptest::prelude::{
+        prop_assert, prop_assert_eq, prop_oneof, proptest, ProptestConfig, Strategy, TestCaseError,
+    };
+
+    // Function to set up the testing context and unit test environment
+    fn get_context(predecessor: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        builder
+    }
+
+    // Builder for deterministic Event fixtures that don't read the env
+    struct EventBuilder {
+        event: Event,
+    }
+
+    impl EventBuilder {
+        // Start from a fixture with fixed creator and timestamp
+        fn new(id: i32) -> Self {
+            Self {
+                event: Event {
+                    id,
+                    creator: "alice.testnet".parse().unwrap(),
+                    created_at: 0,
+                    title: format!("Event {}", id),
+                    estimated_budget: 100,
+                    total_votes: 0,
+                    description: "Fixture event".to_string(),
+                    votes: vec![],
+                    tags: vec![],
+                    voting_deadline: None,
+                    deadline_extended: false,
+                    votes_open_at: 0,
+                    budget_history: vec![],
+                    budget_locked: false,
+                    loyalty_weighted: false,
+                    weighted_total: 0,
+                    min_voter_balance: None,
+                    proposal_url: None,
+                    questions: vec![],
+                    answer_tallies: vec![],
+                    status: EventStatus::Open,
+                    draft: false,
+                    deleted_at: None,
+                    funds_raised: 0,
+                    min_votes_for_funding: None,
+                    funding_unlocked: true,
+                    funding_cap: FundingCap::Unlimited,
+                    donors: vec![],
+                    short_code: None,
+                    funded: false,
+                    template_id: None,
+                    approved_in_round: None,
+                    certification: None,
+                    round: None,
+                    refund_cursor: 0,
+                    refunds_settled: 0,
+                    vote_leaves: 0,
+                    vote_frontier: vec![],
+                    vote_merkle_root: None,
+                },
+            }
+        }
+
+        // Set the creator of the event
+        fn creator(mut self, creator: &str) -> Self {
+            self.event.creator = creator.parse().unwrap();
+            self
+        }
+
+        // Set the creation timestamp of the event
+        fn created_at(mut self, created_at: Timestamp) -> Self {
+            self.event.created_at = created_at;
+            self
+        }
+
+        // Set the title of the event
+        fn title(mut self, title: &str) -> Self {
+            self.event.title = title.to_string();
+            self
+        }
+
+        // Set the estimated budget of the event
+        fn budget(mut self, estimated_budget: u128) -> Self {
+            self.event.estimated_budget = estimated_budget;
+            self
+        }
+
+        // Record the given voters and keep the counter in sync
+        fn voters(mut self, voters: &[&str]) -> Self {
+            self.event.votes = voters.iter().map(|v| v.to_string()).collect();
+            self.event.total_votes = voters.len() as i64;
+            self.event.weighted_total = voters.len() as u64;
+            self
+        }
+
+        // Set the tags of the event
+        fn tags(mut self, tags: &[&str]) -> Self {
+            self.event.tags = tags.iter().map(|t| t.to_string()).collect();
+            self
+        }
+
+        // Set the voting deadline of the event
+        fn deadline(mut self, voting_deadline: Timestamp) -> Self {
+            self.event.voting_deadline = Some(voting_deadline);
+            self
+        }
+
+        // Leave the event as an unpublished draft, like add_event does
+        fn draft(mut self) -> Self {
+            self.event.draft = true;
+            self
+        }
+
+        // Return the finished event, recording its budget as the first revision like Event::new
+        fn build(mut self) -> Event {
+            if self.event.budget_history.is_empty() {
+                self.event.budget_history =
+                    vec![(self.event.created_at, self.event.estimated_budget)];
+            }
+            self.event.votes_open_at = self.event.votes_open_at.max(self.event.created_at);
+            self.event
+        }
+    }
+
+    // Unit test to add a project
+    #[test]
     fn add_project() {
+        // Create a new instance of the contract with one event by Alice, the owner
+        let mut contract = contract_with_events(1);
+
+        // Assert that the count of events is equal to 1
+        assert_eq!(contract.event_count(), 1);
+    }
+
+    // Unit test to add a voter
+    #[test]
+    fn add_voter() {
+        // Alice adds an event and votes for it
+        let mut contract = Scenario::new()
+            .create(ALICE, "New Contemporary Art Show", 200, None)
+            .vote(ALICE, 0)
+            .contract();
+
+        // Assert that the total votes for the first event is equal to 1
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Scenario test to reject votes once the deadline has passed, keeping the earlier ones
+    #[test]
+    fn scenario_deadline_expiry() {
+        // Bob adds an event, Carol votes for it, then voting closes
+        let mut contract = Scenario::new()
+            .create(BOB, "Book Fair", 100, None)
+            .vote(CAROL, 0)
+            .close(0)
+            .contract();
+
+        // Assert that Alice's late vote is rejected and Carol's still counts
+        assert_eq!(contract.try_add_vote(0), Err(VoteError::VotingClosed.message()));
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Scenario test to reject a second vote by the same account
+    #[test]
+    fn scenario_duplicate_vote() {
+        // Alice adds an event and Bob votes for it
+        let mut contract = Scenario::new()
+            .create(ALICE, "Art Show", 100, None)
+            .vote(BOB, 0)
+            .contract();
+
+        // Assert that Bob's second vote is rejected and not counted
+        ctx(BOB, 0, 0);
+        assert_eq!(contract.try_add_vote(0), Err(VoteError::AlreadyVoted.message()));
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Scenario test to page through the voters of an event
+    #[test]
+    fn scenario_voter_pagination() {
+        // Three accounts vote for the second of two events
+        let contract = Scenario::new()
+            .events(2)
+            .vote(ALICE, 1)
+            .vote(BOB, 1)
+            .vote(CAROL, 1)
+            .contract();
+
+        // Assert that pages follow voting order and stop at the end
+        assert_eq!(contract.get_voters(1, 0, 2), vec![ALICE.to_string(), BOB.to_string()]);
+        assert_eq!(contract.get_voters(1, 2, 2), vec![CAROL.to_string()]);
+        assert!(contract.get_voters(1, 3, 2).is_empty());
+        assert!(contract.get_voters(0, 0, 2).is_empty());
+    }
+
+    // Scenario test to keep an event's changes with its creator, even against the contract owner
+    #[test]
+    #[should_panic(expected = "Only the event creator can edit the event")]
+    fn scenario_creator_ownership() {
+        // Bob adds an event on Alice's contract
+        let mut contract = Scenario::new().create(BOB, "Book Fair", 100, None).contract();
+
+        // Alice, the owner, tries to rename it
+        contract.edit_event(0, Some("Alice's Fair".to_string()), None, None);
+    }
+
+    // Scenario test to unlock donations with votes and complete the budget
+    #[test]
+    fn scenario_funding_flow() {
+        // Bob adds an event needing one vote before donations, Carol votes, then Carol and Alice donate
+        let contract = Scenario::new()
+            .create(BOB, "Book Fair", 100, Some(1))
+            .vote(CAROL, 0)
+            .donate(CAROL, 0, 60)
+            .donate(ALICE, 0, 40)
+            .contract();
+
+        // Assert that the budget was raised from both donors and the relay heard about each step
+        let funding = contract.get_event(0).funding;
+        assert_eq!(funding.funds_raised, U128(100));
+        assert_eq!(funding.donor_count, 2);
+        let outbox = contract.peek_outbox(0, 10);
+        let kinds: Vec<&str> = outbox.iter().map(|n| n.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["funding_unlocked", "event_funded"]);
+    }
+
+    // Scenario test for the shape and paging of an account's data export
+    #[test]
+    fn export_account_data() {
+        // Alice adds an event that Bob votes and donates on, then Bob adds his own at a later time
+        let contract = Scenario::new()
+            .events(1)
+            .vote(BOB, 0)
+            .donate(BOB, 0, 30)
+            .advance(500)
+            .create(BOB, "Book Fair", 100, None)
+            .contract();
+
+        // Assert that the first page holds Bob's vote and donation on event 0, and points to event 1
+        let page = contract.export_account_data(account(BOB), 0, 1);
+        assert_eq!(page.account_id, account(BOB));
+        assert_eq!(page.registered_at, Some(0));
+        assert_eq!(page.last_event_at, Some(500));
+        assert!(page.is_creator);
+        assert!(page.created_events.is_empty());
+        assert_eq!(
+            page.votes,
+            vec![AccountVote { event_id: 0, cast_at: Some(0), fee: U128(0), weight: 1 }]
+        );
+        assert_eq!(page.donations, vec![AccountDonation { event_id: 0, amount: U128(30) }]);
+        assert_eq!(page.next_from_event, Some(1));
+
+        // Assert that the last page holds the event Bob created and nothing more
+        let page = contract.export_account_data(account(BOB), 1, 10);
+        assert_eq!(page.created_events, vec![1]);
+        assert!(page.votes.is_empty() && page.donations.is_empty());
+        assert_eq!(page.next_from_event, None);
+    }
+
+    // Scenario test to erase an account's registration and creator defaults while keeping its votes and donations
+    #[test]
+    fn erase_profile() {
+        let mut contract = Scenario::new().events(1).vote(BOB, 0).donate(BOB, 0, 30).contract();
+        let storage_cost = env::storage_byte_cost().as_yoctonear() * CREATOR_DEFAULTS_BYTES as u128;
+        ctx(BOB, storage_cost, 0);
+        contract.set_creator_defaults(CreatorDefaults::default());
+
+        // Bob erases the profile, which holds the registration time and the saved defaults
+        ctx(BOB, 0, 0);
+        assert!(contract.erase_profile());
+        assert!(!contract.erase_profile());
+
+        // Assert that the defaults are gone and their storage deposit is on its way back
+        assert_eq!(contract.get_creator_defaults(account(BOB)), None);
+        let refund = &contract.list_pending_transfers()[0];
+        assert_eq!(refund.purpose, "storage_refund");
+        assert_eq!(refund.to, account(BOB));
+        assert_eq!(refund.amount, U128(storage_cost));
+
+        // Assert that the vote still counts and the donation is still held for refunds
+        let export = contract.export_account_data(account(BOB), 0, 10);
+        assert_eq!(export.registered_at, None);
+        assert_eq!(export.votes.len(), 1);
+        assert_eq!(export.donations.len(), 1);
+        assert_eq!(contract.get_total_votes(0), 1);
+        assert_eq!(contract.get_event(0).funding.funds_raised, U128(30));
+    }
+
+    // Unit test to vote for several events at once, skipping the ones that cannot take the vote
+    #[test]
+    fn add_votes_batch() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract with three events, Bob having voted for the second
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(2).build());
+
+        // Vote for all of them, a missing one and the third one twice
+        let results = contract.add_votes_batch(vec![0, 1, 2, 7, 2]);
+
+        // Assert that only the new votes were cast, without aborting the rest
+        assert_eq!(results, vec![(0, true), (1, false), (2, true), (7, false), (2, false)]);
+        assert_eq!(contract.get_total_votes(0), 1);
+        assert_eq!(contract.get_total_votes(1), 1);
+        assert_eq!(contract.get_total_votes(2), 1);
+    }
+
+    // Unit test to refuse batches while votes cost a fee, since one deposit cannot pay for several
+    #[test]
+    #[should_panic(expected = "Batch voting is unavailable while votes require a deposit")]
+    fn add_votes_batch_with_fee() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract charging a vote fee, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_vote_fee(U128(100));
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Bob tries to vote in a batch
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_votes_batch(vec![0]);
+    }
+
+    // Unit test to check a consistent event
+    #[test]
+    fn event_is_consistent() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event voted on by Bob
+        let mut contract = Contract::new(alice);
+        let event = EventBuilder::new(0).voters(&["bob.testnet"]).build();
+        contract.events.push(event.clone());
+
+        // Assert that the stored event is the fixture and its counter matches the voters
+        assert_eq!(contract.list_events(), vec![event]);
+        assert!(contract.is_event_consistent(0));
+    }
+
+    // Unit test to check that a desynced event is reported
+    #[test]
+    fn event_is_inconsistent() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event voted on by Bob
+        let mut contract = Contract::new(alice);
+        contract
+            .events
+            .push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+
+        // Artificially bump the counter without recording a voter
+        contract.events[0].total_votes += 1;
+
+        // Assert that the mismatch is detected
+        assert!(!contract.is_event_consistent(0));
+    }
+
+    // Unit test to check that add_event stores the expected event
+    #[test]
+    fn add_event_matches_fixture() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context with Alice signing at a fixed time
+        let mut context = get_context(alice.clone());
+        context.signer_account_id(alice.clone()).block_timestamp(42);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Fixture event".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that the stored event equals the fixture built by hand, still a draft
+        let expected = EventBuilder::new(0)
+            .creator("alice.testnet")
+            .created_at(42)
+            .title("Art Show")
+            .budget(200)
+            .draft()
+            .build();
+        assert_eq!(contract.list_my_drafts(), vec![expected]);
+    }
+
+    // Unit test to set tags on an event
+    #[test]
+    fn set_tags() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with Alice's event
+        let mut contract = Contract::new(alice.clone());
+        contract
+            .events
+            .push(EventBuilder::new(0).creator("alice.testnet").build());
+
+        // Tag the event
+        contract.set_event_tags(0, vec!["art".to_string()]);
+
+        // Assert that the tag was stored
+        assert_eq!(contract.list_events()[0].tags, vec!["art".to_string()]);
+    }
+
+    // Unit test to reject tagging someone else's event
+    #[test]
+    #[should_panic(expected = "Only the event creator can set tags")]
+    fn set_tags_not_creator() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(bob.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with Alice's event
+        let mut contract = Contract::new(bob);
+        contract
+            .events
+            .push(EventBuilder::new(0).creator("alice.testnet").build());
+
+        // Try to tag Alice's event as Bob
+        contract.set_event_tags(0, vec!["art".to_string()]);
+    }
+
+    // Unit test to combine search filters
+    #[test]
+    fn query_events_combines_filters() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a mix of events
+        let mut contract = Contract::new(alice.clone());
+        let art_by_alice = EventBuilder::new(0)
+            .creator("alice.testnet")
+            .tags(&["art"])
+            .voters(&["bob.testnet", "carol.testnet"])
+            .build();
+        let music_by_alice = EventBuilder::new(1)
+            .creator("alice.testnet")
+            .tags(&["music"])
+            .voters(&["bob.testnet", "carol.testnet"])
+            .build();
+        let art_by_bob = EventBuilder::new(2)
+            .creator("bob.testnet")
+            .tags(&["art"])
+            .voters(&["carol.testnet"])
+            .build();
+        let quiet_art_by_alice = EventBuilder::new(3)
+            .creator("alice.testnet")
+            .tags(&["art"])
+            .build();
+        contract.events = vec![
+            art_by_alice.clone(),
+            music_by_alice.clone(),
+            art_by_bob.clone(),
+            quiet_art_by_alice.clone(),
+        ];
+
+        // No filters return every event
+        assert_eq!(contract.query_events(None, None, None).len(), 4);
+
+        // Creator and tag together return the intersection
+        assert_eq!(
+            contract.query_events(Some(alice.clone()), Some("art".to_string()), None),
+            vec![art_by_alice.clone(), quiet_art_by_alice]
+        );
+
+        // Adding a vote threshold narrows it further
+        assert_eq!(
+            contract.query_events(Some(alice), Some("art".to_string()), Some(1)),
+            vec![art_by_alice.clone()]
+        );
+
+        // A vote threshold alone ignores creator and tag
+        assert_eq!(
+            contract.query_events(None, None, Some(2)),
+            vec![art_by_alice, music_by_alice]
+        );
+
+        // Filters with no overlap return nothing
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        assert!(contract
+            .query_events(Some(bob), Some("music".to_string()), None)
+            .is_empty());
+    }
+
+    // Unit test to reject a zero budget
+    #[test]
+    #[should_panic(expected = "Estimated budget must be between")]
+    fn add_event_zero_budget() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event without a budget
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            0,
+            "Free event".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to reject the maximum possible budget
+    #[test]
+    #[should_panic(expected = "Estimated budget must be between")]
+    fn add_event_max_budget() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event with an absurd budget
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            u128::MAX,
+            "Pricey event".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test for the owner tightening the budget bounds
+    #[test]
+    fn set_budget_bounds() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and tighten the bounds
+        let mut contract = Contract::new(alice);
+        contract.set_budget_bounds(U128(100), U128(500));
+
+        // Assert that the new bounds are stored and a budget inside them is accepted
+        assert_eq!(contract.get_config().min_budget, 100);
+        assert_eq!(contract.get_config().max_budget, 500);
+        contract.add_event(
+            "Art Show".to_string(),
+            500,
+            "Within bounds".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        assert_eq!(contract.event_count(), 1);
+    }
+
+    // Unit test to reject budget bounds set by someone other than the owner
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn set_budget_bounds_not_owner() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(bob);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract owned by Alice and try to change the bounds
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.set_budget_bounds(U128(1), U128(10));
+    }
+
+    // Unit test for the total requested budget and its saturation flag
+    #[test]
+    fn total_requested_budget_saturates() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with two ordinary events
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).budget(200).build());
+        contract.events.push(EventBuilder::new(1).budget(300).build());
+
+        // Assert that ordinary budgets sum exactly
+        assert_eq!(
+            contract.total_requested_budget(),
+            BudgetTotal {
+                total: U128(500),
+                saturated: false
+            }
+        );
+
+        // Inject a legacy event with a huge budget that would overflow the sum
+        contract.events.push(EventBuilder::new(2).budget(u128::MAX).build());
+
+        // Assert that the sum is capped and flagged
+        assert_eq!(
+            contract.total_requested_budget(),
+            BudgetTotal {
+                total: U128(u128::MAX),
+                saturated: true
+            }
+        );
+    }
+
+    // Unit test to reject a second vote from the same account
+    #[test]
+    #[should_panic(expected = "You have already voted for this event")]
+    fn add_vote_twice() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Vote twice for the same event
+        contract.add_vote(0, None);
+        contract.add_vote(0, None);
+    }
+
+    // Unit test to remove a paid vote and refund the fee
+    #[test]
+    fn remove_vote_refunds_fee() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+
+        // Set up the testing context with Bob attaching a fee
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(10));
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event and vote for it
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+
+        // Remove the vote without attaching anything
+        let context = get_context(bob.clone());
+        testing_env!(context.build());
+        contract.remove_vote(0);
+
+        // Assert that the vote is gone and a refund of the fee was sent to Bob
+        assert_eq!(contract.get_total_votes(0), 0);
+        assert!(contract.list_events()[0].votes.is_empty());
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, bob);
+        assert_eq!(receipts[1].receiver_id, env::current_account_id());
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(10)
+        ));
+    }
+
+    // Unit test to remove a free vote without any refund
+    #[test]
+    fn remove_free_vote_no_refund() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(bob);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event, then vote and remove it
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+        contract.remove_vote(0);
+
+        // Assert that no refund was scheduled
+        assert!(get_created_receipts().is_empty());
+    }
+
+    // Unit test to make sure a removed vote cannot be refunded twice
+    #[test]
+    #[should_panic(expected = "You have not voted for this event")]
+    fn remove_vote_twice() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+
+        // Set up the testing context with Bob attaching a fee
+        let mut context = get_context(bob);
+        context.attached_deposit(NearToken::from_yoctonear(10));
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event and vote for it
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+
+        // Removing the vote twice must fail the second time
+        contract.remove_vote(0);
+        contract.remove_vote(0);
+    }
+
+    // Scenario test to list only the events the caller created
+    #[test]
+    fn my_events() {
+        // Alice and Bob each add events, Bob's two around one of Alice's
+        let mut contract = Scenario::new()
+            .create(BOB, "Book Fair", 100, None)
+            .events(1)
+            .create(BOB, "Art Show", 100, None)
+            .contract();
+
+        // Assert that Bob gets exactly his two events
+        ctx(BOB, 0, 0);
+        let ids: Vec<i32> = contract.my_events().iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![0, 2]);
+    }
+
+    // Unit test to keep drafts out of listings and show them only to their creator
+    #[test]
+    fn drafts_visible_to_creator_only() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a published fixture event and a draft by Alice
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that only the published event is listed, and Alice sees her draft
+        assert_eq!(contract.list_events().len(), 1);
+        assert_eq!(contract.list_events()[0].id, 0);
+        let drafts = contract.list_my_drafts();
+        assert_eq!(drafts.len(), 1);
+        assert!(drafts[0].draft);
+
+        // Assert that Bob sees no drafts and cannot vote on Alice's
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob.clone()).build());
+        assert!(contract.list_my_drafts().is_empty());
+        assert_eq!(contract.try_add_vote(1), Err("Event is still a draft".to_string()));
+        assert_eq!(contract.dry_run_vote(1, bob).violations, vec![VoteError::EventIsDraft]);
+    }
+
+    // Unit test that another creator's draft is never returned by get_event or latest_event, nor approved in a round
+    #[test]
+    fn drafts_hidden_from_views_and_rounds() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a published event and a newer, more voted draft by Bob
+        let mut contract = Contract::new(alice.clone());
+        let published = EventBuilder::new(0).created_at(100).voters(&["a.testnet"]);
+        contract.events.push(published.build());
+        let voters = ["a.testnet", "b.testnet", "c.testnet"];
+        let draft = EventBuilder::new(1).creator("bob.testnet").created_at(200);
+        contract.events.push(draft.voters(&voters).draft().build());
+
+        // Assert that Alice can neither read the draft nor get it as the latest event
+        let get = std::panic::AssertUnwindSafe(|| contract.get_event(1).id);
+        let error = std::panic::catch_unwind(get).map_err(|e| match e.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => e.downcast_ref::<String>().cloned().unwrap_or_default(),
+        });
+        assert_eq!(error, Err("Event is still a draft".to_string()));
+        assert_eq!(contract.latest_event().unwrap().id, 0);
+
+        // Assert that finalizing a round approves only the published event
+        assert_eq!(contract.finalize_round(2), vec![0]);
+        assert_eq!(contract.events[1].approved_in_round, None);
+
+        // Assert that Bob, the creator, still reads the draft through get_my_event, but not through the view
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        assert_eq!(contract.get_my_event(1).id, 1);
+        let get = std::panic::AssertUnwindSafe(|| contract.get_event(1).id);
+        assert!(std::panic::catch_unwind(get).is_err());
+    }
+
+    // Unit test to keep other accounts from reading a draft through get_my_event
+    #[test]
+    #[should_panic(expected = "Only the event creator can read the event through get_my_event")]
+    fn get_my_event_other_account() {
+        // Create a contract with a draft by Bob, then read it as Carol
+        let mut contract = Scenario::new().contract();
+        let draft = EventBuilder::new(0).creator(BOB).draft();
+        contract.events.push(draft.build());
+        ctx(CAROL, 0, 0);
+        contract.get_my_event(0);
+    }
+
+    // Unit test to publish a draft, which lists it and opens it for votes
+    #[test]
+    fn publish_event() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add a draft
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Publish it and assert that it moved from the drafts to the listing
+        contract.publish_event(0);
+        assert!(contract.list_my_drafts().is_empty());
+        assert_eq!(contract.list_events().len(), 1);
+        assert!(!contract.list_events()[0].draft);
+
+        // Assert that Bob can now vote on it
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test to reject publishing by anyone but the creator
+    #[test]
+    #[should_panic(expected = "Only the event creator can publish the event")]
+    fn publish_event_not_creator() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a draft fixture by Alice
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).draft().build());
+
+        // Bob tries to publish it
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.publish_event(0);
+    }
+
+    // Unit test to trim and collapse whitespace in titles and descriptions
+    #[test]
+    fn add_event_sanitizes_strings() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event with messy strings
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "  Art \t  Show\u{0007}  ".to_string(),
+            200,
+            "Line one\r\nLine two\u{0000}\n\n".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that whitespace was normalized and control characters were stripped
+        let event = &contract.list_my_drafts()[0];
+        assert_eq!(event.title, "Art Show");
+        assert_eq!(event.description, "Line one \nLine two");
+    }
+
+    // Unit test to keep emoji and CJK text intact
+    #[test]
+    fn add_event_keeps_unicode() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event with emoji and CJK strings
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "  🎨   美術展  ".to_string(),
+            200,
+            "現代アートの展示会 🖼️".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that the multi-byte characters survive sanitization
+        let event = &contract.list_my_drafts()[0];
+        assert_eq!(event.title, "🎨 美術展");
+        assert_eq!(event.description, "現代アートの展示会 🖼️");
+    }
+
+    // Unit test to measure the title limit in characters rather than bytes
+    #[test]
+    fn title_limit_counts_characters() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // A title of exactly the limit in CJK characters is far longer than the limit in bytes
+        let title = "展".repeat(MAX_TITLE_CHARS);
+        assert!(title.len() > MAX_TITLE_CHARS);
+
+        // Create a new instance of the contract and add the event
+        let mut contract = Contract::new(alice);
+        contract.add_event(title.clone(), 200, "CJK title".to_string(), None, None, None, None);
+
+        // Assert that the full title was accepted
+        assert_eq!(contract.list_my_drafts()[0].title, title);
+    }
+
+    // Unit test to reject a title one character over the limit
+    #[test]
+    #[should_panic(expected = "Title cannot exceed 100 characters")]
+    fn title_too_long() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event with an overlong emoji title
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "🎉".repeat(MAX_TITLE_CHARS + 1),
+            200,
+            "Too long".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to reject a title that is empty after sanitization
+    #[test]
+    #[should_panic(expected = "Title cannot be empty")]
+    fn title_blank() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event with a blank title
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            " \t\n\u{0007} ".to_string(),
+            200,
+            "Blank title".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test for the average votes per event
+    #[test]
+    fn average_votes() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract without events
+        let mut contract = Contract::new(alice);
+        assert_eq!(contract.average_votes(), 0);
+
+        // Add three events with 1, 2 and 4 votes
+        contract.events.push(EventBuilder::new(0).voters(&["a.testnet"]).build());
+        contract
+            .events
+            .push(EventBuilder::new(1).voters(&["a.testnet", "b.testnet"]).build());
+        contract.events.push(
+            EventBuilder::new(2)
+                .voters(&["a.testnet", "b.testnet", "c.testnet", "d.testnet"])
+                .build(),
+        );
+
+        // Assert that the average is 7 / 3 rounded down
+        assert_eq!(contract.average_votes(), 2);
+    }
+
+    // Unit test for the quartiles of the vote counts
+    #[test]
+    fn vote_quartiles() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract without events
+        let mut contract = Contract::new(alice);
+        assert_eq!(contract.vote_quartiles(), (0, 0, 0));
+
+        // Add nine events with 0 to 8 votes, out of order
+        let voters: Vec<String> = (0..8).map(|i| format!("voter{}.testnet", i)).collect();
+        for (id, count) in [3, 0, 8, 5, 1, 7, 2, 6, 4].into_iter().enumerate() {
+            let voters: Vec<&str> = voters[..count].iter().map(String::as_str).collect();
+            contract.events.push(EventBuilder::new(id as i32).voters(&voters).build());
+        }
+
+        // Assert that the quartiles sit at the 3rd, 5th and 7th smallest counts
+        assert_eq!(contract.vote_quartiles(), (2, 4, 6));
+    }
+
+    // Unit test for the CSV export, quoting titles with commas or quotes
+    #[test]
+    fn export_csv() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a plain event, one with a comma and a quote, and a draft
+        let mut contract = Contract::new(alice);
+        contract.events.push(
+            EventBuilder::new(0)
+                .creator("bob.testnet")
+                .title("Art Show")
+                .budget(500)
+                .voters(&["a.testnet", "b.testnet"])
+                .build(),
+        );
+        contract
+            .events
+            .push(EventBuilder::new(1).title("Food, \"Drinks\"").budget(200).build());
+        contract.events.push(EventBuilder::new(2).title("Hidden").draft().build());
+
+        // Assert that the header comes first, followed by one row per published event
+        let csv = contract.export_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "id,creator,title,total_votes,estimated_budget");
+        assert_eq!(lines[1], "0,bob.testnet,Art Show,2,500");
+        assert_eq!(lines[2], "1,alice.testnet,\"Food, \"\"Drinks\"\"\",0,200");
+        assert_eq!(lines.len(), 3);
+    }
+
+    // Unit test that the schema hash depends only on the build, not on the state
+    #[test]
+    fn schema_hash_is_stable() {
+        let contract = contract_with_events(0);
+        let hash = contract.schema_hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(contract_with_events(3).schema_hash(), hash);
+        assert_eq!(contract.abi_version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(contract.state_version(), STATE_VERSION);
+    }
+
+    // Unit test that every second logged change emits a heartbeat matching get_checksum,
+    // and that refunds and dropped voter entries advance the checksum like logged changes
+    #[test]
+    fn heartbeat_matches_checksum() {
+        let mut contract = contract_with_events(1);
+        contract.set_heartbeat_interval(2);
+        let start = contract.get_checksum();
+
+        // Vote, donate and retract one step at a time, checking the logs of each step
+        let mut heartbeats = 0;
+        for step in 0..6u64 {
+            match step % 3 {
+                0 => ctx(BOB, 0, step),
+                1 => ctx(CAROL, 10, step),
+                _ => ctx(BOB, 0, step),
+            }
+            match step % 3 {
+                0 => contract.add_vote(0, None),
+                1 => contract.donate(0),
+                _ => contract.remove_vote(0),
+            }
+            let checksum = contract.get_checksum();
+            let heartbeat = get_logs()
+                .into_iter()
+                .find(|log| log.contains(r#""event":"heartbeat""#));
+
+            // Assert that a heartbeat came exactly on even sequence numbers and carried the view's values
+            assert_eq!(heartbeat.is_some(), checksum.change_seq % 2 == 0);
+            if let Some(log) = heartbeat {
+                let json: serde_json::Value =
+                    serde_json::from_str(log.trim_start_matches("EVENT_JSON:")).unwrap();
+                let data = &json["data"][0];
+                assert_eq!(data["checksum"], checksum.checksum);
+                assert_eq!(data["event_count"], 1);
+                assert_eq!(data["total_votes_cast"], checksum.total_votes_cast);
+                assert_eq!(data["total_raised"], checksum.total_raised.0.to_string());
+                assert_eq!(data["change_seq"], checksum.change_seq);
+                heartbeats += 1;
+            }
+        }
+        assert!(heartbeats > 0);
+
+        // Assert that the checksum moved and the counters reflect the final state
+        let end = contract.get_checksum();
+        assert_ne!(end.checksum, start.checksum);
+        assert_eq!((end.total_votes_cast, end.total_raised), (0, U128(20)));
+
+        // Assert that refunding the donations of the deleted event advances the checksum
+        ctx(ALICE, 0, 6);
+        contract.delete_event(0);
+        ctx(ALICE, 0, 6 + DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000);
+        let before = contract.get_checksum();
+        contract.process_refunds(0, 10);
+        let after = contract.get_checksum();
+        assert_eq!(after.change_seq, before.change_seq + 1);
+        assert_ne!(after.checksum, before.checksum);
+        assert_eq!(after.total_raised, U128(0));
+
+        // Assert that dropping a repeated voter entry advances it too. The fixture skips add_vote,
+        // so its votes are added to the running total by hand.
+        let repeated = EventBuilder::new(1).voters(&[BOB, BOB]);
+        contract.events.push(repeated.build());
+        contract.total_votes_cast += 2;
+        let before = contract.get_checksum();
+        contract.dedup_voters(1);
+        let after = contract.get_checksum();
+        assert_eq!(after.change_seq, before.change_seq + 1);
+        assert_ne!(after.checksum, before.checksum);
+        assert_eq!(after.total_votes_cast, before.total_votes_cast - 1);
+    }
+
+    // Unit test that change methods logging nothing still advance the checksum, once per call
+    #[test]
+    fn unlogged_changes_advance_checksum() {
+        let mut contract = contract_with_events(1);
+        let steps: Vec<fn(&mut Contract)> = vec![
+            |contract| contract.set_voting_deadline(0, 100),
+            |contract| contract.lock_budget(0),
+            |contract| contract.set_max_open_events(Some(5)),
+            |contract| contract.set_category_cap("Music".to_string(), U128(500)),
+            |contract| {
+                contract.issue_invite();
+            },
+        ];
+        for step in steps {
+            let before = contract.get_checksum();
+            step(&mut contract);
+            let after = contract.get_checksum();
+            assert_eq!(after.change_seq, before.change_seq + 1);
+            assert_ne!(after.checksum, before.checksum);
+        }
+    }
+
+    // Unit test that storage prefixes are unique and unchanged, and that collections sharing a key stay apart
+    #[test]
+    fn storage_keys_do_not_collide() {
+        // Assert that every prefix is the single byte its collection has always used
+        let keys = [
+            (StorageKey::VoteFees, b'f'),
+            (StorageKey::Creators, b'c'),
+            (StorageKey::LastEventAt, b'l'),
+            (StorageKey::RegisteredAt, b'r'),
+            (StorageKey::VoteWeights, b'w'),
+            (StorageKey::Donations, b'd'),
+            (StorageKey::VotedAt, b't'),
+            (StorageKey::Outbox, b'o'),
+            (StorageKey::ShortCodes, b's'),
+            (StorageKey::Invites, b'i'),
+            (StorageKey::CategoryCaps, b'k'),
+            (StorageKey::CategoryCommitted, b'm'),
+            (StorageKey::Rounds, b'h'),
+            (StorageKey::LedgerBalances, b'a'),
+            (StorageKey::LedgerEntries, b'e'),
+            (StorageKey::Transfers, b'x'),
+            (StorageKey::DonationMatches, b'n'),
+            (StorageKey::VoteReceipts, b'v'),
+            (StorageKey::CreatorDefaults, b'g'),
+            (StorageKey::AccountVotes, b'y'),
+            (StorageKey::TitlePrefixes, b'p'),
+        ];
+        let mut prefixes = HashSet::new();
+        for (key, byte) in keys {
+            let prefix = key.into_storage_key();
+            assert_eq!(prefix, vec![byte], "{:?}", key);
+            assert!(prefixes.insert(prefix), "{:?} reuses a prefix", key);
+        }
+
+        // Write the same key to two maps and assert that each reads back its own value
+        let mut contract = contract_with_events(1);
+        let key = (0, account(BOB));
+        contract.voted_at.insert(&key, &42);
+        contract.vote_weights.insert(&key, &3);
+        assert_eq!(contract.voted_at.get(&key), Some(42));
+        assert_eq!(contract.vote_weights.get(&key), Some(3));
+
+        // Remove from one and assert that the other keeps its value
+        contract.voted_at.remove(&key);
+        assert_eq!(contract.voted_at.get(&key), None);
+        assert_eq!(contract.vote_weights.get(&key), Some(3));
+    }
+
+    // Unit test for the range between the most and least voted events
+    #[test]
+    fn vote_spread() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a single event, which has no spread
+        let mut contract = Contract::new(alice);
+        assert_eq!(contract.vote_spread(), 0);
+        contract.events.push(EventBuilder::new(0).voters(&["a.testnet"]).build());
+        assert_eq!(contract.vote_spread(), 0);
+
+        // Add events with 4 and 2 votes
+        let voters = ["a.testnet", "b.testnet", "c.testnet", "d.testnet"];
+        contract.events.push(EventBuilder::new(1).voters(&voters).build());
+        contract.events.push(EventBuilder::new(2).voters(&voters[..2]).build());
+
+        // Assert that the spread runs from 1 to 4 votes
+        assert_eq!(contract.vote_spread(), 3);
+    }
+
+    // Unit test to count events by 30-day month of creation
+    #[test]
+    fn events_per_month() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with events in months 0, 2 and 3, two of them in month 2
+        let month = 30 * SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Contract::new(alice);
+        assert!(contract.events_per_month().is_empty());
+        for (id, created_at) in [0, 2 * month, 3 * month - 1, 3 * month].into_iter().enumerate() {
+            contract.events.push(EventBuilder::new(id as i32).created_at(created_at).build());
+        }
+
+        // Assert that the empty month 1 is left out and the last nanosecond of month 2 stays in it
+        assert_eq!(contract.events_per_month(), vec![(0, 1), (2, 2), (3, 1)]);
+    }
+
+    // Unit test for a clear winner, which needs no tiebreak
+    #[test]
+    fn top_tie_clear_winner() {
+        let contract = Scenario::new()
+            .events(3)
+            .vote(BOB, 0)
+            .vote(CAROL, 0)
+            .vote(BOB, 1)
+            .contract();
+        assert!(contract.top_tie().is_empty());
+    }
+
+    // Unit test for two events tied for first, leaving out the one behind them
+    #[test]
+    fn top_tie_two_events() {
+        let contract = Scenario::new()
+            .events(3)
+            .vote(BOB, 0)
+            .vote(BOB, 2)
+            .vote(CAROL, 2)
+            .vote(CAROL, 0)
+            .contract();
+        let ids: Vec<i32> = contract.top_tie().iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![0, 2]);
+    }
+
+    // Unit test ranking related events by shared voters, breaking ties by ID
+    #[test]
+    fn related_events() {
+        let mut contract = Scenario::new()
+            .events(5)
+            .vote(ALICE, 0)
+            .vote(ALICE, 1)
+            .vote(ALICE, 2)
+            .vote(BOB, 0)
+            .vote(BOB, 1)
+            .vote(BOB, 3)
+            .vote(CAROL, 0)
+            .vote(CAROL, 3)
+            .vote(CAROL, 4)
+            .contract();
+        assert_eq!(contract.related_events(0, 3), vec![(1, 2), (3, 2), (2, 1)]);
+        assert_eq!(contract.related_events(4, 10), vec![(0, 1), (3, 1)]);
+
+        // Assert that a removed vote no longer links the events
+        ctx(BOB, 0, 0);
+        contract.remove_vote(1);
+        assert_eq!(contract.related_events(0, 10), vec![(3, 2), (1, 1), (2, 1), (4, 1)]);
+    }
+
+    // Unit test leaving closed, deleted and funded events out of the related events
+    #[test]
+    fn related_events_skips_inactive() {
+        let mut contract = Scenario::new()
+            .events(5)
+            .vote(BOB, 0)
+            .vote(BOB, 1)
+            .vote(BOB, 2)
+            .vote(BOB, 3)
+            .vote(BOB, 4)
+            .close(2)
+            .contract();
+        contract.delete_event(3);
+        contract.mark_funded(4);
+        assert_eq!(contract.related_events(0, 10), vec![(1, 1)]);
+    }
+
+    // Unit test for the share of events that received a vote
+    #[test]
+    fn voted_event_ratio() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract without events
+        let mut contract = Contract::new(alice);
+        assert_eq!(contract.voted_event_ratio(), 0);
+
+        // Add four events, two of them with votes
+        contract.events.push(EventBuilder::new(0).voters(&["a.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).build());
+        contract
+            .events
+            .push(EventBuilder::new(2).voters(&["a.testnet", "b.testnet"]).build());
+        contract.events.push(EventBuilder::new(3).build());
+
+        // Assert that half of the events, 5000 basis points, have votes
+        assert_eq!(contract.voted_event_ratio(), 5_000);
+    }
+
+    // Unit test to cap the inline voter list of a popular event
+    #[test]
+    fn get_event_truncates_voters() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with an event voted on by 60 accounts
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        for i in 0..60 {
+            let voter: AccountId = format!("voter{}.testnet", i).parse().unwrap();
+            testing_env!(get_context(voter).build());
+            contract.add_vote(0, None);
+        }
+
+        // Assert that only the 50 most recent voters are inline, with the flag and total count
+        let view = contract.get_event(0);
+        assert!(view.voters_truncated);
+        assert_eq!(view.voter_count, 60);
+        assert_eq!(view.total_votes, 60);
+        assert_eq!(view.votes.len(), 50);
+        assert_eq!(view.votes[0], "voter10.testnet");
+        assert_eq!(view.votes[49], "voter59.testnet");
+
+        // Assert that storage keeps everything and get_voters returns the older ones
+        assert_eq!(contract.list_events()[0].votes.len(), 60);
+        let oldest = contract.get_voters(0, 0, 10);
+        assert_eq!(oldest.len(), 10);
+        assert_eq!(oldest[0], "voter0.testnet");
+        assert_eq!(oldest[9], "voter9.testnet");
+    }
+
+    // Unit test to verify inclusion proofs built off-chain against the stored vote Merkle root
+    #[test]
+    fn vote_merkle_proofs() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event that has no root before its first vote
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        assert_eq!(contract.get_vote_merkle_root(0), None);
+
+        // Bob, Carol and Dave vote at different times
+        for (voter, at) in [("bob.testnet", 10), ("carol.testnet", 20), ("dave.testnet", 30)] {
+            let mut context = get_context(voter.parse().unwrap());
+            context.block_timestamp(at);
+            testing_env!(context.build());
+            contract.add_vote(0, None);
+        }
+
+        // Recompute every leaf from its receipt, as an off-chain verifier would
+        let hash = |bytes: Vec<u8>| -> [u8; 32] { env::sha256(&bytes).try_into().unwrap() };
+        let leaves: Vec<[u8; 32]> = (0..3)
+            .map(|index| {
+                let leaf = contract.get_vote_leaf(0, index).unwrap();
+                let preimage =
+                    [leaf.voter.as_bytes(), &0u64.to_le_bytes(), &leaf.cast_at.to_le_bytes()];
+                assert_eq!(leaf.hash.0, hash(preimage.concat()).to_vec());
+                hash(preimage.concat())
+            })
+            .collect();
+        assert_eq!(contract.get_vote_leaf(0, 1).unwrap().voter.as_str(), "carol.testnet");
+        assert_eq!(contract.get_vote_leaf(0, 3), None);
+
+        // Build the proof of each leaf from the full tree, padding with empty subtrees
+        let root = contract.get_vote_merkle_root(0).unwrap().0;
+        for (index, leaf) in leaves.iter().enumerate() {
+            let mut layer = leaves.clone();
+            let mut zero = [0u8; 32];
+            let mut proof = Vec::new();
+            for level in 0..VOTE_MERKLE_DEPTH {
+                proof.push(*layer.get((index >> level) ^ 1).unwrap_or(&zero));
+                if layer.len() % 2 == 1 {
+                    layer.push(zero);
+                }
+                layer = layer
+                    .chunks(2)
+                    .map(|pair| hash([pair[0], pair[1]].concat()))
+                    .collect();
+                zero = hash([zero, zero].concat());
+            }
+
+            // Assert that folding the leaf with its proof gives the stored root
+            let mut node = *leaf;
+            for (level, sibling) in proof.iter().enumerate() {
+                node = if (index >> level) & 1 == 0 {
+                    hash([node, *sibling].concat())
+                } else {
+                    hash([*sibling, node].concat())
+                };
+            }
+            assert_eq!(node.to_vec(), root);
+        }
+
+        // Assert that removing a vote keeps its receipt, so the published root stays valid
+        testing_env!(get_context("carol.testnet".parse().unwrap()).build());
+        contract.remove_vote(0);
+        assert_eq!(contract.get_vote_merkle_root(0).unwrap().0, root);
+    }
+
+    // Unit test to return every voter inline for a small event
+    #[test]
+    fn get_event_small_not_truncated() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with an event voted on by two accounts
+        let mut contract = Contract::new(alice);
+        contract
+            .events
+            .push(EventBuilder::new(0).voters(&["bob.testnet", "carol.testnet"]).build());
+
+        // Assert that nothing was left out
+        let view = contract.get_event(0);
+        assert!(!view.voters_truncated);
+        assert_eq!(view.voter_count, 2);
+        assert_eq!(view.votes, vec!["bob.testnet".to_string(), "carol.testnet".to_string()]);
+    }
+
+    // Unit test for the existence check of an existing event
+    #[test]
+    fn event_exists() {
+        let contract = contract_with_events(2);
+        assert!(contract.event_exists(0));
+        assert!(contract.event_exists(1));
+    }
+
+    // Unit test for the existence check of missing and deleted events
+    #[test]
+    fn event_exists_missing() {
+        let mut contract = contract_with_events(2);
+        contract.delete_event(1);
+        assert!(!contract.event_exists(1));
+        assert!(!contract.event_exists(2));
+        assert!(!contract.event_exists(usize::MAX));
+    }
+
+    // Unit test for the state of active, deleted, purged and never created IDs
+    #[test]
+    fn get_event_state() {
+        // Alice deletes events 1 and 2, and purges event 2 once the restore window has passed
+        let window = DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000;
+        let mut contract = contract_with_events(3);
+        contract.delete_event(1);
+        contract.delete_event(2);
+        ctx(ALICE, 0, window / 2);
+        contract.restore_event(1);
+        contract.delete_event(1);
+        ctx(ALICE, 0, window);
+        assert_eq!(contract.purge_deleted(10), 1);
+
+        // Assert that each ID reports where it stands, and that a new event gets a fresh ID
+        assert_eq!(contract.get_event_state(0), EventLocation::Active);
+        assert_eq!(contract.get_event_state(1), EventLocation::Deleted);
+        assert_eq!(contract.get_event_state(2), EventLocation::Purged);
+        assert_eq!(contract.get_event_state(3), EventLocation::Unknown);
+        let description = "Scenario event".to_string();
+        contract.add_event("Music".to_string(), 100, description, None, None, None, None);
+        assert_eq!(contract.get_event_state(3), EventLocation::Active);
+        assert_eq!(contract.get_event_state(2), EventLocation::Purged);
+    }
+
+    // Unit test for the reason a vote on a deleted, purged or never created event is rejected
+    #[test]
+    fn vote_on_removed_event_errors() {
+        let window = DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000;
+        let mut contract = contract_with_events(2);
+        contract.delete_event(0);
+        contract.delete_event(1);
+        ctx(ALICE, 0, window / 2);
+        contract.restore_event(1);
+        contract.delete_event(1);
+        ctx(ALICE, 0, window);
+        contract.purge_deleted(10);
+
+        // Assert that each missing event gives its own message
+        ctx(BOB, 0, window);
+        assert_eq!(contract.try_add_vote(1), Err("Event has been deleted".to_string()));
+        assert_eq!(contract.try_add_vote(0), Err("Event has been deleted and purged".to_string()));
+        assert_eq!(contract.try_add_vote(2), Err("Event not found".to_string()));
+    }
+
+    // Unit test for a successful non-panicking vote
+    #[test]
+    fn try_add_vote_ok() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event and vote for it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Assert that the vote was accepted and counted
+        assert_eq!(contract.try_add_vote(0), Ok(()));
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test for a non-panicking vote on an invalid ID
+    #[test]
+    fn try_add_vote_invalid_id() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract without events
+        let mut contract = Contract::new(alice);
+
+        // Assert that the vote is rejected with a reason
+        assert_eq!(contract.try_add_vote(3), Err("Event not found".to_string()));
+    }
+
+    // Unit test for a non-panicking double vote
+    #[test]
+    fn try_add_vote_twice() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event and vote for it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+
+        // Assert that the second vote is rejected with a reason and not counted
+        assert_eq!(
+            contract.try_add_vote(0),
+            Err("You have already voted for this event".to_string())
+        );
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test for a sub-account voting on behalf of its parent
+    #[test]
+    fn add_vote_as_sub_account() {
+        // Set the account ID for testing as a sub-account of the DAO
+        let member: AccountId = "alice.dao.near".parse().unwrap();
+        let dao: AccountId = "dao.near".parse().unwrap();
+
+        // Set up the testing context and unit test environment
+        let context = get_context(member);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a fixture event and vote as the DAO
+        let mut contract = Contract::new("owner.near".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote_as(0, dao);
+
+        // Assert that the vote is recorded under the parent and both accounts are logged
+        assert_eq!(contract.list_events()[0].votes, vec!["dao.near".to_string()]);
+        assert!(get_logs()[0].contains(r#""voter":"dao.near","cast_by":"alice.dao.near""#));
+    }
+
+    // Unit test to apply the duplicate check to the attributed account
+    #[test]
+    #[should_panic(expected = "You have already voted for this event")]
+    fn add_vote_as_duplicate_parent() {
+        // Set up the testing context for a first member of the DAO
+        let dao: AccountId = "dao.near".parse().unwrap();
+        testing_env!(get_context("alice.dao.near".parse().unwrap()).build());
+
+        // Create a new instance of the contract with a fixture event and vote as the DAO
+        let mut contract = Contract::new("owner.near".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote_as(0, dao.clone());
+
+        // A second member voting for the same DAO is a duplicate
+        testing_env!(get_context("bob.dao.near".parse().unwrap()).build());
+        contract.add_vote_as(0, dao);
+    }
+
+    // Unit test to reject an unrelated account claiming a parent
+    #[test]
+    #[should_panic(expected = "Caller is not a direct sub-account")]
+    fn add_vote_as_unrelated_account() {
+        // Set up the testing context for an account outside the DAO
+        testing_env!(get_context("mallory.near".parse().unwrap()).build());
+
+        // Create a new instance of the contract with a fixture event and vote as the DAO
+        let mut contract = Contract::new("owner.near".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote_as(0, "dao.near".parse().unwrap());
+    }
+
+    // Unit test to require a dot boundary between the sub-account label and its parent
+    #[test]
+    #[should_panic(expected = "Caller is not a direct sub-account")]
+    fn add_vote_as_dot_boundary() {
+        // Set up the testing context for an account that merely ends with the parent's name
+        testing_env!(get_context("evilalice.near".parse().unwrap()).build());
+
+        // Create a new instance of the contract with a fixture event and vote as alice.near
+        let mut contract = Contract::new("owner.near".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote_as(0, "alice.near".parse().unwrap());
+    }
+
+    // Unit test for the sub-account check itself
+    #[test]
+    fn direct_sub_account_check() {
+        let parent: AccountId = "alice.near".parse().unwrap();
+        let check = |child: &str| is_direct_sub_account(&child.parse().unwrap(), &parent);
+
+        // Only accounts exactly one label below the parent qualify
+        assert!(check("bob.alice.near"));
+        assert!(!check("evilalice.near"));
+        assert!(!check("alice.near"));
+        assert!(!check("x.bob.alice.near"));
+    }
+
+    // Unit test for the creator setting a voting deadline
+    #[test]
+    fn set_voting_deadline() {
+        // Set the account ID for testing as Alice at a fixed time
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with Alice's event and set its deadline
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.set_voting_deadline(0, 5_000);
+
+        // Assert that the deadline is exposed in the event view
+        assert_eq!(contract.get_event(0).voting_deadline, Some(5_000));
+    }
+
+    // Unit test to reject a deadline in the past
+    #[test]
+    #[should_panic(expected = "Voting deadline must be in the future")]
+    fn set_voting_deadline_in_past() {
+        // Set the account ID for testing as Alice at a fixed time
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with Alice's event and set a past deadline
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.set_voting_deadline(0, 500);
+    }
+
+    // Unit test to reject a voting deadline further ahead than the configured maximum
+    #[test]
+    #[should_panic(expected = "Voting deadline must be at most 3600 seconds away")]
+    fn set_voting_deadline_too_far() {
+        let mut contract = contract_with_events(1);
+        contract.set_max_voting_period(3_600);
+        contract.set_voting_deadline(0, 3_601 * 1_000_000_000);
+    }
+
+    // Unit test to reject a creator moving a deadline already set, which only extend_deadline may do
+    #[test]
+    #[should_panic(expected = "Voting deadline is already set; use extend_deadline to change it")]
+    fn set_voting_deadline_twice() {
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        ctx(BOB, 0, 0);
+        contract.set_voting_deadline(0, day);
+        contract.set_voting_deadline(0, 30 * day);
+    }
+
+    // Unit test to reject reopening an event whose voting has closed, even for the owner
+    #[test]
+    #[should_panic(expected = "Voting has closed for this event")]
+    fn set_voting_deadline_after_close() {
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).close(0).contract();
+        contract.set_voting_deadline(0, 100);
+    }
+
+    // Unit test for creator defaults filling omitted add_event arguments, with explicit arguments winning
+    #[test]
+    fn creator_defaults() {
+        // Bob saves defaults, paying for their storage
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        let defaults = CreatorDefaults {
+            voting_period_secs: Some(3_600),
+            template_id: None,
+            min_votes_for_funding: Some(3),
+        };
+        ctx(BOB, 10u128.pow(23), 1_000);
+        contract.set_creator_defaults(defaults.clone());
+        assert_eq!(contract.get_creator_defaults(account(BOB)), Some(defaults));
+        assert_eq!(contract.get_creator_defaults(account(CAROL)), None);
+
+        // Assert that the storage cost is held for Bob and the rest of the deposit is on its way back
+        let storage_cost = env::storage_byte_cost().as_yoctonear() * CREATOR_DEFAULTS_BYTES as u128;
+        let held = contract.get_ledger_balance(LedgerAccount::StorageDeposit(account(BOB)));
+        assert_eq!(held, I128(storage_cost as i128));
+        let refund = &contract.list_pending_transfers()[0];
+        assert_eq!(refund.purpose, "storage_overpayment");
+        assert_eq!(refund.to, account(BOB));
+        assert_eq!(refund.amount, U128(10u128.pow(23) - storage_cost));
+        assert_eq!(refund.event_id, NO_EVENT);
+
+        // Assert that an event given no threshold takes the defaults
+        let description = "Scenario event".to_string();
+        ctx(BOB, 0, 1_000);
+        contract.add_event("Music".to_string(), 100, description.clone(), None, None, None, None);
+        let event = &contract.events[1];
+        assert_eq!(event.min_votes_for_funding, Some(3));
+        assert_eq!(event.voting_deadline, Some(1_000 + 3_600 * 1_000_000_000));
+
+        // Assert that an explicit threshold wins over the default
+        contract.add_event("Dance".to_string(), 100, description, None, Some(1), None, None);
+        assert_eq!(contract.events[2].min_votes_for_funding, Some(1));
+    }
+
+    // Unit test to reject saving creator defaults without paying for their storage
+    #[test]
+    #[should_panic(expected = "to cover storage")]
+    fn creator_defaults_no_deposit() {
+        let mut contract = contract_with_events(0);
+        contract.set_creator_defaults(CreatorDefaults::default());
+    }
+
+    // Unit test to remove creator defaults, refunding the storage deposit held for them
+    #[test]
+    fn remove_creator_defaults() {
+        // Bob saves defaults attaching exactly their storage cost, so nothing is refunded yet
+        let mut contract = contract_with_events(0);
+        let storage_cost = env::storage_byte_cost().as_yoctonear() * CREATOR_DEFAULTS_BYTES as u128;
+        ctx(BOB, storage_cost, 0);
+        contract.set_creator_defaults(CreatorDefaults::default());
+        assert!(contract.list_pending_transfers().is_empty());
+
+        // Bob removes them; there is nothing left to remove the second time
+        ctx(BOB, 0, 0);
+        assert!(contract.remove_creator_defaults());
+        assert!(!contract.remove_creator_defaults());
+
+        // Assert that the defaults are gone and the whole deposit is on its way back
+        assert_eq!(contract.get_creator_defaults(account(BOB)), None);
+        let held = contract.get_ledger_balance(LedgerAccount::StorageDeposit(account(BOB)));
+        assert_eq!(held, I128(0));
+        let refund = &contract.list_pending_transfers()[0];
+        assert_eq!(refund.purpose, "storage_refund");
+        assert_eq!(refund.to, account(BOB));
+        assert_eq!(refund.amount, U128(storage_cost));
+    }
+
+    // Unit test to reject a saved default that the owner's settings no longer allow
+    #[test]
+    #[should_panic(expected = "Default voting period of 604800 seconds exceeds the maximum of 86400")]
+    fn creator_defaults_revalidated() {
+        // Alice saves a week-long voting period, which the limit allows at that time
+        let mut contract = contract_with_events(0);
+        let defaults = CreatorDefaults {
+            voting_period_secs: Some(7 * SECONDS_PER_DAY),
+            ..CreatorDefaults::default()
+        };
+        ctx(ALICE, 10u128.pow(23), 0);
+        contract.set_creator_defaults(defaults);
+
+        // The limit tightens to a day before Alice's next event
+        ctx(ALICE, 0, 0);
+        contract.set_max_voting_period(SECONDS_PER_DAY);
+        let description = "Scenario event".to_string();
+        contract.add_event("Music".to_string(), 100, description, None, None, None, None);
+    }
+
+    // Unit test for a creator's single deadline extension, logged, followed by the owner's
+    #[test]
+    fn extend_deadline() {
+        // Bob creates an event and sets its deadline a day ahead
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        ctx(BOB, 0, 0);
+        contract.set_voting_deadline(0, day);
+
+        // Assert that Bob may push it back by the full two weeks, and that the extension is logged
+        contract.extend_deadline(0, 15 * day);
+        assert_eq!(contract.events[0].voting_deadline, Some(15 * day));
+        assert!(contract.events[0].deadline_extended);
+        assert!(get_logs().iter().any(|log| log.contains("\"event\":\"deadline_extended\"")));
+
+        // Assert that the owner may extend it again after Bob used his extension
+        ctx(ALICE, 0, 0);
+        contract.extend_deadline(0, 20 * day);
+        assert_eq!(contract.events[0].voting_deadline, Some(20 * day));
+    }
+
+    // Unit test to reject a creator's second deadline extension
+    #[test]
+    #[should_panic(expected = "Deadline has already been extended once")]
+    fn extend_deadline_twice() {
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        ctx(BOB, 0, 0);
+        contract.set_voting_deadline(0, day);
+        contract.extend_deadline(0, 2 * day);
+        contract.extend_deadline(0, 3 * day);
+    }
+
+    // Unit test to reject an extension beyond the configured maximum
+    #[test]
+    #[should_panic(expected = "Deadline cannot be extended by more than 1209600 seconds")]
+    fn extend_deadline_over_cap() {
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        ctx(BOB, 0, 0);
+        contract.set_voting_deadline(0, day);
+        contract.extend_deadline(0, 15 * day + 1);
+    }
+
+    // Unit test to reject extending the deadline of an event whose voting has closed
+    #[test]
+    #[should_panic(expected = "Voting has closed for this event")]
+    fn extend_deadline_after_close() {
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).close(0).contract();
+        ctx(BOB, 0, 10);
+        contract.extend_deadline(0, 100);
+    }
+
+    // Unit test to reject votes after the deadline
+    #[test]
+    #[should_panic(expected = "Voting has closed for this event")]
+    fn add_vote_after_deadline() {
+        // Set the account ID for testing as Alice after the deadline
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(5_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with an expired event and vote for it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).deadline(5_000).build());
+        contract.add_vote(0, None);
+    }
+
+    // Unit test for events closing within a time window
+    #[test]
+    fn events_closing_within() {
+        const SECOND: u64 = 1_000_000_000;
+
+        // Set the account ID for testing as Alice at a fixed time
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let now = 1_000 * SECOND;
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(now);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with events at varying deadlines
+        let mut contract = Contract::new(alice);
+        contract.events = vec![
+            EventBuilder::new(0).deadline(now - SECOND).build(),
+            EventBuilder::new(1).deadline(now + 30 * SECOND).build(),
+            EventBuilder::new(2).deadline(now + 60 * SECOND).build(),
+            EventBuilder::new(3).deadline(now + 61 * SECOND).build(),
+            EventBuilder::new(4).build(),
+        ];
+
+        // Assert that only the open events closing within a minute are returned
+        let ids: Vec<i32> = contract
+            .events_closing_within(60)
+            .iter()
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    // Unit test for deposit-free voting. Its gas is checked against ADD_VOTE_GAS_BUDGET by the sandbox
+    // test in tests/integration.rs, since testing_env! does not charge for wasm execution.
+    #[test]
+    fn add_vote_without_deposit() {
+        // Set the account ID for testing as Bob without any deposit
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract with a fixture event
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Assert that wallets are told no deposit is needed
+        let requirements = contract.get_vote_requirements();
+        assert!(!requirements.deposit_required);
+        assert_eq!(requirements.min_deposit, NearToken::from_yoctonear(0));
+
+        // Vote and assert that it counted
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test to reject a vote without deposit when one is required
+    #[test]
+    #[should_panic(expected = "Voting requires an attached deposit")]
+    fn add_vote_deposit_required() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract requiring deposits, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_require_vote_deposit(true);
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Assert that wallets are told a deposit is needed
+        let requirements = contract.get_vote_requirements();
+        assert!(requirements.deposit_required);
+        assert_eq!(requirements.min_deposit, NearToken::from_yoctonear(1));
+
+        // Vote without attaching anything
+        contract.add_vote(0, None);
+    }
+
+    // Unit test to accept a vote with a deposit when one is required
+    #[test]
+    fn add_vote_with_required_deposit() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract requiring deposits, with a fixture event
+        let mut contract = Contract::new(alice.clone());
+        contract.set_require_vote_deposit(true);
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Vote with one yoctoNEAR attached
+        let mut context = get_context(alice);
+        context.attached_deposit(NearToken::from_yoctonear(1));
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+
+        // Assert that the vote counted
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test for an authorized creator adding an event
+    #[test]
+    fn add_event_authorized_creator() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and whitelist Bob
+        let mut contract = Contract::new(alice);
+        contract.add_creator(bob.clone());
+        assert_eq!(contract.list_creators(), vec![bob.clone()]);
+
+        // Add and publish an event as Bob
+        testing_env!(get_context(bob).build());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "By Bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.publish_event(0);
+
+        // Assert that the event was added
+        assert_eq!(contract.event_count(), 1);
+    }
+
+    // Unit test to reject an event from an account outside the whitelist
+    #[test]
+    #[should_panic(expected = "Not an authorized creator")]
+    fn add_event_unauthorized_creator() {
+        // Set the account ID for testing as Bob, who is not whitelisted
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract owned by Alice and add an event as Bob
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "By Bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to revoke creation rights
+    #[test]
+    #[should_panic(expected = "Not an authorized creator")]
+    fn add_event_removed_creator() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, whitelist Bob and remove him again
+        let mut contract = Contract::new(alice);
+        contract.add_creator(bob.clone());
+        contract.remove_creator(bob.clone());
+
+        // Add an event as Bob
+        testing_env!(get_context(bob).build());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "By Bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to attribute creation and votes to the predecessor when a relayer signs
+    #[test]
+    fn relayed_calls_use_predecessor() {
+        // Set up a context where the relayer signs but Bob is the predecessor
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let relayer: AccountId = "relayer.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and whitelist Bob
+        let mut contract = Contract::new(alice);
+        contract.add_creator(bob.clone());
+
+        // Create an event and vote for it through the relayer
+        let mut context = get_context(bob.clone());
+        context.signer_account_id(relayer.clone());
+        testing_env!(context.build());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Relayed".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        contract.add_vote(0, None);
+
+        // Assert that Bob, not the relayer, is the creator and the voter
+        let event = &contract.list_events()[0];
+        assert_eq!(event.creator, bob);
+        assert_eq!(event.votes, vec!["bob.testnet".to_string()]);
+
+        // A second relayed vote by Bob is still a duplicate, whoever signs it
+        context.signer_account_id("other-relayer.testnet".parse().unwrap());
+        testing_env!(context.build());
+        assert_eq!(
+            contract.try_add_vote(0),
+            Err("You have already voted for this event".to_string())
+        );
+
+        // Bob can remove his own vote through the relayer too
+        contract.remove_vote(0);
+        assert_eq!(contract.get_total_votes(0), 0);
+        assert!(contract.attribution_policy().contains("predecessor"));
+    }
+
+    // Unit test for the latest event
+    #[test]
+    fn latest_event() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract without events
+        let mut contract = Contract::new(alice.clone());
+        assert_eq!(contract.latest_event(), None);
+
+        // Add and publish several events at mixed times
+        for (i, time) in [100, 300, 200].iter().enumerate() {
+            let mut context = get_context(alice.clone());
+            context.block_timestamp(*time);
+            testing_env!(context.build());
+            contract.add_event(
+                format!("Event {}", i),
+                200,
+                "Description".to_string(),
+                None,
+                None,
+                None,
+                None,
+            );
+            contract.publish_event(i);
+        }
+
+        // Assert that the event created last in time is returned
+        let latest = contract.latest_event().unwrap();
+        assert_eq!(latest.created_at, 300);
+        assert_eq!(latest.title, "Event 1");
+    }
+
+    // Unit test that cross-checks the method registry against what the methods enforce
+    #[test]
+    fn method_requirements_match_enforcement() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract requiring vote deposits and a creation fee, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_config(Config {
+            require_vote_deposit: true,
+            creation_fee: 50,
+            ..Config::default()
+        });
+        contract.add_creator("bob.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Only payable methods may ask for a deposit
+        let requirements = contract.get_method_requirements();
+        assert_eq!(requirements.len(), Method::ALL.len());
+        for requirement in requirements.iter() {
+            if !requirement.payable {
+                assert_eq!(requirement.min_deposit, U128(0), "{}", requirement.name);
+            }
+        }
+
+        // Run a call that fails by panicking, returning its panic message as the error
+        let caught = |call: &mut dyn FnMut()| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(call)).map_err(|e| {
+                match e.downcast_ref::<&str>() {
+                    Some(message) => message.to_string(),
+                    None => e.downcast_ref::<String>().cloned().unwrap_or_default(),
+                }
+            })
+        };
+
+        // Every payable method must accept exactly the advertised deposit and reject less
+        for requirement in requirements.iter().filter(|r| r.payable) {
+            let min_deposit = requirement.min_deposit.0;
+            assert!(min_deposit > 0, "{}", requirement.name);
+            let call = |contract: &mut Contract, voter: &str, deposit: u128| {
+                let mut context = get_context(voter.parse().unwrap());
+                context.attached_deposit(NearToken::from_yoctonear(deposit));
+                testing_env!(context.build());
+                let method = Method::ALL
+                    .iter()
+                    .find(|m| m.name() == requirement.name)
+                    .unwrap();
+                match method {
+                    Method::AddEvent => caught(&mut || {
+                        let (title, description) = ("Art".to_string(), "Show".to_string());
+                        contract.add_event(title, 100, description, None, None, None, None)
+                    }),
+                    Method::AddVote | Method::TryAddVote => contract
+                        .internal_add_vote(0, voter.parse().unwrap(), *method, None)
+                        .map_err(|e| e.message()),
+                    Method::AddVoteAs => contract
+                        .internal_add_vote(0, "dao.testnet".parse().unwrap(), *method, None)
+                        .map_err(|e| e.message()),
+                    Method::Donate => caught(&mut || contract.donate(0)),
+                    Method::CreateMatch => caught(&mut || contract.create_match(0, U128(deposit))),
+                    Method::SetCreatorDefaults => {
+                        caught(&mut || contract.set_creator_defaults(CreatorDefaults::default()))
+                    }
+                    _ => panic!("{} is payable but not exercised here", requirement.name),
+                }
+            };
+            let storage_error = format!("Attach at least {} yoctoNEAR to cover storage", min_deposit);
+            let fee_error = format!(
+                "Attach exactly the creation fee of {} yoctoNEAR",
+                min_deposit
+            );
+            let error = match requirement.name.as_str() {
+                "add_event" => fee_error.as_str(),
+                "donate" => "Donation must be positive",
+                "create_match" => "Match cap must be positive",
+                "set_creator_defaults" => storage_error.as_str(),
+                _ => "Voting requires an attached deposit",
+            };
+            assert_eq!(
+                call(&mut contract, "bob.testnet", min_deposit - 1),
+                Err(error.to_string()),
+                "{}",
+                requirement.name
+            );
+            assert_eq!(call(&mut contract, "bob.testnet", min_deposit), Ok(()));
+            contract.events[0].votes.clear();
+            contract.events[0].total_votes = 0;
+            contract.events[0].weighted_total = 0;
+        }
+    }
+
+    // Unit test for the gas hints reported to wallets
+    #[test]
+    fn method_requirements_gas_hints() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with default settings
+        let contract = Contract::new(alice);
+
+        // Assert that add_vote reports its gas budget and no deposit by default
+        let add_vote = contract
+            .get_method_requirements()
+            .into_iter()
+            .find(|r| r.name == "add_vote")
+            .unwrap();
+        assert!(add_vote.payable);
+        assert_eq!(add_vote.min_deposit, U128(0));
+        assert_eq!(add_vote.suggested_gas, U64(ADD_VOTE_GAS_BUDGET.as_gas()));
+    }
+
+    // Unit test for budget revisions accumulating on edits
+    #[test]
+    fn budget_history_accumulates() {
+        // Set the account ID for testing as Alice at a fixed time
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(10);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract and add an event
+        let mut contract = Contract::new(alice.clone());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Edit the budget twice and the title once at later times
+        context.block_timestamp(20);
+        testing_env!(context.build());
+        contract.edit_event(0, None, Some(U128(300)), None);
+        context.block_timestamp(30);
+        testing_env!(context.build());
+        contract.edit_event(0, Some("Art Fair".to_string()), None, None);
+        context.block_timestamp(40);
+        testing_env!(context.build());
+        contract.edit_event(0, None, Some(U128(250)), Some("New description".to_string()));
+
+        // Assert that only budget changes were recorded, in order
+        assert_eq!(
+            contract.get_budget_history(0),
+            vec![(U64(10), U128(200)), (U64(20), U128(300)), (U64(40), U128(250))]
+        );
+        let event = contract.get_my_event(0);
+        assert_eq!(event.title, "Art Fair");
+        assert_eq!(event.estimated_budget, U128(250));
+        assert_eq!(event.description, "New description");
+    }
+
+    // Unit test to keep other fields editable while the budget is locked
+    #[test]
+    fn lock_budget_allows_title_edit() {
+        // Bob adds an event and locks its budget
+        let mut contract = Scenario::new().create(BOB, "Book Fair", 100, None).contract();
+        ctx(BOB, 0, 0);
+        contract.lock_budget(0);
+
+        // Assert that Bob can still change the title and description
+        contract.edit_event(0, Some("Book Fair 2026".to_string()), None, Some("Bigger".to_string()));
+        let event = contract.get_event(0);
+        assert_eq!(event.title, "Book Fair 2026");
+        assert_eq!(event.description, "Bigger");
+        assert_eq!(event.estimated_budget, U128(100));
+    }
+
+    // Unit test to reject a budget change once the owner locked the budget
+    #[test]
+    #[should_panic(expected = "Budget is locked for this event")]
+    fn lock_budget_rejects_budget_edit() {
+        // Bob adds an event and Alice, the owner, locks its budget
+        let mut contract = Scenario::new().create(BOB, "Book Fair", 100, None).contract();
+        contract.lock_budget(0);
+
+        // Bob tries to raise the budget
+        ctx(BOB, 0, 0);
+        contract.edit_event(0, None, Some(U128(200)), None);
+    }
+
+    // Unit test to let only the creator or the owner lock a budget
+    #[test]
+    #[should_panic(expected = "Only the event creator or the owner can lock the budget")]
+    fn lock_budget_not_creator() {
+        // Bob adds an event and Carol tries to lock its budget
+        let mut contract = Scenario::new().create(BOB, "Book Fair", 100, None).contract();
+        ctx(CAROL, 0, 0);
+        contract.lock_budget(0);
+    }
+
+    // Unit test for the budget history cap
+    #[test]
+    fn budget_history_capped() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, add an event and revise its budget 60 times
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            1,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        for budget in 2..=61 {
+            contract.edit_event(0, None, Some(U128(budget)), None);
+        }
+
+        // Assert that only the 50 most recent revisions are kept
+        let history = contract.get_budget_history(0);
+        assert_eq!(history.len(), MAX_BUDGET_HISTORY);
+        assert_eq!(history[0].1, U128(12));
+        assert_eq!(history[49].1, U128(61));
+    }
+
+    // Unit test to reject edits by someone other than the creator
+    #[test]
+    #[should_panic(expected = "Only the event creator can edit the event")]
+    fn edit_event_not_creator() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract with Alice's event and edit it as Bob
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.edit_event(0, Some("Hijacked".to_string()), None, None);
+    }
+
+    // Unit test to validate edited budgets against the configured bounds
+    #[test]
+    #[should_panic(expected = "Estimated budget must be between")]
+    fn edit_event_zero_budget() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, add an event and zero its budget
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.edit_event(0, None, Some(U128(0)), None);
+    }
+
+    // Unit test to check that dry_run_vote reports the same violation try_add_vote fails with
+    #[test]
+    fn dry_run_vote_matches_try_add_vote() {
+        // Set the account ID for testing as Bob at a time after the deadline
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.block_timestamp(100);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with an open and a closed event Bob voted for
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet"]).deadline(50).build());
+
+        // Assert that every violation is listed, in the order the real method checks them
+        assert_eq!(
+            contract.dry_run_vote(1, bob.clone()).violations,
+            vec![VoteError::VotingClosed, VoteError::AlreadyVoted]
+        );
+        assert_eq!(
+            contract.dry_run_vote(7, bob.clone()).violations,
+            vec![VoteError::EventNotFound]
+        );
+        assert!(contract.dry_run_vote(0, "carol.testnet".parse().unwrap()).allowed);
+
+        // Assert that the real method fails with the first reported violation and nothing changed
+        for id in [0, 1, 7] {
+            let check = contract.dry_run_vote(id, bob.clone());
+            assert!(!check.allowed);
+            assert_eq!(
+                contract.try_add_vote(id),
+                Err(check.violations[0].message().to_string())
+            );
+        }
+        assert_eq!(contract.list_events()[0].total_votes, 1);
+    }
+
+    // Unit test for the actions the creator and a voter may take as an event goes from draft to deleted
+    #[test]
+    fn get_permissions_lifecycle() {
+        // Bob adds a draft, which Bob may edit and schedule but nobody may vote on yet
+        ctx(ALICE, 0, 0);
+        let mut contract = Contract::new(account(ALICE));
+        contract.add_creator(account(BOB));
+        ctx(BOB, 0, 0);
+        let (title, description) = ("Art Show".to_string(), "Paintings".to_string());
+        contract.add_event(title, 100, description, None, None, None, None);
+        let bob = contract.get_permissions(0, account(BOB));
+        assert!(bob.can_edit && bob.can_close && !bob.can_vote && !bob.can_comment);
+        let carol = contract.get_permissions(0, account(CAROL));
+        assert!(!carol.can_vote && !carol.can_edit && !carol.can_close && !carol.can_withdraw);
+        assert_eq!(
+            carol.reasons,
+            vec![
+                "vote: Event is still a draft",
+                "edit: Only the event creator can edit the event",
+                "close: Only the event creator or the owner can set the voting deadline",
+                "withdraw: You have not voted for this event",
+                "comment: Events do not take comments",
+            ]
+        );
+
+        // Once published Carol may vote, and after voting may withdraw the vote instead
+        contract.publish_event(0);
+        assert!(contract.get_permissions(0, account(CAROL)).can_vote);
+        ctx(CAROL, 0, 0);
+        contract.add_vote(0, None);
+        let carol = contract.get_permissions(0, account(CAROL));
+        assert!(!carol.can_vote && carol.can_withdraw);
+        assert!(carol.reasons.contains(&"vote: You have already voted for this event".to_string()));
+
+        // Closing voting keeps the creator's edit rights but freezes Carol's vote and the deadline
+        ctx(BOB, 0, 0);
+        contract.set_voting_deadline(0, 1);
+        ctx(ALICE, 0, 1);
+        let bob = contract.get_permissions(0, account(BOB));
+        assert!(bob.can_edit && !bob.can_close);
+        let carol = contract.get_permissions(0, account(CAROL));
+        assert!(!carol.can_vote && !carol.can_withdraw);
+        assert!(carol.reasons.contains(&"withdraw: Voting has closed for this event".to_string()));
+
+        // Once deleted nobody may act on the event
+        contract.delete_event(0);
+        let bob = contract.get_permissions(0, account(BOB));
+        assert!(!bob.can_vote && !bob.can_edit && !bob.can_close && !bob.can_withdraw);
+        assert!(bob.reasons.contains(&"edit: Event has been deleted".to_string()));
+    }
+
+    // Unit test to check that dry_run_add_event lists every violation without adding an event
+    #[test]
+    fn dry_run_add_event_lists_violations() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract
+        let contract = Contract::new(alice.clone());
+
+        // Assert that an unauthorized creator with a blank title and zero budget gets all three
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let check = contract.dry_run_add_event(bob, " \t ".to_string(), U128(0), None);
+        assert!(!check.allowed);
+        assert_eq!(
+            check.violations,
+            vec![
+                CreateError::NotAuthorized,
+                CreateError::TitleEmpty,
+                CreateError::BudgetOutOfRange {
+                    min: U128(DEFAULT_MIN_BUDGET),
+                    max: U128(DEFAULT_MAX_BUDGET),
+                },
+            ]
+        );
+
+        // Assert that the limits are measured after sanitization
+        let title = "🎉".repeat(MAX_TITLE_CHARS + 1);
+        let check = contract.dry_run_add_event(alice.clone(), title, U128(200), None);
+        assert_eq!(check.violations, vec![CreateError::TitleTooLong { max_chars: 100 }]);
+        assert_eq!(check.violations[0].message(), "Title cannot exceed 100 characters");
+        assert!(
+            contract
+                .dry_run_add_event(alice, "  Art   Show ".to_string(), U128(200), None)
+                .allowed
+        );
+        assert!(contract.list_events().is_empty());
+    }
+
+    // Unit test to check that add_event fails with the first violation dry_run_add_event reports
+    #[test]
+    #[should_panic(expected = "Estimated budget must be between 1 and")]
+    fn add_event_matches_dry_run() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and dry-run an event with a zero budget
+        let mut contract = Contract::new(alice.clone());
+        let check = contract.dry_run_add_event(alice, "Art Show".to_string(), U128(0), None);
+        assert_eq!(
+            check.violations[0].message(),
+            format!("Estimated budget must be between 1 and {}", DEFAULT_MAX_BUDGET)
+        );
+
+        // Adding it for real must fail with the same reason
+        contract.add_event(
+            "Art Show".to_string(),
+            0,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to count accounts voting on more than one event
+    #[test]
+    fn multi_event_voter_count() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with Bob voting on three events and Carol on two
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet", "carol.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet", "dave.testnet"]).build());
+        contract.events.push(EventBuilder::new(2).voters(&["bob.testnet", "carol.testnet"]).build());
+        contract.events.push(EventBuilder::new(3).voters(&["erin.testnet"]).build());
+
+        // Assert that only Bob and Carol are counted
+        assert_eq!(contract.multi_event_voter_count(), 2);
+    }
+
+    // Unit test to throttle a creator until the cooldown has elapsed
+    #[test]
+    fn creation_cooldown() {
+        // Set the account ID for testing as Alice at a fixed time
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(1_000_000_000_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a one minute cooldown and add an event
+        let mut contract = Contract::new(alice.clone());
+        contract.set_creation_cooldown(60);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that a second event 59 seconds later is throttled
+        context.block_timestamp(1_000_000_000_000 + 59_000_000_000);
+        testing_env!(context.build());
+        let check = contract.dry_run_add_event(
+            alice.clone(),
+            "Book Fair".to_string(),
+            U128(200),
+            None,
+        );
+        assert_eq!(check.violations, vec![CreateError::CooldownActive]);
+
+        // Assert that the second event is accepted once the minute has passed
+        context.block_timestamp(1_000_000_000_000 + 60_000_000_000);
+        testing_env!(context.build());
+        contract.add_event(
+            "Book Fair".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(contract.list_my_drafts().len(), 2);
+    }
+
+    // Unit test to reject an event added during the cooldown
+    #[test]
+    #[should_panic(expected = "Creation cooldown active")]
+    fn creation_cooldown_active() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a cooldown and add two events back to back
+        let mut contract = Contract::new(alice);
+        contract.set_creation_cooldown(60);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.add_event(
+            "Book Fair".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test for each loyalty tier after time passes between registration and voting
+    #[test]
+    fn loyalty_tiers() {
+        // Register three voters at the same time
+        let day: u64 = SECONDS_PER_DAY * 1_000_000_000;
+        let voters = ["bob.testnet", "carol.testnet", "dave.testnet"];
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        for voter in voters {
+            let mut context = get_context(voter.parse().unwrap());
+            context.block_timestamp(day);
+            testing_env!(context.build());
+            assert_eq!(contract.register_voter(), day);
+        }
+        contract.events.push(EventBuilder::new(0).build());
+        contract.events[0].loyalty_weighted = true;
+
+        // Each voter votes after a different amount of time has passed
+        for (voter, age_days) in voters.iter().zip([89, 90, 365]) {
+            let mut context = get_context(voter.parse().unwrap());
+            context.block_timestamp(day + age_days * day);
+            testing_env!(context.build());
+            contract.add_vote(0, None);
+        }
+
+        // Assert that the votes weigh 1, 2 and 3 while the raw count stays at 3
+        let event = contract.get_event(0);
+        assert_eq!(event.total_votes, 3);
+        assert_eq!(event.weighted_total, 1 + 2 + 3);
+        assert!(get_logs()[0].contains(r#""weight":3"#));
+        assert_eq!(contract.get_registered_at("bob.testnet".parse().unwrap()), Some(day));
+    }
+
+    // Unit test to ignore loyalty on unweighted events and register voters on first vote
+    #[test]
+    fn loyalty_unweighted_event() {
+        // Set the account ID for testing as Bob, registered long ago
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.register_voter();
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Bob votes two years later on an event that is not loyalty-weighted
+        context.block_timestamp(730 * SECONDS_PER_DAY * 1_000_000_000);
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_event(0).weighted_total, 1);
+
+        // Carol is registered by her first vote
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        testing_env!(get_context(carol.clone()).block_timestamp(42).build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_registered_at(carol), Some(42));
+    }
+
+    // Unit test to subtract the recorded weight when a boosted vote is removed
+    #[test]
+    fn remove_loyalty_weighted_vote() {
+        // Set the account ID for testing as Bob, registered a year before voting
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.register_voter();
+        contract.events.push(EventBuilder::new(0).voters(&["carol.testnet"]).build());
+        contract.events[0].loyalty_weighted = true;
+        context.block_timestamp(365 * SECONDS_PER_DAY * 1_000_000_000);
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_event(0).weighted_total, 4);
+
+        // The owner lowers the tiers, which must not change what removing the vote subtracts
+        testing_env!(get_context("alice.testnet".parse().unwrap()).build());
+        contract.set_loyalty_tiers(vec![]);
+        testing_env!(context.build());
+        contract.remove_vote(0);
+        assert_eq!(contract.get_event(0).weighted_total, 1);
+    }
+
+    // Scenario test to return the weighted total to zero once every weighted vote is removed
+    #[test]
+    fn remove_weighted_votes_to_zero() {
+        // Bob registers at time 0; a year later Alice's loyalty-weighted event gets his 3x vote and Carol's 1x
+        let year = 365 * SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = contract_with_events(1);
+        contract.set_loyalty_weighted(0, true);
+        ctx(BOB, 0, 0);
+        contract.register_voter();
+        for voter in [BOB, CAROL] {
+            ctx(voter, 0, year);
+            contract.add_vote(0, None);
+        }
+        assert_eq!(contract.get_event(0).weighted_total, 4);
+
+        // Assert that removing both votes subtracts exactly what each contributed
+        for voter in [BOB, CAROL] {
+            ctx(voter, 0, year);
+            contract.remove_vote(0);
+        }
+        let event = contract.get_event(0);
+        assert_eq!((event.total_votes, event.weighted_total), (0, 0));
+    }
+
+    // Unit test to reject loyalty tiers that are not sorted by age
+    #[test]
+    #[should_panic(expected = "Loyalty tiers must be sorted by ascending age")]
+    fn set_loyalty_tiers_unsorted() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and set tiers in the wrong order
+        let mut contract = Contract::new(alice);
+        contract.set_loyalty_tiers(vec![
+            LoyaltyTier { min_age_secs: 100, multiplier: 3 },
+            LoyaltyTier { min_age_secs: 10, multiplier: 2 },
+        ]);
+    }
+
+    // Unit test to store and return a valid proposal link
+    #[test]
+    fn proposal_url_valid() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add events with and without a link
+        let mut contract = Contract::new(alice);
+        let url = "https://gov.near.org/t/art-show/42".to_string();
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            Some(url.clone()),
+            None,
+            None,
+            None,
+        );
+        contract.add_event(
+            "Book Fair".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert that the link is returned by the getter and the creator's event view
+        assert_eq!(contract.get_proposal_url(0), Some(url.clone()));
+        assert_eq!(contract.get_my_event(0).proposal_url, Some(url));
+        assert_eq!(contract.get_proposal_url(1), None);
+
+        // A link of 511 characters is still accepted
+        let url = format!("https://{}", "a".repeat(MAX_PROPOSAL_URL_CHARS - 9));
+        contract.add_event(
+            "Long Link".to_string(),
+            200,
+            "Description".to_string(),
+            Some(url),
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to reject links that are not https
+    #[test]
+    #[should_panic(expected = "Proposal URL must start with https://")]
+    fn proposal_url_not_https() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add an event with a plain http link
+        let mut contract = Contract::new(alice);
+        let url = "http://gov.near.org/t/art-show/42".to_string();
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            Some(url),
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to reject links of 512 characters or more
+    #[test]
+    #[should_panic(expected = "Proposal URL must be shorter than 512 characters")]
+    fn proposal_url_too_long() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add an event with a 512 character link
+        let mut contract = Contract::new(alice);
+        let url = format!("https://{}", "a".repeat(MAX_PROPOSAL_URL_CHARS - 8));
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            Some(url),
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to reject links with embedded whitespace
+    #[test]
+    #[should_panic(expected = "Proposal URL cannot contain whitespace or control characters")]
+    fn proposal_url_whitespace() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add an event with a space in its link
+        let mut contract = Contract::new(alice);
+        let url = "https://gov.near.org/t/art show".to_string();
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            Some(url),
+            None,
+            None,
+            None,
+        );
+    }
+
+    // Unit test to aggregate survey answers across voters
+    #[test]
+    fn survey_answers_tallied() {
+        // Set the account ID for testing as Alice, the creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event asking two questions
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.set_event_questions(0, survey());
+
+        // Three voters answer, one of them skipping the survey
+        for (voter, answers) in [
+            ("bob.testnet", Some(vec![0, 2])),
+            ("carol.testnet", Some(vec![0, 1])),
+            ("dave.testnet", None),
+        ] {
+            testing_env!(get_context(voter.parse().unwrap()).build());
+            contract.add_vote(0, answers);
+        }
+
+        // Assert that only the totals per choice are reported
+        let results = contract.get_survey_results(0);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].prompt, "Will you attend in person?");
+        assert_eq!(results[0].tallies, vec![2, 0]);
+        assert_eq!(results[1].tallies, vec![0, 1, 1]);
+        assert_eq!(contract.get_event(0).total_votes, 3);
+    }
+
+    // Unit test to validate the number of answers and each choice index
+    #[test]
+    fn survey_answers_validated() {
+        // Set the account ID for testing as Alice, the creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with one event with questions and one without
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.events.push(EventBuilder::new(1).build());
+        contract.set_event_questions(0, survey());
+
+        // Assert that bad answers are rejected without counting the vote
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        for (id, answers, error) in [
+            (0, vec![0], VoteError::InvalidAnswers),
+            (0, vec![0, 1, 2], VoteError::InvalidAnswers),
+            (0, vec![2, 0], VoteError::InvalidAnswers),
+            (0, vec![1, 3], VoteError::InvalidAnswers),
+            (1, vec![0], VoteError::AnswersNotAccepted),
+        ] {
+            let voter = "bob.testnet".parse().unwrap();
+            let result = contract.internal_add_vote(id, voter, Method::AddVote, Some(answers));
+            assert_eq!(result, Err(error));
+        }
+        assert_eq!(contract.get_event(0).total_votes, 0);
+        assert_eq!(contract.get_survey_results(0)[1].tallies, vec![0, 0, 0]);
+    }
+
+    // Unit test to reject more than the allowed number of questions
+    #[test]
+    #[should_panic(expected = "An event cannot have more than 3 questions")]
+    fn set_event_questions_too_many() {
+        // Set the account ID for testing as Alice, the creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and ask four questions
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        let mut questions = survey();
+        questions.extend(survey());
+        contract.set_event_questions(0, questions);
+    }
+
+    // Unit test to reject a question with too many choices
+    #[test]
+    #[should_panic(expected = "A question must have between 1 and 5 choices")]
+    fn set_event_questions_too_many_choices() {
+        // Set the account ID for testing as Alice, the creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and ask a question with six choices
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.set_event_questions(
+            0,
+            vec![SurveyQuestion {
+                prompt: "Favourite day?".to_string(),
+                choices: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat"].map(String::from).to_vec(),
+            }],
+        );
+    }
+
+    // Two questions used by the survey tests
+    fn survey() -> Vec<SurveyQuestion> {
+        vec![
+            SurveyQuestion {
+                prompt: "Will you attend in person?".to_string(),
+                choices: vec!["Yes".to_string(), "No".to_string()],
+            },
+            SurveyQuestion {
+                prompt: "How did you hear about it?".to_string(),
+                choices: vec!["Friends".to_string(), "Social".to_string(), "Other".to_string()],
+            },
+        ]
+    }
+
+    // Unit test for the scaled votes per budget ratio
+    #[test]
+    fn votes_per_budget() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with events of known votes and budgets
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).budget(4).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).budget(3).voters(&["bob.testnet", "carol.testnet"]).build());
+        contract.events.push(EventBuilder::new(2).budget(5_000_000).build());
+
+        // Assert the ratios, rounded down after scaling
+        assert_eq!(contract.votes_per_budget(0), 250_000);
+        assert_eq!(contract.votes_per_budget(1), 666_666);
+        assert_eq!(contract.votes_per_budget(2), 0);
+    }
+
+    // Unit test to reject the ratio for an event with a zero budget
+    #[test]
+    #[should_panic(expected = "Event has no budget")]
+    fn votes_per_budget_zero_budget() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a zero budget event
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).budget(0).build());
+        contract.votes_per_budget(0);
+    }
+
+    // Unit test to reject the ratio for an unknown event
+    #[test]
+    #[should_panic(expected = "Event not found")]
+    fn votes_per_budget_invalid_id() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract without events
+        let contract = Contract::new(alice);
+        contract.votes_per_budget(0);
+    }
+
+    // Unit test to count links in descriptions against the configured maximum
+    #[test]
+    fn content_policy_counts_links() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Words with a scheme or a www. prefix count as links, in any case
+        assert_eq!(count_links("See https://a.org, HTTP://b.org and WWW.c.org"), 3);
+        assert_eq!(count_links("Email me at alice@near.org about the show"), 0);
+
+        // Create a new instance of the contract and add an event with exactly the allowed links
+        let mut contract = Contract::new(alice);
+        contract.set_link_policy(true, 2);
+        let description = "Info at https://a.org and www.b.org".to_string();
+        contract.add_event("Art Show".to_string(), 200, description, None, None, None, None);
+
+        // Assert that a link in the title is reported by the dry run, naming the rule
+        let check = contract.dry_run_add_event(
+            "alice.testnet".parse().unwrap(),
+            "Visit www.spam.example".to_string(),
+            U128(200),
+            None,
+        );
+        assert_eq!(
+            check.violations,
+            vec![CreateError::ContentPolicy { rule: ContentRule::LinkInTitle }]
+        );
+    }
+
+    // Unit test to reject a description with more links than allowed
+    #[test]
+    #[should_panic(expected = "Content policy: descriptions cannot contain more than 2 links")]
+    fn content_policy_too_many_links() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and add an event with three links
+        let mut contract = Contract::new(alice);
+        contract.set_link_policy(true, 2);
+        let description = "https://a.org https://b.org https://c.org".to_string();
+        contract.add_event("Art Show".to_string(), 200, description, None, None, None, None);
+    }
+
+    // Unit test to match banned terms regardless of case, without naming the term
+    #[test]
+    fn content_policy_banned_terms() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and ban a term given in mixed case
+        let mut contract = Contract::new(alice.clone());
+        contract.add_banned_term("  Free  Tokens ".to_string());
+        assert_eq!(contract.list_banned_terms(), vec!["free tokens".to_string()]);
+
+        // Assert that every case variant in a title is rejected with the rule, not the term
+        for title in ["free tokens", "FREE TOKENS here", "Get Free Tokens!"] {
+            let check = contract.dry_run_add_event(
+                alice.clone(),
+                title.to_string(),
+                U128(200),
+                None,
+            );
+            assert_eq!(
+                check.violations,
+                vec![CreateError::ContentPolicy { rule: ContentRule::BannedTerm }]
+            );
+            assert!(!check.violations[0].message().to_lowercase().contains("free"));
+        }
+
+        // Lifting the ban accepts the title again
+        contract.remove_banned_term("FREE TOKENS".to_string());
+        assert!(
+            contract
+                .dry_run_add_event(alice, "Free Tokens".to_string(), U128(200), None)
+                .allowed
+        );
+    }
+
+    // Unit test to apply the banned terms to edited descriptions
+    #[test]
+    #[should_panic(expected = "Content policy: text contains a banned term")]
+    fn content_policy_edit_event() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, add an event and edit in a banned term
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.add_banned_term("casino".to_string());
+        contract.edit_event(0, None, None, Some("Now with a CaSiNo".to_string()));
+    }
+
+    // Unit test to cap the number of banned terms
+    #[test]
+    #[should_panic(expected = "Cannot ban more than 100 terms")]
+    fn content_policy_banned_terms_capped() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and ban one term too many
+        let mut contract = Contract::new(alice);
+        for i in 0..=MAX_BANNED_TERMS {
+            contract.add_banned_term(format!("term{}", i));
+        }
+    }
+
+    // Unit test to list only the events created since UTC midnight
+    #[test]
+    fn events_today() {
+        // Set the time to 2024-03-10 15:00 UTC
+        let day: u64 = SECONDS_PER_DAY * 1_000_000_000;
+        let midnight: u64 = 1_710_028_800 * 1_000_000_000;
+        let mut context = get_context("alice.testnet".parse().unwrap());
+        context.block_timestamp(midnight + 15 * 3_600 * 1_000_000_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with events around the day boundary
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).created_at(midnight - day).build());
+        contract.events.push(EventBuilder::new(1).created_at(midnight - 1).build());
+        contract.events.push(EventBuilder::new(2).created_at(midnight).build());
+        contract.events.push(EventBuilder::new(3).created_at(midnight + day / 2).build());
+
+        // Assert that only the events from midnight onwards are returned
+        let ids: Vec<i32> = contract.events_today().iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    // Unit test to hide a deleted event from every view and reject changes to it
+    #[test]
+    fn deleted_event_hidden() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with two events and delete the first
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).created_at(5).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet"]).build());
+        contract.delete_event(0);
+
+        // Assert that listings and aggregates skip it while the owner can still see it
+        assert_eq!(contract.list_events().len(), 1);
+        assert_eq!(contract.event_count(), 1);
+        assert_eq!(contract.latest_event().unwrap().id, 1);
+        assert_eq!(contract.multi_event_voter_count(), 0);
+        assert_eq!(contract.list_deleted()[0].id, 0);
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        assert_eq!(contract.dry_run_vote(0, carol).violations, vec![VoteError::EventDeleted]);
+        assert!(get_logs()[0].contains(r#""event":"status_changed""#));
+    }
+
+    // Unit test to reject reading a deleted event by its ID
+    #[test]
+    #[should_panic(expected = "Event has been deleted")]
+    fn deleted_event_get_event() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a deleted event and read it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.delete_event(0);
+        contract.get_event(0);
+    }
+
+    // Unit test to reject edits to a deleted event
+    #[test]
+    #[should_panic(expected = "Event has been deleted")]
+    fn deleted_event_rejects_edit() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a deleted event and edit it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.delete_event(0);
+        contract.edit_event(0, Some("Back again".to_string()), None, None);
+    }
+
+    // Unit test to restore a deleted event inside the restore window
+    #[test]
+    fn restore_event_within_window() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract, add an event with a vote and delete it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+        contract.delete_event(0);
+
+        // A day before the window ends, nothing can be purged and the owner restores the event
+        context.block_timestamp((DEFAULT_RESTORE_WINDOW_SECS - SECONDS_PER_DAY) * 1_000_000_000);
+        testing_env!(context.build());
+        assert_eq!(contract.purge_deleted(10), 0);
+        contract.restore_event(0);
+
+        // Assert that the event is back with its votes
+        assert_eq!(contract.get_event(0).votes, vec!["bob.testnet".to_string()]);
+        assert!(contract.list_deleted().is_empty());
+    }
+
+    // Unit test to reject a restore once the window has expired
+    #[test]
+    #[should_panic(expected = "Restore window has expired")]
+    fn restore_event_after_window() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        // Create a new instance of the contract, delete an event and restore it too late
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.delete_event(0);
+        context.block_timestamp(DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000);
+        testing_env!(context.build());
+        contract.restore_event(0);
+    }
+
+    // Unit test to purge expired deleted events, refunding fees and keeping IDs stable
+    #[test]
+    fn purge_deleted_after_window() {
+        // Set the account ID for testing as Bob, who pays to vote on two events
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(5));
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        for id in 0..3 {
+            contract.events.push(EventBuilder::new(id).build());
+            contract.add_vote(id as usize, None);
+        }
+
+        // The owner deletes the first two events
+        let alice = get_context("alice.testnet".parse().unwrap());
+        testing_env!(alice.clone().build());
+        contract.delete_event(0);
+        contract.delete_event(1);
+
+        // Once the window has passed, nothing is purged until its refunds are processed
+        context.block_timestamp(DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000);
+        context.attached_deposit(NearToken::from_yoctonear(0));
+        testing_env!(context.build());
+        assert_eq!(contract.purge_deleted(10), 0);
+
+        // Event 1 is settled first, and event 0 still waiting does not hold it back
+        assert_eq!(contract.process_refunds(1, 10), 2);
+        assert_eq!(contract.purge_deleted(10), 1);
+        assert_eq!(contract.events[1].status, EventStatus::Purged);
+        assert_eq!(contract.process_refunds(0, 1), 1);
+        assert_eq!(contract.process_refunds(0, 1), 1);
+        assert_eq!(contract.process_refunds(0, 1), 0);
+        assert_eq!(contract.purge_deleted(10), 1);
+        assert_eq!(contract.purge_deleted(10), 0);
+
+        // Assert that the purged slots are emptied, Bob got both fees back and event 2 kept its ID
+        assert_eq!(contract.events[0].status, EventStatus::Purged);
+        assert!(contract.events[1].votes.is_empty() && contract.events[1].title.is_empty());
+        let refunds: Vec<NearToken> = get_created_receipts()
+            .iter()
+            .flat_map(|receipt| receipt.actions.iter())
+            .filter_map(|action| match action {
+                MockAction::Transfer { deposit, .. } => Some(*deposit),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(refunds, vec![NearToken::from_yoctonear(5); 2]);
+        assert_eq!(contract.get_event(2).id, 2);
+        assert_eq!(contract.list_events().len(), 1);
+    }
+
+    // Unit test to ignore duplicate voter entries left by legacy data
+    #[test]
+    fn unique_votes() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event listing Bob three times
+        let mut contract = Contract::new(alice);
+        contract.events.push(
+            EventBuilder::new(0)
+                .voters(&["bob.testnet", "carol.testnet", "bob.testnet", "bob.testnet"])
+                .build(),
+        );
+
+        // Assert that Bob and Carol are each counted once
+        assert_eq!(contract.get_event(0).voter_count, 4);
+        assert_eq!(contract.unique_votes(0), 2);
+    }
+
+    // Unit test for the owner merging duplicate voter entries left by legacy data
+    #[test]
+    fn dedup_voters() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event listing Bob three times, once in another case
+        let mut contract = Contract::new(alice);
+        contract.events.push(
+            EventBuilder::new(0)
+                .voters(&["bob.testnet", "carol.testnet", "bob.testnet", "BOB.testnet"])
+                .build(),
+        );
+        contract.dedup_voters(0);
+
+        // Assert that each voter is listed once, in first-vote order, and the counters match the list
+        let event = &contract.events[0];
+        assert_eq!(event.votes, vec!["bob.testnet", "carol.testnet"]);
+        assert_eq!(event.total_votes, 2);
+        assert_eq!(event.weighted_total, 2);
+        assert!(contract.is_event_consistent(0));
+    }
+
+    // Unit test that dedup_voters recomputes a loyalty-weighted total from the recorded weights and logs it
+    #[test]
+    fn dedup_voters_weighted() {
+        // An event lists Bob twice, and Bob's boosted weight of 3 was added for both entries
+        let mut contract = contract_with_events(0);
+        let voters = [BOB, CAROL, "Bob.testnet"];
+        let mut event = EventBuilder::new(0).voters(&voters).build();
+        event.loyalty_weighted = true;
+        event.weighted_total = 3 + 1 + 3;
+        contract.events.push(event);
+        contract.total_votes_cast = 3;
+        contract.vote_weights.insert(&(0, account(BOB)), &3);
+        let before = contract.get_checksum();
+        contract.dedup_voters(0);
+
+        // Assert that Bob's vote counts once at weight 3, beside Carol's at weight 1
+        let event = &contract.events[0];
+        assert_eq!(event.votes, [BOB, CAROL]);
+        assert_eq!((event.total_votes, event.weighted_total), (2, 4));
+
+        // Assert that the change was logged and moved the checksum to the new vote count
+        let deduped = r#""event":"voters_deduped""#;
+        assert!(get_logs().iter().any(|log| log.contains(deduped)));
+        let after = contract.get_checksum();
+        assert_eq!(after.change_seq, before.change_seq + 1);
+        assert_ne!(after.checksum, before.checksum);
+        assert_eq!(after.total_votes_cast, 2);
+
+        // Assert that a second pass finds nothing to drop and records no change
+        contract.dedup_voters(0);
+        assert_eq!(contract.get_checksum(), after);
+    }
+
+    // Unit test to rank events by distinct voters, ignoring duplicate entries and applying the limit
+    #[test]
+    fn events_by_unique_voters() {
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Event 0 lists Bob three times, event 1 has three distinct voters and event 2 has two
+        let mut contract = Contract::new(alice);
+        contract.events.push(
+            EventBuilder::new(0)
+                .voters(&["bob.testnet", "bob.testnet", "bob.testnet"])
+                .build(),
+        );
+        contract.events.push(
+            EventBuilder::new(1)
+                .voters(&["bob.testnet", "carol.testnet", "dave.testnet"])
+                .build(),
+        );
+        contract
+            .events
+            .push(EventBuilder::new(2).voters(&["bob.testnet", "carol.testnet"]).build());
+
+        // Assert the order by distinct voters, and that the limit keeps the top ones
+        assert_eq!(contract.events_by_unique_voters(10), vec![(1, 3), (2, 2), (0, 1)]);
+        assert_eq!(contract.events_by_unique_voters(2), vec![(1, 3), (2, 2)]);
+    }
+
+    // Unit test for case-insensitive title suggestions, leaving out drafts and deleted events
+    #[test]
+    fn autocomplete_titles() {
+        let mut contract = Scenario::new()
+            .create(ALICE, "Art Show", 100, None)
+            .create(ALICE, "art fair downtown", 100, None)
+            .create(ALICE, "Music Night", 100, None)
+            .create(ALICE, "Artisan Market", 100, None)
+            .contract();
+        let description = "Scenario event".to_string();
+        contract.add_event("Art draft".to_string(), 100, description, None, None, None, None);
+        contract.delete_event(3);
+
+        // Assert that matches come in ID order within a bucket, and that the limit applies
+        let expected = vec![(1, "art fair downtown".to_string()), (0, "Art Show".to_string())];
+        assert_eq!(contract.autocomplete_titles("ART".to_string(), 10), expected);
+        assert_eq!(contract.autocomplete_titles("ART".to_string(), 1), expected[..1].to_vec());
+
+        // Assert that prefixes longer than a bucket are checked against the whole title
+        let fair = contract.autocomplete_titles("Art Fair Down".to_string(), 10);
+        assert_eq!(fair, vec![(1, "art fair downtown".to_string())]);
+        assert!(contract.autocomplete_titles("art fair up".to_string(), 10).is_empty());
+
+        // Assert that the index lives in its own storage, so new buckets do not grow the root state
+        let size = contract.serialized_size();
+        contract.index_title(99, "Zebra crossing");
+        assert_eq!(contract.serialized_size(), size);
+        contract.unindex_title(99, "Zebra crossing");
+        let zebra = contract.autocomplete_titles("zebra".to_string(), 10);
+        assert!(zebra.is_empty());
+    }
+
+    // Unit test for an edited title moving its event to another prefix bucket
+    #[test]
+    fn autocomplete_titles_after_edit() {
+        let mut contract = contract_with_events(2);
+        contract.edit_event(1, Some("Jazz Evening".to_string()), None, None);
+
+        // Assert that the old title no longer matches and the new one does
+        assert!(contract.autocomplete_titles("Event 1".to_string(), 10).is_empty());
+        let events = contract.autocomplete_titles("ev".to_string(), 10);
+        assert_eq!(events, vec![(0, "Event 0".to_string())]);
+        let new = contract.autocomplete_titles("jazz".to_string(), 10);
+        assert_eq!(new, vec![(1, "Jazz Evening".to_string())]);
+
+        // Assert that a restored event is suggested again
+        contract.delete_event(1);
+        assert!(contract.autocomplete_titles("jazz".to_string(), 10).is_empty());
+        contract.restore_event(1);
+        assert_eq!(contract.autocomplete_titles("jazz".to_string(), 10).len(), 1);
+    }
+
+    // Unit test to reject prefixes shorter than two characters
+    #[test]
+    #[should_panic(expected = "Prefix must be at least 2 characters")]
+    fn autocomplete_titles_short_prefix() {
+        let contract = contract_with_events(1);
+        contract.autocomplete_titles("E".to_string(), 10);
+    }
+
+    // Unit test to reject donations until an event has enough votes
+    #[test]
+    #[should_panic(expected = "Funding opens after 2 more votes")]
+    fn donate_below_threshold() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event needing three votes, and vote once
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Fixture event".to_string(),
+            None,
+            Some(3),
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+
+        // Donate as Carol before the threshold is reached
+        let mut context = get_context("carol.testnet".parse().unwrap());
+        context.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.donate(0);
+    }
+
+    // Unit test for a hard-capped event filled exactly to its budget
+    #[test]
+    fn funding_cap_exact_fill() {
+        let mut contract = contract_with_events(1);
+        contract.set_funding_cap(0, FundingCap::HardCap);
+        assert_eq!(contract.get_event(0).funding.remaining_capacity, Some(U128(100)));
+
+        // Bob and Carol donate exactly the budget between them
+        ctx(BOB, 60, 0);
+        contract.donate(0);
+        assert_eq!(contract.get_event(0).funding.remaining_capacity, Some(U128(40)));
+        ctx(CAROL, 40, 0);
+        contract.donate(0);
+
+        // Assert that the event took all of it and has no capacity left
+        let funding = contract.get_event(0).funding;
+        assert_eq!(funding.funds_raised, U128(100));
+        assert_eq!(funding.remaining_capacity, Some(U128(0)));
+        assert!(get_created_receipts().is_empty());
+    }
+
+    // Unit test to reject a donation beyond a hard-capped event's budget, naming what is left
+    #[test]
+    #[should_panic(expected = "Donation exceeds the remaining capacity of 40 yoctoNEAR")]
+    fn funding_cap_exceeded() {
+        let mut contract = contract_with_events(1);
+        contract.set_funding_cap(0, FundingCap::HardCap);
+        ctx(BOB, 60, 0);
+        contract.donate(0);
+        ctx(CAROL, 41, 0);
+        contract.donate(0);
+    }
+
+    // Unit test to credit a donation up to the budget and refund the overflow
+    #[test]
+    fn funding_cap_overflow_refund() {
+        let mut contract = contract_with_events(1);
+        contract.set_funding_cap(0, FundingCap::HardCapWithOverflowRefund);
+        ctx(BOB, 70, 0);
+        contract.donate(0);
+
+        // Carol donates 50 when only 30 are left
+        let carol = account(CAROL);
+        ctx(CAROL, 50, 0);
+        contract.donate(0);
+
+        // Assert that 30 were credited and the other 20 sent back to Carol
+        let funding = contract.get_event(0).funding;
+        assert_eq!(funding.funds_raised, U128(100));
+        assert_eq!(funding.remaining_capacity, Some(U128(0)));
+        assert_eq!(contract.donations.get(&(0, carol.clone())), Some(30));
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, carol);
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(20)
+        ));
+    }
+
+    // Unit test to unlock funding on the vote that reaches the threshold
+    #[test]
+    fn funding_unlocks_at_threshold() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event needing two votes
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Fixture event".to_string(),
+            None,
+            Some(2),
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        assert!(!contract.get_event(0).funding.funding_unlocked);
+
+        // The first vote leaves funding locked and logs nothing about it
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+        assert!(!contract.get_event(0).funding.funding_unlocked);
+        assert!(!get_logs().iter().any(|log| log.contains("funding_unlocked")));
+
+        // The second vote unlocks funding and logs it
+        testing_env!(get_context("carol.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+        assert!(contract.get_event(0).funding.funding_unlocked);
+        let unlocked = r#""event":"funding_unlocked","data":[{"event_id":0,"total_votes":2}]"#;
+        assert!(get_logs().iter().any(|log| log.contains(unlocked)));
+
+        // Donations are now accepted and show up in the funding section
+        let mut context = get_context("dave.testnet".parse().unwrap());
+        context.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.donate(0);
+        let funding = contract.get_event(0).funding;
+        assert_eq!(funding.funds_raised, U128(100));
+        assert_eq!(funding.donor_count, 1);
+    }
+
+    // Unit test to keep funding unlocked after votes are retracted below the threshold
+    #[test]
+    fn funding_stays_unlocked_after_retraction() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event needing one vote, and reach it
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Fixture event".to_string(),
+            None,
+            Some(1),
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+
+        // Retract the vote, dropping back below the threshold
+        contract.remove_vote(0);
+        assert_eq!(contract.get_total_votes(0), 0);
+
+        // Assert that funding stays open, so earlier donors are not locked out
+        assert!(contract.get_event(0).funding.funding_unlocked);
+        let mut context = get_context("carol.testnet".parse().unwrap());
+        context.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.donate(0);
+        assert_eq!(contract.get_event(0).funding.funds_raised, U128(100));
+    }
+
+    // Unit test to accept a vote paying exactly the vote fee, with nothing refunded
+    #[test]
+    fn vote_fee_exact() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract charging 100 yoctoNEAR per vote, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_vote_fee(U128(100));
+        contract.events.push(EventBuilder::new(0).build());
+        assert_eq!(contract.get_vote_requirements().min_deposit, NearToken::from_yoctonear(100));
+
+        // Vote as Bob attaching exactly the fee
+        let mut context = get_context("bob.testnet".parse().unwrap());
+        context.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+
+        // Assert that the vote counted and no refund was sent
+        assert_eq!(contract.get_total_votes(0), 1);
+        assert!(get_created_receipts().is_empty());
+    }
+
+    // Unit test to refund the part of a deposit above the vote fee
+    #[test]
+    fn vote_fee_refunds_overpayment() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract charging 100 yoctoNEAR per vote, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_vote_fee(U128(100));
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Vote as Bob attaching 250
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(250));
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+
+        // Assert that the 150 above the fee went back to Bob and only the fee was recorded
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, bob);
+        assert_eq!(receipts[1].receiver_id, env::current_account_id());
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(150)
+        ));
+        assert_eq!(contract.vote_fees.get(&(0, bob)), Some(100));
+    }
+
+    // Unit test to refund the whole deposit of a vote when no vote fee is charged
+    #[test]
+    fn vote_overpayment_refunded_without_fee() {
+        // Create a contract with one event by Alice and no vote fee
+        let mut contract = contract_with_events(1);
+        assert_eq!(contract.get_config().vote_fee, 0);
+
+        // Vote as Bob attaching 250 anyway
+        ctx(BOB, 250, 0);
+        contract.add_vote(0, None);
+
+        // Assert that nothing was kept as a fee and the 250 went back to Bob
+        assert_eq!(contract.vote_fees.get(&(0, account(BOB))), None);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, account(BOB));
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(250)
+        ));
+    }
+
+    // Unit test to reject a vote paying less than the vote fee
+    #[test]
+    #[should_panic(expected = "Voting requires an attached deposit")]
+    fn vote_fee_underpayment() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract charging 100 yoctoNEAR per vote, with a fixture event
+        let mut contract = Contract::new(alice);
+        contract.set_vote_fee(U128(100));
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Vote as Bob attaching less than the fee
+        let mut context = get_context("bob.testnet".parse().unwrap());
+        context.attached_deposit(NearToken::from_yoctonear(99));
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+    }
+
+    // Unit test to accept a vote attaching the event's minimum voter balance and refund the proof
+    #[test]
+    fn min_voter_balance_sufficient_proof() {
+        // Create a contract with one event by Alice requiring voters to hold 1000 yoctoNEAR
+        let mut contract = contract_with_events(1);
+        contract.set_min_voter_balance(0, Some(U128(1000)));
+        assert_eq!(contract.get_event(0).min_voter_balance, Some(U128(1000)));
+
+        // Vote as Bob attaching exactly the minimum
+        ctx(BOB, 1000, 0);
+        contract.add_vote(0, None);
+
+        // Assert that the vote counted and the whole proof went back to Bob
+        assert_eq!(contract.get_total_votes(0), 1);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, account(BOB));
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(1000)
+        ));
+    }
+
+    // Unit test to reject a vote attaching less than the event's minimum voter balance
+    #[test]
+    #[should_panic(
+        expected = "Voting on this event requires attaching at least 1000 yoctoNEAR as balance proof"
+    )]
+    fn min_voter_balance_insufficient_proof() {
+        // Create a contract with one event by Alice requiring voters to hold 1000 yoctoNEAR
+        let mut contract = contract_with_events(1);
+        contract.set_min_voter_balance(0, Some(U128(1000)));
+
+        // Vote as Bob attaching less than the minimum
+        ctx(BOB, 999, 0);
+        contract.add_vote(0, None);
+    }
+
+    // Unit test to reject setting the minimum voter balance of someone else's event
+    #[test]
+    #[should_panic(expected = "Only the event creator can set the minimum voter balance")]
+    fn min_voter_balance_not_creator() {
+        let mut contract = contract_with_events(1);
+        ctx(BOB, 0, 0);
+        contract.set_min_voter_balance(0, Some(U128(1000)));
+    }
+
+    // Unit test to summarize an event for its organizer's dashboard
+    #[test]
+    fn event_analytics() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a 400 yoctoNEAR event and two busier rivals
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).budget(400).build());
+        contract.events.push(
+            EventBuilder::new(1)
+                .voters(&["w.testnet", "x.testnet", "y.testnet", "z.testnet"])
+                .build(),
+        );
+        contract.events.push(
+            EventBuilder::new(2)
+                .voters(&["v.testnet", "w.testnet", "x.testnet", "y.testnet", "z.testnet"])
+                .deadline(1)
+                .build(),
+        );
+
+        // Bob votes 20 days ago, Carol 3 days ago and Dave today, in the middle of the day
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        for (voter, days_ago) in [("bob.testnet", 20), ("carol.testnet", 3), ("dave.testnet", 0)] {
+            let mut context = get_context(voter.parse().unwrap());
+            context.block_timestamp((100 - days_ago) * day + day / 2);
+            testing_env!(context.build());
+            contract.add_vote(0, None);
+        }
+
+        // Dave also donates a quarter of the budget
+        let mut context = get_context("dave.testnet".parse().unwrap());
+        context
+            .block_timestamp(100 * day + day / 2)
+            .attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.donate(0);
+
+        // Assert each field; event 2 is closed, so only event 1 counts toward the rank
+        let analytics = contract.get_event_analytics(0);
+        let mut votes_per_day = vec![0; 14];
+        votes_per_day[10] = 1;
+        votes_per_day[13] = 1;
+        assert_eq!(analytics.event_id, 0);
+        assert_eq!(analytics.votes_per_day, votes_per_day);
+        assert!(!analytics.votes_per_day_truncated);
+        assert_eq!(analytics.unique_voters, 3);
+        assert_eq!(analytics.funding_progress_percent, 25);
+        assert_eq!(analytics.rank, Some(2));
+        assert!(!analytics.rank_truncated);
+        assert_eq!(contract.get_event_analytics(2).rank, None);
+    }
+
+    // Unit test to rank only against the most recent MAX_RANK_SCAN events
+    #[test]
+    fn event_analytics_rank_truncated() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a busy old event followed by MAX_RANK_SCAN quiet ones
+        let mut contract = Contract::new(alice);
+        let busy = EventBuilder::new(0).voters(&["x.testnet", "y.testnet"]);
+        contract.events.push(busy.build());
+        for id in 1..=MAX_RANK_SCAN as i32 {
+            contract.events.push(EventBuilder::new(id).build());
+        }
+        let newest = EventBuilder::new(MAX_RANK_SCAN as i32 + 1).voters(&["x.testnet"]);
+        contract.events.push(newest.build());
+
+        // Assert that the busy old event is out of the window, so the newest event ranks first
+        let analytics = contract.get_event_analytics(MAX_RANK_SCAN + 1);
+        assert_eq!(analytics.rank, Some(1));
+        assert!(analytics.rank_truncated);
+    }
+
+    // Unit test to rank newer, well-voted events above older ones in the feed
+    #[test]
+    fn ranked_feed() {
+        // Set the account ID for testing as Alice, at day 30
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(30 * day);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with events of different ages and vote counts
+        let mut contract = Contract::new(alice);
+        let voters = ["a.testnet", "b.testnet", "c.testnet", "d.testnet", "e.testnet"];
+        // Old and popular: 5 votes, 20 days old, no bonus left
+        contract.events.push(EventBuilder::new(0).created_at(10 * day).voters(&voters).build());
+        // New and popular: 5 votes, created today, full bonus
+        contract.events.push(EventBuilder::new(1).created_at(30 * day).voters(&voters).build());
+        // New and quiet: 1 vote, 2 days old
+        let quiet = EventBuilder::new(2).created_at(28 * day).voters(&voters[..1]);
+        contract.events.push(quiet.build());
+        // Old and quiet: no votes, 25 days old
+        contract.events.push(EventBuilder::new(3).created_at(5 * day).build());
+
+        // Assert that scores 15, 9, 5 and 0 order the feed, and that the limit applies
+        let ids: Vec<i32> = contract.ranked_feed(10).iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![1, 2, 0, 3]);
+        assert_eq!(contract.ranked_feed(2).len(), 2);
+    }
+
+    // Unit test to queue notifications on significant transitions
+    #[test]
+    fn outbox_append() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event of budget 100 needing one vote
+        let mut contract = Contract::new(alice);
+        contract.add_event(
+            "Art Show".to_string(),
+            100,
+            "Fixture event".to_string(),
+            None,
+            Some(1),
+            None,
+            None,
+        );
+        contract.publish_event(0);
+
+        // Bob's vote unlocks funding and his donation completes the budget
+        let mut context = get_context("bob.testnet".parse().unwrap());
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+        context.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(context.build());
+        contract.donate(0);
+
+        // The owner marks the event funded and then deletes it
+        testing_env!(get_context("alice.testnet".parse().unwrap()).block_timestamp(7).build());
+        contract.mark_funded(0);
+        contract.delete_event(0);
+
+        // Assert that the four transitions were queued in order, and votes and donations were not
+        let outbox = contract.peek_outbox(0, 10);
+        let kinds: Vec<&str> = outbox.iter().map(|n| n.kind.as_str()).collect();
+        let expected = vec!["funding_unlocked", "event_funded", "event_funded", "status_changed"];
+        assert_eq!(kinds, expected);
+        assert_eq!(outbox[2].payload_json, r#"{"event_id":0,"funds_raised":"100"}"#);
+        assert_eq!(outbox[3].id, 3);
+        assert_eq!(outbox[3].payload_json, r#"{"event_id":0,"status":"Deleted"}"#);
+        assert_eq!(outbox[3].created_at, 7);
+        assert_eq!(contract.peek_outbox(1, 1), vec![outbox[1].clone()]);
+    }
+
+    // Unit test to prune acknowledged notifications
+    #[test]
+    fn outbox_ack_prunes() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a keeper and three queued notifications
+        let mut contract = Contract::new(alice);
+        let keeper: AccountId = "relay.testnet".parse().unwrap();
+        contract.set_keeper(Some(keeper.clone()));
+        contract.events.push(EventBuilder::new(0).build());
+        contract.delete_event(0);
+        contract.restore_event(0);
+        contract.delete_event(0);
+
+        // Acknowledge the first two as the keeper
+        testing_env!(get_context(keeper.clone()).build());
+        assert_eq!(contract.ack_outbox(1), 2);
+
+        // Assert that only the third remains, its storage is kept and the others are reclaimed
+        let remaining = contract.peek_outbox(0, 10);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, 2);
+        assert!(contract.outbox.get(&0).is_none());
+        assert!(contract.outbox.get(&1).is_none());
+
+        // Acknowledging past the end or again removes nothing more than exists
+        assert_eq!(contract.ack_outbox(1), 0);
+        assert_eq!(contract.ack_outbox(99), 1);
+        assert!(contract.peek_outbox(0, 10).is_empty());
+        assert_eq!(contract.outbox_dropped(), 0);
+    }
+
+    // Unit test to reject acknowledgements from anyone but the keeper
+    #[test]
+    #[should_panic(expected = "Only the keeper can acknowledge notifications")]
+    fn outbox_ack_keeper_only() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a keeper and acknowledge as the owner
+        let mut contract = Contract::new(alice);
+        contract.set_keeper(Some("relay.testnet".parse().unwrap()));
+        contract.ack_outbox(0);
+    }
+
+    // Unit test to evict the oldest notifications from a full outbox and count them
+    #[test]
+    fn outbox_eviction() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract and queue two more notifications than fit
+        let mut contract = Contract::new(alice);
+        for id in 0..MAX_OUTBOX + 2 {
+            contract.notify(&StatusChangedLog {
+                event_id: id,
+                status: EventStatus::Open,
+            });
+        }
+
+        // Assert that the two oldest were dropped and counted
+        assert_eq!(contract.outbox_dropped(), 2);
+        let outbox = contract.peek_outbox(0, MAX_OUTBOX + 10);
+        assert_eq!(outbox.len() as u64, MAX_OUTBOX);
+        assert_eq!(outbox[0].id, 2);
+        assert_eq!(outbox.last().unwrap().id, MAX_OUTBOX + 1);
+    }
+
+    // Unit test to rank creators by the votes their events received
+    #[test]
+    fn creator_vote_totals() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract where Bob's two events outvote Carol's one
+        let mut contract = Contract::new(alice);
+        let voters = ["v.testnet", "w.testnet", "x.testnet"];
+        let carol = EventBuilder::new(0).creator("carol.testnet").voters(&voters);
+        contract.events.push(carol.build());
+        for id in 1..3 {
+            let bob = EventBuilder::new(id).creator("bob.testnet").voters(&voters[..2]);
+            contract.events.push(bob.build());
+        }
+
+        // Assert that Bob leads with 4 votes, ahead of Carol with 3
+        assert_eq!(
+            contract.creator_vote_totals(),
+            vec![
+                ("bob.testnet".parse().unwrap(), 4),
+                ("carol.testnet".parse().unwrap(), 3),
+            ]
+        );
+    }
+
+    // Unit test to claim a short code and resolve it regardless of case
+    #[test]
+    fn short_code_case_insensitive() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with Alice's event and claim a lowercase code
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.set_short_code(0, "art24".to_string());
+
+        // Assert that the code is stored uppercase and resolves in any case
+        assert_eq!(contract.get_event(0).short_code, Some("ART24".to_string()));
+        assert_eq!(contract.resolve_short_code("ART24".to_string()), Some(0));
+        assert_eq!(contract.resolve_short_code("Art24".to_string()), Some(0));
+        assert_eq!(contract.resolve_short_code("MUSIC".to_string()), None);
+
+        // Claiming a new code releases the old one
+        contract.set_short_code(0, "Show1".to_string());
+        assert_eq!(contract.resolve_short_code("art24".to_string()), None);
+        assert_eq!(contract.resolve_short_code("SHOW1".to_string()), Some(0));
+    }
+
+    // Unit test to reject a short code already claimed by another event, in any case
+    #[test]
+    #[should_panic(expected = "Short code is already taken")]
+    fn short_code_collision() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with two of Alice's events
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.events.push(EventBuilder::new(1).creator("alice.testnet").build());
+
+        // Claim the same code for both
+        contract.set_short_code(0, "ART24".to_string());
+        contract.set_short_code(1, "art24".to_string());
+    }
+
+    // Unit test to reject short codes the owner reserved
+    #[test]
+    #[should_panic(expected = "Short code is reserved")]
+    fn short_code_reserved() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract reserving "admin", with an event of Alice's
+        let mut contract = Contract::new(alice);
+        contract.add_reserved_code("admin".to_string());
+        assert_eq!(contract.list_reserved_codes(), vec!["ADMIN".to_string()]);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+
+        // Claim the reserved code in a different case
+        contract.set_short_code(0, "Admin".to_string());
+    }
+
+    // Unit test to reject malformed short codes
+    #[test]
+    #[should_panic(expected = "Short code must be 3 to 12 letters or digits")]
+    fn short_code_invalid() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with Alice's event and claim a code with a dash
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.set_short_code(0, "ART-24".to_string());
+    }
+
+    // Unit test to release an event's short code when it is deleted
+    #[test]
+    fn short_code_released_on_delete() {
+        // Set the account ID for testing as Alice, the owner and creator
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with two events, the first holding ART24
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.events.push(EventBuilder::new(1).creator("alice.testnet").build());
+        contract.set_short_code(0, "ART24".to_string());
+
+        // Delete the first event
+        contract.delete_event(0);
+
+        // Assert that the code no longer resolves and the second event can claim it
+        assert_eq!(contract.resolve_short_code("ART24".to_string()), None);
+        contract.set_short_code(1, "ART24".to_string());
+        assert_eq!(contract.resolve_short_code("art24".to_string()), Some(1));
+
+        // A restored event comes back without a code
+        contract.restore_event(0);
+        assert_eq!(contract.get_event(0).short_code, None);
+    }
+
+    // Unit test to let the owner mark an event funded
+    #[test]
+    fn mark_funded() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a fixture event and mark it funded
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.mark_funded(0);
+
+        // Assert that the flag is set and dry runs report it
+        assert!(contract.get_event(0).funding.funded);
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        assert_eq!(contract.dry_run_vote(0, bob).violations, vec![VoteError::AlreadyFunded]);
+    }
+
+    // Unit test to reject marking an event funded by anyone but the owner
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn mark_funded_owner_only() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract owned by Alice, with a fixture event
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+
+        // Mark it funded as Bob
+        contract.mark_funded(0);
+    }
+
+    // Unit test to reject votes once an event is funded
+    #[test]
+    #[should_panic(expected = "Event already funded")]
+    fn add_vote_after_funded() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a funded fixture event
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.mark_funded(0);
+
+        // Vote as Bob
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_vote(0, None);
+    }
+
+    // Template used by the template tests: a music category taking budgets of 100 to 500
+    fn music_template() -> Template {
+        Template {
+            category: "Music".to_string(),
+            min_budget: 100,
+            max_budget: 500,
+            required_tags: vec!["music".to_string(), "live".to_string()],
+        }
+    }
+
+    // Unit test to create an event from a template, stamping its ID and required tags
+    #[test]
+    fn add_event_from_template() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, publish a template and add an event from it
+        let mut contract = Contract::new(alice);
+        let template_id = contract.create_template(music_template());
+        contract.add_event(
+            "Jazz Night".to_string(),
+            300,
+            "Description".to_string(),
+            None,
+            None,
+            Some(template_id),
+            None,
+        );
+
+        // Assert that the event carries the template ID and its required tags
+        let event = contract.get_my_event(0);
+        assert_eq!(event.template_id, Some(template_id));
+        assert_eq!(event.tags, vec!["music".to_string(), "live".to_string()]);
+        assert_eq!(contract.list_templates()[0].template, music_template());
+
+        // Extra tags are fine as long as the required ones stay
+        let tags = vec!["live".to_string(), "jazz".to_string(), "music".to_string()];
+        contract.set_event_tags(0, tags.clone());
+        assert_eq!(contract.get_my_event(0).tags, tags);
+    }
+
+    // Unit test to reject a budget outside the template's range
+    #[test]
+    #[should_panic(expected = "Budget must be between 100 and 500 for this template")]
+    fn add_event_template_budget_out_of_range() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, publish a template and exceed its budget
+        let mut contract = Contract::new(alice);
+        let template_id = contract.create_template(music_template());
+        contract.add_event(
+            "Jazz Night".to_string(),
+            501,
+            "Description".to_string(),
+            None,
+            None,
+            Some(template_id),
+            None,
+        );
+    }
+
+    // Unit test to reject editing the budget of an event made from a template outside the template's range
+    #[test]
+    #[should_panic(expected = "Budget must be between 100 and 500 for this template")]
+    fn edit_event_template_budget_out_of_range() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, add an event from a template and move its budget past the range
+        let mut contract = Contract::new(alice);
+        let template_id = contract.create_template(music_template());
+        contract.add_event(
+            "Jazz Night".to_string(),
+            300,
+            "Description".to_string(),
+            None,
+            None,
+            Some(template_id),
+            None,
+        );
+        contract.edit_event(0, None, Some(U128(501)), None);
+    }
+
+    // Unit test to reject dropping a template's required tags
+    #[test]
+    #[should_panic(expected = "Tags must include the template's required tags")]
+    fn set_event_tags_keeps_template_tags() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event made from a template
+        let mut contract = Contract::new(alice);
+        let template_id = contract.create_template(music_template());
+        contract.add_event(
+            "Jazz Night".to_string(),
+            300,
+            "Description".to_string(),
+            None,
+            None,
+            Some(template_id),
+            None,
+        );
+
+        // Replace its tags without "live"
+        contract.set_event_tags(0, vec!["music".to_string()]);
+    }
+
+    // Unit test to reject new events from a deactivated template
+    #[test]
+    #[should_panic(expected = "Template is not active")]
+    fn add_event_deactivated_template() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract, publish a template, update and deactivate it
+        let mut contract = Contract::new(alice);
+        let template_id = contract.create_template(music_template());
+        let mut wider = music_template();
+        wider.max_budget = 1_000;
+        contract.update_template(template_id, wider.clone());
+        contract.deactivate_template(template_id);
+        let listed = &contract.list_templates()[0];
+        assert_eq!((listed.template.clone(), listed.active), (wider, false));
+
+        // Add an event from it
+        contract.add_event(
+            "Jazz Night".to_string(),
+            300,
+            "Description".to_string(),
+            None,
+            None,
+            Some(template_id),
+            None,
+        );
+    }
+
+    // Unit test to reject template management by anyone but the owner
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn create_template_owner_only() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract owned by Alice and publish a template as Bob
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.create_template(music_template());
+    }
+
+    // Unit test to move a vote to another event, keeping both tallies and the fee record consistent
+    #[test]
+    fn migrate_my_vote() {
+        // Set the account ID for testing as Bob, attaching a fee
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(10));
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with two fixture events and vote on the first
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).voters(&["carol.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).build());
+        contract.add_vote(0, None);
+
+        // Move the vote to the second event
+        testing_env!(get_context(bob.clone()).build());
+        contract.migrate_my_vote(0, 1);
+
+        // Assert that the vote left the first event and counts once on the second
+        assert_eq!(contract.get_total_votes(0), 1);
+        assert_eq!(contract.get_total_votes(1), 1);
+        assert!(contract.is_event_consistent(0));
+        assert!(contract.is_event_consistent(1));
+        assert_eq!(contract.get_voters(1, 0, 10), vec!["bob.testnet".to_string()]);
+        assert!(get_logs()[0].contains(r#""event":"vote_migrated""#));
+
+        // The fee moved with the vote and is refunded when it is removed from the new event
+        contract.remove_vote(1);
+        assert!(matches!(
+            get_created_receipts()[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(10)
+        ));
+    }
+
+    // Unit test to reject moving a vote the caller never cast
+    #[test]
+    #[should_panic(expected = "You have not voted for this event")]
+    fn migrate_my_vote_not_voted() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract with two fixture events
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.events.push(EventBuilder::new(1).build());
+
+        // Move a vote Bob never cast
+        contract.migrate_my_vote(0, 1);
+    }
+
+    // Unit test to reject moving a vote to an event that no longer accepts votes
+    #[test]
+    #[should_panic(expected = "Voting has closed for this event")]
+    fn migrate_my_vote_target_closed() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob);
+        context.block_timestamp(100);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with an open event Bob voted on and a closed one
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).deadline(50).build());
+
+        // Move the vote to the closed event
+        contract.migrate_my_vote(0, 1);
+    }
+
+    // Unit test to keep a moved vote from skipping the balance proof of the target event
+    #[test]
+    #[should_panic(
+        expected = "Votes cannot be moved onto an event with a minimum voter balance; vote on it directly"
+    )]
+    fn migrate_my_vote_target_min_voter_balance() {
+        // Create a contract with two events by Alice, the second requiring voters to hold 1000 yoctoNEAR
+        let mut contract = Scenario::new().events(2).vote(BOB, 0).contract();
+        contract.set_min_voter_balance(1, Some(U128(1000)));
+
+        // Bob, who attached nothing when voting, moves the vote to the second event
+        ctx(BOB, 0, 0);
+        contract.migrate_my_vote(0, 1);
+    }
+
+    // Unit test to keep votes on a closed event from being taken back
+    #[test]
+    #[should_panic(expected = "Voting has closed for this event")]
+    fn remove_vote_after_close() {
+        // Bob adds an event, Carol votes for it, then voting closes
+        let mut contract = Scenario::new()
+            .create(BOB, "Book Fair", 100, None)
+            .vote(CAROL, 0)
+            .close(0)
+            .contract();
+
+        // Carol tries to retract her vote
+        ctx(CAROL, 0, 1);
+        contract.remove_vote(0);
+    }
+
+    // Unit test to add an event with an invite code and reject reusing it
+    #[test]
+    #[should_panic(expected = "Invite code is invalid or already used")]
+    fn invite_code_reuse() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract that is invite-only, whitelist Bob and issue two codes
+        let mut contract = Contract::new(alice);
+        contract.set_invite_only(true);
+        contract.add_creator("bob.testnet".parse().unwrap());
+        let code = contract.issue_invite();
+        let other = contract.issue_invite();
+        assert_ne!(code, other);
+        assert_eq!(contract.invites_outstanding(), 2);
+
+        // Bob adds an event with the first code, which uses it up
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        let description = "Description".to_string();
+        let title = "Art Show".to_string();
+        let invite = Some(code.clone());
+        contract.add_event(title.clone(), 200, description.clone(), None, None, None, invite);
+        assert_eq!(contract.list_my_drafts().len(), 1);
+        assert_eq!(contract.invites_outstanding(), 1);
+
+        // Bob tries the same code again
+        contract.add_event(title, 200, description, None, None, None, Some(code));
+    }
+
+    // Unit test to reject unknown invite codes and missing ones while creation is invite-only
+    #[test]
+    fn invite_code_invalid() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract that is invite-only, with Bob whitelisted
+        let mut contract = Contract::new(alice.clone());
+        contract.set_invite_only(true);
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        contract.add_creator(bob.clone());
+        contract.issue_invite();
+
+        // Assert that a made-up code and no code are both rejected, while the owner needs none
+        let title = "Art Show".to_string();
+        let check = |code: Option<&str>| {
+            contract
+                .dry_run_add_event(bob.clone(), title.clone(), U128(200), code.map(String::from))
+                .violations
+        };
+        assert_eq!(check(Some("0123456789abcdef")), vec![CreateError::InvalidInvite]);
+        assert_eq!(check(None), vec![CreateError::InviteRequired]);
+        assert!(contract.dry_run_add_event(alice, title.clone(), U128(200), None).allowed);
+        assert_eq!(contract.invites_outstanding(), 1);
+    }
+
+    // Unit test to restore creation without codes when invite-only is turned off
+    #[test]
+    fn invite_only_off() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with Bob whitelisted, turning invite-only on and off
+        let mut contract = Contract::new(alice);
+        contract.add_creator("bob.testnet".parse().unwrap());
+        contract.set_invite_only(true);
+        contract.set_invite_only(false);
+
+        // Assert that Bob adds an event without a code
+        testing_env!(get_context("bob.testnet".parse().unwrap()).build());
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(contract.list_my_drafts().len(), 1);
+    }
+
+    // Unit test to bucket votes by day since the event was created
+    #[test]
+    fn votes_per_day() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an event created at noon on day 10
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).created_at(10 * day + day / 2).build());
+
+        // Vote twice on the creation day, once the next morning and once four days later
+        let votes = [
+            ("a.testnet", 10 * day + day / 2),
+            ("b.testnet", 10 * day + day - 1),
+            ("c.testnet", 11 * day + day / 4),
+            ("d.testnet", 14 * day + day / 2),
+        ];
+        for (voter, at) in votes {
+            let mut context = get_context(voter.parse().unwrap());
+            context.block_timestamp(at);
+            testing_env!(context.build());
+            contract.add_vote(0, None);
+        }
+
+        // Assert that days are counted from creation, not from midnight, and empty days are left out
+        assert_eq!(contract.votes_per_day(0), vec![(0, 3), (4, 1)]);
+    }
+
+    // Unit test to reject votes during the grace period and accept them once it ends
+    #[test]
+    fn vote_grace_period() {
+        // Set the account ID for testing as Alice, the owner, at time 1,000
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+
+        // Create a new instance of the contract with a 500ns grace period and add an event
+        let mut contract = Contract::new(alice);
+        contract.set_vote_grace_period(500, None);
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "Description".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        assert_eq!(contract.get_event(0).votes_open_at, 1_500);
+
+        // One nanosecond before the boundary, Bob's vote is rejected with the opening time
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.block_timestamp(1_499);
+        testing_env!(context.build());
+        assert_eq!(contract.try_add_vote(0), Err("Voting opens at 1500".to_string()));
+        assert_eq!(
+            contract.dry_run_vote(0, bob).violations,
+            vec![VoteError::NotOpenYet { opens_at: 1_500 }]
+        );
+
+        // At the boundary, the vote counts
+        context.block_timestamp(1_500);
+        testing_env!(context.build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test to let long-registered creators skip the grace period
+    #[test]
+    fn vote_grace_period_exemption() {
+        // Set the account ID for testing as Alice, the owner, registering her at time 0
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.build());
+        let mut contract = Contract::new(alice);
+        contract.register_voter();
+
+        // A day later, exempt creators registered for at least a day from a 500ns grace period
+        let day = SECONDS_PER_DAY * 1_000_000_000;
+        context.block_timestamp(day);
+        testing_env!(context.build());
+        contract.set_vote_grace_period(500, Some(SECONDS_PER_DAY));
+        contract.add_creator("bob.testnet".parse().unwrap());
+
+        // Alice's event opens immediately, while Bob, who has just registered, gets the grace period
+        contract.add_event(
+            "Art Show".to_string(),
+            200,
+            "By Alice".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.publish_event(0);
+        testing_env!(get_context("bob.testnet".parse().unwrap()).block_timestamp(day).build());
+        contract.add_event(
+            "Book Fair".to_string(),
+            200,
+            "By Bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(contract.get_event(0).votes_open_at, day);
+        assert_eq!(contract.get_my_event(1).votes_open_at, day + 500);
+
+        // Carol can vote on Alice's event in the same block it was created
+        testing_env!(get_context("carol.testnet".parse().unwrap()).block_timestamp(day).build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_total_votes(0), 1);
+    }
+
+    // Unit test to count the events the caller voted for
+    #[test]
+    fn my_vote_count() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract where Bob voted on two of three events
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["carol.testnet"]).build());
+        let both = EventBuilder::new(2).voters(&["carol.testnet", "bob.testnet"]);
+        contract.events.push(both.build());
+
+        // Assert that Bob counts two events and Dave, who never voted, none
+        assert_eq!(contract.my_vote_count(), 2);
+        testing_env!(get_context("dave.testnet".parse().unwrap()).build());
+        assert_eq!(contract.my_vote_count(), 0);
+    }
+
+    // Unit test to skip events that would overshoot their category's cap and approve the next-highest instead
+    #[test]
+    fn finalize_round_category_caps() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract capping music at 500 per round
+        let mut contract = Contract::new(alice);
+        let music = contract.create_template(Template {
+            category: "Music".to_string(),
+            min_budget: 1,
+            max_budget: 1_000,
+            required_tags: vec![],
+        });
+        contract.set_category_cap("Music".to_string(), U128(500));
+
+        // Music events of budget 300, 300 and 200, voted in that order, plus an uncapped event
+        let mut add = |title: &str, budget: u128, template_id: Option<u64>| {
+            let description = "Description".to_string();
+            let title = title.to_string();
+            contract.add_event(title, budget, description, None, None, template_id, None);
+        };
+        add("Jazz Night", 300, Some(music));
+        add("Rock Night", 300, Some(music));
+        add("Folk Night", 200, Some(music));
+        add("Book Fair", 900, None);
+        (0..4).for_each(|id| contract.publish_event(id));
+        let voters = ["a.testnet", "b.testnet", "c.testnet", "d.testnet"];
+        for (id, count) in [(0, 4), (1, 3), (2, 2), (3, 1)] {
+            contract.events[id].votes = voters[..count].iter().map(|v| v.to_string()).collect();
+            contract.events[id].total_votes = count as i64;
+        }
+
+        // Rock Night would bring music to 600, so Folk Night takes its place
+        assert_eq!(contract.finalize_round(3), vec![0, 2, 3]);
+        let capacity = contract.get_category_capacity("Music".to_string());
+        assert_eq!(capacity.cap, Some(U128(500)));
+        assert_eq!(capacity.committed, U128(500));
+        assert_eq!(capacity.remaining, Some(U128(0)));
+        assert_eq!(contract.events[1].approved_in_round, None);
+        assert_eq!(contract.events[2].approved_in_round, Some(1));
+
+        // The next round starts with the full cap, so Rock Night is approved then
+        assert_eq!(contract.finalize_round(3), vec![1]);
+        let capacity = contract.get_category_capacity("Music".to_string());
+        assert_eq!(capacity.committed, U128(300));
+        assert_eq!(capacity.remaining, Some(U128(200)));
+        assert_eq!(contract.get_category_capacity("Art".to_string()).cap, None);
+    }
+
+    // Unit test to warn about, but still add, an event whose budget exceeds its category's cap
+    #[test]
+    fn add_event_over_category_cap_warns() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract capping music at 500 per round
+        let mut contract = Contract::new(alice);
+        let music = contract.create_template(Template {
+            category: "Music".to_string(),
+            min_budget: 1,
+            max_budget: 1_000,
+            required_tags: vec![],
+        });
+        contract.set_category_cap("Music".to_string(), U128(500));
+
+        // Add a music event over the cap
+        let description = "Description".to_string();
+        contract.add_event("Opera".to_string(), 800, description, None, None, Some(music), None);
+
+        // Assert that it was added with a warning naming the cap
+        assert_eq!(contract.list_my_drafts().len(), 1);
+        let warning =
+            r#""event":"category_cap_warning","data":[{"event_id":0,"category":"Music","cap":"500"}]"#;
+        assert!(get_logs().iter().any(|log| log.contains(warning)));
+    }
+
+    // Unit test to initialize the contract with its fees and open events cap given up front
+    #[test]
+    fn init_with_config() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a creation fee, a vote fee and an open events cap
+        let contract = Contract::init_with_config(alice.clone(), U128(50), U128(25), 200);
+
+        // Assert that the owner and the three settings were stored, and that the rest kept their defaults
+        let expected = Config {
+            creation_fee: 50,
+            vote_fee: 25,
+            max_open_events: Some(200),
+            ..Config::default()
+        };
+        assert_eq!(contract.owner, alice);
+        assert_eq!(contract.get_config(), expected);
+        assert_eq!(contract.get_vote_requirements().min_deposit, NearToken::from_yoctonear(25));
+        let estimate = contract.estimate_creation_cost(0, 0, 0);
+        assert_eq!(estimate.creation_fee, U128(50));
+        assert!(contract.list_events().is_empty());
+        assert_eq!(contract.invites_outstanding(), 0);
+    }
+
+    // Unit test that set_config and the setters reject each out-of-bounds value, naming the setting
+    #[test]
+    fn set_config_out_of_bounds() {
+        let mut contract = contract_with_events(0);
+        let message = |error: Box<dyn std::any::Any + Send>| match error.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => error.downcast_ref::<String>().cloned().unwrap_or_default(),
+        };
+
+        // One value past a bound for every setting, applied to otherwise default settings
+        let over: Vec<(&str, fn(&mut Config))> = vec![
+            ("min_budget", |c| {
+                c.min_budget = DEFAULT_MAX_BUDGET + 1;
+                c.max_budget = DEFAULT_MAX_BUDGET + 1;
+            }),
+            ("max_budget", |c| c.max_budget = DEFAULT_MAX_BUDGET + 1),
+            ("max_inline_voters", |c| c.max_inline_voters = 0),
+            ("creation_cooldown_secs", |c| c.creation_cooldown_secs = 365 * SECONDS_PER_DAY),
+            ("loyalty_multiplier", |c| c.loyalty_tiers[0].multiplier = 11),
+            ("max_links_in_description", |c| c.max_links_in_description = 21),
+            ("restore_window_secs", |c| c.restore_window_secs = 91 * SECONDS_PER_DAY),
+            ("vote_fee", |c| c.vote_fee = 10u128.pow(26)),
+            ("vote_grace_period_ns", |c| c.vote_grace_period_ns = u64::MAX),
+            ("grace_exempt_min_age_secs", |c| c.grace_exempt_min_age_secs = Some(u64::MAX)),
+            ("deadline_grace_period_ns", |c| c.deadline_grace_period_ns = u64::MAX),
+            ("min_round_turnout", |c| c.min_round_turnout = 1_000_001),
+            ("min_round_votes", |c| c.min_round_votes = 1_000_001),
+            ("max_open_events", |c| c.max_open_events = Some(0)),
+            ("max_deadline_extension_secs", |c| c.max_deadline_extension_secs = u64::MAX),
+            ("heartbeat_interval", |c| c.heartbeat_interval = 1_000_001),
+            ("max_voting_period_secs", |c| c.max_voting_period_secs = 60),
+            ("creation_fee", |c| c.creation_fee = 10u128.pow(26)),
+        ];
+        assert_eq!(over.len(), contract.get_param_bounds().len());
+        for (name, apply) in over {
+            let mut config = Config::default();
+            apply(&mut config);
+            let set = std::panic::AssertUnwindSafe(|| contract.set_config(config));
+            let error = message(std::panic::catch_unwind(set).unwrap_err());
+            assert!(error.starts_with(&format!("{} must be between", name)), "{}", error);
+        }
+
+        // Assert that the dedicated setters enforce the same bounds
+        let cooldown = std::panic::AssertUnwindSafe(|| contract.set_creation_cooldown(u64::MAX));
+        let error = message(std::panic::catch_unwind(cooldown).unwrap_err());
+        assert_eq!(error, "creation_cooldown_secs must be between 0 and 604800");
+        let fee = std::panic::AssertUnwindSafe(|| contract.set_vote_fee(U128(u128::MAX)));
+        let error = message(std::panic::catch_unwind(fee).unwrap_err());
+        assert!(error.starts_with("vote_fee must be between"), "{}", error);
+
+        // Assert that nothing was changed and the defaults themselves are accepted
+        assert_eq!(contract.get_config(), Config::default());
+        contract.set_config(Config::default());
+    }
+
+    // Unit test for the bounds view reporting each setting's default within its bounds
+    #[test]
+    fn get_param_bounds() {
+        let contract = contract_with_events(0);
+        let bounds = contract.get_param_bounds();
+        let cooldown = bounds.iter().find(|b| b.name == "creation_cooldown_secs").unwrap();
+        assert_eq!(cooldown.default, Some(U128(0)));
+        assert_eq!(cooldown.max, U128(7 * SECONDS_PER_DAY as u128));
+        for bound in bounds {
+            assert!(bound.min.0 <= bound.max.0, "{}", bound.name);
+            if let Some(default) = bound.default {
+                assert!((bound.min.0..=bound.max.0).contains(&default.0), "{}", bound.name);
+            }
+        }
+    }
+
+    // Unit test to block creation at the open events cap until an event closes or is approved
+    #[test]
+    fn max_open_events() {
+        // Create a contract with two events by Alice, capped at two open events, the first closing at 100
+        let mut contract = contract_with_events(2);
+        contract.set_max_open_events(Some(2));
+        contract.set_voting_deadline(0, 100);
+        assert_eq!(contract.get_config().max_open_events, Some(2));
+
+        // Assert that a third event is rejected with the current count
+        let check = |contract: &Contract| {
+            let title = "Event 2".to_string();
+            contract.dry_run_add_event(account(ALICE), title, U128(100), None).violations
+        };
+        assert_eq!(check(&contract), vec![CreateError::TooManyOpenEvents { open: 2, max: 2 }]);
+
+        // Once the first event's deadline passes, it frees its slot and creation works again
+        ctx(ALICE, 0, 100);
+        assert!(check(&contract).is_empty());
+        contract.add_event("Event 2".to_string(), 100, "Third".to_string(), None, None, None, None);
+
+        // The new draft takes the slot, until the second event is approved in a round
+        assert_eq!(check(&contract), vec![CreateError::TooManyOpenEvents { open: 2, max: 2 }]);
+        ctx(BOB, 0, 100);
+        contract.add_vote(1, None);
+        ctx(ALICE, 0, 100);
+        assert_eq!(contract.finalize_round(1), vec![1]);
+        assert!(check(&contract).is_empty());
+    }
+
+    // Unit test to reject an event over the open events cap, naming the current count
+    #[test]
+    #[should_panic(expected = "Too many open events: 2 of at most 2")]
+    fn max_open_events_reached() {
+        let mut contract = contract_with_events(2);
+        contract.set_max_open_events(Some(2));
+        contract.add_event("Event 2".to_string(), 100, "Third".to_string(), None, None, None, None);
+    }
+
+    // Unit test to reject restoring an event over the open events cap
+    #[test]
+    #[should_panic(expected = "Too many open events: 1 of at most 1")]
+    fn max_open_events_restore() {
+        let mut contract = contract_with_events(2);
+        contract.delete_event(0);
+        contract.set_max_open_events(Some(1));
+        contract.restore_event(0);
+    }
+
+    // Unit test to reject settings the owner setters would reject
+    #[test]
+    #[should_panic(expected = "max_open_events must be between 1 and 100000")]
+    fn init_with_config_invalid() {
         // Set the account ID for testing as Alice
         let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
 
-        // Set up the testing context and unit test environment
-        let context = get_context(alice.clone());
+        // Create a new instance of the contract allowing no open events at all
+        Contract::init_with_config(alice, U128(0), U128(0), 0);
+    }
+
+    // Unit test for add_event taking exactly the creation fee and booking it to the platform
+    #[test]
+    fn add_event_creation_fee() {
+        let mut contract = contract_with_events(0);
+        contract.set_config(Config {
+            creation_fee: 50,
+            ..Config::default()
+        });
+        let add = |contract: &mut Contract, deposit: u128| {
+            ctx(ALICE, deposit, 0);
+            let add = std::panic::AssertUnwindSafe(|| {
+                let (title, description) = ("Art Show".to_string(), "Paintings".to_string());
+                contract.add_event(title, 100, description, None, None, None, None)
+            });
+            std::panic::catch_unwind(add).map_err(|e| match e.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => e.downcast_ref::<String>().cloned().unwrap_or_default(),
+            })
+        };
+
+        // Assert that too little or too much is rejected, and the exact fee is kept
+        let error = Err("Attach exactly the creation fee of 50 yoctoNEAR".to_string());
+        assert_eq!(add(&mut contract, 49), error);
+        assert_eq!(add(&mut contract, 51), error);
+        assert_eq!(add(&mut contract, 50), Ok(()));
+        assert_eq!(contract.events.len(), 1);
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::Fees), I128(50));
+    }
+
+    // Unit test that attaching the deposit estimate_creation_cost reports is accepted by add_event
+    #[test]
+    fn add_event_with_estimated_deposit() {
+        let mut contract = contract_with_events(0);
+        contract.set_config(Config {
+            creation_fee: 50,
+            ..Config::default()
+        });
+        let (title, description) = ("Art Show".to_string(), "Paintings".to_string());
+        let estimate =
+            contract.estimate_creation_cost(title.len() as u32, description.len() as u32, 0);
+        assert_eq!(estimate.total_deposit, U128(50));
+
+        // Assert that the event is added with the estimated deposit attached
+        ctx(ALICE, estimate.total_deposit.0, 0);
+        contract.add_event(title, 100, description, None, None, None, None);
+        assert_eq!(contract.events.len(), 1);
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::Fees), I128(50));
+    }
+
+    // Scenario test for the auditor certifying an approval, which then lets the owner mark it funded
+    #[test]
+    fn certify_result() {
+        // Alice appoints an auditor, and event 0 wins the round with Bob's vote
+        let auditor = "auditor.testnet";
+        let mut contract = Scenario::new().events(2).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account(auditor)));
+        assert_eq!(contract.finalize_round(1), vec![0]);
+        assert_eq!(contract.get_event(0).certification, Some(Certification::Pending));
+        assert_eq!(contract.get_event(1).certification, None);
+
+        // The auditor certifies the result
+        ctx(auditor, 0, 0);
+        contract.certify_result(0);
+        assert_eq!(contract.get_event(0).certification, Some(Certification::Certified));
+        assert_eq!(contract.get_auditor(), Some(account(auditor)));
+
+        // Assert that the owner can now release the funds, and remove the auditor as nothing is pending
+        ctx(ALICE, 0, 0);
+        contract.mark_funded(0);
+        assert!(contract.events[0].funded);
+        contract.set_auditor(None);
+        assert_eq!(contract.get_auditor(), None);
+    }
+
+    // Scenario test for the auditor rejecting an approval, which stays out of later rounds
+    #[test]
+    fn reject_result() {
+        let auditor = "auditor.testnet";
+        let mut contract = Scenario::new().events(2).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account(auditor)));
+        contract.finalize_round(1);
+
+        // The auditor rejects the result with a reason
+        ctx(auditor, 0, 0);
+        contract.reject_result(0, "  Budget not itemized ".to_string());
+        let rejected = Certification::Rejected {
+            reason: "Budget not itemized".to_string(),
+        };
+        assert_eq!(contract.get_event(0).certification, Some(rejected));
+
+        // Assert that the next round only considers event 1
+        ctx(ALICE, 0, 0);
+        assert_eq!(contract.finalize_round(1), vec![1]);
+    }
+
+    // Scenario test to block releasing funds for an approval the auditor has not certified yet
+    #[test]
+    #[should_panic(expected = "Event result is not certified")]
+    fn mark_funded_before_certification() {
+        let mut contract = Scenario::new().events(1).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account("auditor.testnet")));
+        contract.finalize_round(1);
+        contract.mark_funded(0);
+    }
+
+    // Scenario test to keep the auditor while a result waits for certification, so it cannot get stuck
+    #[test]
+    #[should_panic(expected = "Cannot remove the auditor while 1 results are pending")]
+    fn remove_auditor_with_pending_certification() {
+        let mut contract = Scenario::new().events(1).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account("auditor.testnet")));
+        contract.finalize_round(1);
+        contract.set_auditor(None);
+    }
+
+    // Scenario test for a new auditor taking over the results still pending certification
+    #[test]
+    fn replace_auditor_with_pending_certification() {
+        let mut contract = Scenario::new().events(1).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account("auditor.testnet")));
+        contract.finalize_round(1);
+        contract.set_auditor(Some(account(CAROL)));
+
+        // Assert that the new auditor decides the pending result
+        ctx(CAROL, 0, 0);
+        contract.certify_result(0);
+        let certification = contract.get_event(0).certification;
+        assert_eq!(certification, Some(Certification::Certified));
+    }
+
+    // Scenario test to let only the auditor decide on results
+    #[test]
+    #[should_panic(expected = "Only the auditor can certify or reject results")]
+    fn certify_result_not_auditor() {
+        let mut contract = Scenario::new().events(1).vote(BOB, 0).contract();
+        contract.set_auditor(Some(account("auditor.testnet")));
+        contract.finalize_round(1);
+        contract.certify_result(0);
+    }
+
+    // Unit test for a two-step ownership transfer, pending until the proposed owner accepts
+    #[test]
+    fn ownership_transfer() {
+        let mut contract = contract_with_events(0);
+        assert_eq!(contract.get_pending_owner(), None);
+
+        // Assert that the proposal is pending and Alice stays the owner until Bob accepts
+        contract.propose_ownership_transfer(account(BOB));
+        assert_eq!(contract.get_pending_owner(), Some(account(BOB)));
+        assert_eq!(contract.owner, account(ALICE));
+
+        // Assert that accepting makes Bob the owner and clears the proposal
+        ctx(BOB, 0, 0);
+        contract.accept_ownership();
+        assert_eq!(contract.owner, account(BOB));
+        assert_eq!(contract.get_pending_owner(), None);
+    }
+
+    // Unit test that only the owner can propose an ownership transfer
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn propose_ownership_transfer_not_owner() {
+        let mut contract = contract_with_events(0);
+        ctx(BOB, 0, 0);
+        contract.propose_ownership_transfer(account(BOB));
+    }
+
+    // Unit test that the previous owner loses owner-only methods once the transfer is accepted
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn ownership_transfer_revokes_previous_owner() {
+        let mut contract = contract_with_events(0);
+        contract.propose_ownership_transfer(account(BOB));
+        ctx(BOB, 0, 0);
+        contract.accept_ownership();
+        contract.set_vote_fee(U128(5));
+
+        // Alice is no longer the owner
+        ctx(ALICE, 0, 0);
+        contract.set_vote_fee(U128(10));
+    }
+
+    // Unit test to reject accepting ownership by an account that was not proposed
+    #[test]
+    #[should_panic(expected = "Only the proposed owner can accept ownership")]
+    fn accept_ownership_not_proposed() {
+        let mut contract = contract_with_events(0);
+        contract.propose_ownership_transfer(account(BOB));
+        ctx(CAROL, 0, 0);
+        contract.accept_ownership();
+    }
+
+    // Unit test to archive each finalized round and link events back to it
+    #[test]
+    fn round_history() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.block_timestamp(1_000).build());
+
+        // Create a new instance of the contract with three events
+        let mut contract = Contract::new(alice);
+        contract.events.push(
+            EventBuilder::new(0)
+                .title("Jazz Night")
+                .budget(100)
+                .voters(&["a.testnet", "b.testnet"])
+                .build(),
+        );
+        contract.events.push(
+            EventBuilder::new(1)
+                .title("Rock Night")
+                .budget(200)
+                .voters(&["a.testnet"])
+                .build(),
+        );
+        contract.events.push(
+            EventBuilder::new(2)
+                .title("Folk Night")
+                .budget(300)
+                .build(),
+        );
+
+        // Finalize a first round approving only the most voted event
+        testing_env!(context.block_timestamp(5_000).build());
+        assert_eq!(contract.finalize_round(1), vec![0]);
+
+        // Finalize a second round among the events left
+        testing_env!(context.block_timestamp(9_000).build());
+        assert_eq!(contract.finalize_round(1), vec![1]);
+
+        // Assert that both rounds were archived with their competitors, winners and totals
+        let first = RoundRecord {
+            round: 1,
+            started_at: 1_000,
+            ended_at: 5_000,
+            event_ids: vec![0, 1, 2],
+            winner_ids: vec![0],
+            total_votes: 3,
+            total_paid: U128(100),
+            tie_break: TieBreak::LowestId,
+            forced_reason: None,
+        };
+        let second = RoundRecord {
+            round: 2,
+            started_at: 5_000,
+            ended_at: 9_000,
+            event_ids: vec![1, 2],
+            winner_ids: vec![1],
+            total_votes: 1,
+            total_paid: U128(200),
+            tie_break: TieBreak::LowestId,
+            forced_reason: None,
+        };
+        assert_eq!(contract.get_round(1), Some(first.clone()));
+        assert_eq!(contract.get_round(2), Some(second.clone()));
+        assert_eq!(contract.get_round(3), None);
+        assert_eq!(contract.list_rounds(0, 10), vec![first, second.clone()]);
+        assert_eq!(contract.list_rounds(2, 1), vec![second]);
+
+        // Assert that each event links to the latest round it competed in
+        assert_eq!(contract.get_event(0).round, Some(1));
+        assert_eq!(contract.get_event(1).round, Some(2));
+        assert_eq!(contract.get_event(2).round, Some(2));
+    }
+
+    // Unit test to list a creator's events carrying a tag
+    #[test]
+    fn events_by_creator_and_tag() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with events by two creators
+        let mut contract = Contract::new(alice.clone());
+        let art_by_alice = EventBuilder::new(0).creator("alice.testnet").tags(&["art"]).build();
+        let music_by_alice = EventBuilder::new(1).creator("alice.testnet").tags(&["music"]).build();
+        let art_by_bob = EventBuilder::new(2).creator("bob.testnet").tags(&["art"]).build();
+        contract.events = vec![art_by_alice.clone(), music_by_alice, art_by_bob];
+
+        // Assert that only events matching both the creator and the tag are returned
+        assert_eq!(
+            contract.events_by_creator_and_tag(alice.clone(), "art".to_string()),
+            vec![art_by_alice]
+        );
+
+        // Assert that nothing is returned when the creator never used the tag
+        assert!(contract.events_by_creator_and_tag(alice, "food".to_string()).is_empty());
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        assert!(contract.events_by_creator_and_tag(bob, "music".to_string()).is_empty());
+    }
+
+    // Unit test to book a donation, a vote fee and their refunds in the ledger
+    #[test]
+    fn ledger_tracks_money_flow() {
+        // Set the account ID for testing as Alice, who owns the contract and charges a vote fee
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+        let mut contract = Contract::new(alice.clone());
+        contract.set_vote_fee(U128(10));
+        contract.events.push(EventBuilder::new(0).budget(1_000).build());
+
+        // Assert that the contract holds exactly what the internal accounts add up to
+        let assert_balances = |contract: &Contract, funding: i128, escrow: i128| {
+            assert_eq!(contract.get_ledger_balance(LedgerAccount::EventFunding(0)), I128(funding));
+            assert_eq!(contract.get_ledger_balance(LedgerAccount::Escrow(0)), I128(escrow));
+            assert_eq!(
+                contract.get_ledger_balance(LedgerAccount::External),
+                I128(-(funding + escrow))
+            );
+        };
+
+        // Report every transfer sent so far as arrived
+        let settle = |contract: &mut Contract| {
+            for transfer in contract.list_pending_transfers() {
+                contract.on_transfer_complete(transfer.id, transfer.attempt, Ok(()));
+            }
+        };
+
+        // Bob donates 100 to the event
+        let mut bob = get_context("bob.testnet".parse().unwrap());
+        bob.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(bob.build());
+        contract.donate(0);
+        assert_balances(&contract, 100, 0);
+
+        // Carol votes with a 15 deposit, so the 10 fee is held in escrow and the rest sent back
+        let mut carol = get_context("carol.testnet".parse().unwrap());
+        carol.attached_deposit(NearToken::from_yoctonear(15));
+        testing_env!(carol.build());
+        contract.add_vote(0, None);
+        settle(&mut contract);
+        assert_balances(&contract, 100, 10);
+
+        // Carol removes the vote and the fee is refunded out of escrow
+        testing_env!(get_context("carol.testnet".parse().unwrap()).build());
+        contract.remove_vote(0);
+        settle(&mut contract);
+        assert_balances(&contract, 100, 0);
+
+        // The owner deletes the event and, once it is purged, Bob's donation is sent back
+        let mut owner = get_context(alice);
+        testing_env!(owner.build());
+        contract.delete_event(0);
+        owner.block_timestamp(DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000);
+        testing_env!(owner.build());
+        contract.process_refunds(0, 10);
+        assert_eq!(contract.purge_deleted(1), 1);
+        settle(&mut contract);
+        assert_balances(&contract, 0, 0);
+
+        // Assert that every movement was booked once, in order, each refund passing through PendingRefund
+        use LedgerAccount::{Escrow, EventFunding, External, PendingRefund};
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        let entries: Vec<(LedgerAccount, LedgerAccount, u128, String)> = contract
+            .get_ledger_entries(0, 10)
+            .into_iter()
+            .map(|entry| (entry.from, entry.to, entry.amount.0, entry.memo))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (External, EventFunding(0), 100, "donation".to_string()),
+                (External, Escrow(0), 10, "vote_fee".to_string()),
+                (External, PendingRefund(0, carol.clone()), 5, "vote_overpayment".to_string()),
+                (PendingRefund(0, carol.clone()), External, 5, "vote_overpayment".to_string()),
+                (Escrow(0), PendingRefund(0, carol.clone()), 10, "vote_refund".to_string()),
+                (PendingRefund(0, carol), External, 10, "vote_refund".to_string()),
+                (
+                    EventFunding(0),
+                    PendingRefund(0, bob.clone()),
+                    100,
+                    "donation_refund".to_string(),
+                ),
+                (PendingRefund(0, bob), External, 100, "donation_refund".to_string()),
+            ]
+        );
+        assert_eq!(contract.get_ledger_entries(3, 10).len(), 5);
+        assert!(contract.list_pending_transfers().is_empty());
+    }
+
+    // Unit test to snapshot the vote standings at the current block time
+    #[test]
+    fn snapshot_standings() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.block_timestamp(42_000).build());
+
+        // Create a new instance of the contract with three events, one of them deleted
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).voters(&["a.testnet", "b.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["a.testnet"]).build());
+        contract.events.push(EventBuilder::new(2).build());
+        contract.delete_event(1);
+
+        // Assert that the snapshot is taken at the block time and matches the current tallies
+        assert_eq!(contract.snapshot_standings(), (42_000, vec![(0, 2), (2, 0)]));
+        assert_eq!(contract.snapshot_standings().1[0].1, contract.get_total_votes(0));
+    }
+
+    // Unit test to report the storage a removed vote releases without paying it out
+    #[test]
+    fn remove_vote_reports_freed_storage() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob).build());
+
+        // Create a new instance of the contract with a fixture event and vote for it
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+
+        // Remove the vote, measuring the records and the root state around it
+        let before = env::storage_usage() + contract.serialized_size();
+        contract.remove_vote(0);
+        let freed = before - env::storage_usage() - contract.serialized_size();
+
+        // Assert that the log reports exactly the bytes released, voter entry included, and nothing was sent
+        assert!(freed > 4 + "bob.testnet".len() as u64);
+        let expected = format!(r#""storage_freed_bytes":{}"#, freed);
+        assert!(get_logs().iter().any(|log| log.contains(&expected)));
+        assert!(get_created_receipts().is_empty());
+    }
+
+    // Unit test to treat legacy voter strings with odd casing as the account they name
+    #[test]
+    fn legacy_voter_strings_are_normalized() {
+        // Set the account ID for testing as Bob
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        testing_env!(get_context(bob.clone()).build());
+
+        // Create a new instance of the contract with legacy entries naming Bob in other forms
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).voters(&["Bob.Testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet", " BOB.testnet"]).build());
+
+        // Assert that Bob cannot vote again and that duplicates count once
+        assert_eq!(contract.try_add_vote(0), Err(VoteError::AlreadyVoted.message()));
+        assert_eq!(contract.unique_votes(1), 1);
+        assert_eq!(contract.my_vote_count(), 2);
+
+        // Assert that Bob can still remove the legacy vote
+        contract.remove_vote(0);
+        assert!(contract.list_events()[0].votes.is_empty());
+    }
+
+    // Unit test to reconcile a refund whose callback never arrived and let the recipient claim it again
+    #[test]
+    fn reconcile_stuck_transfer() {
+        // Set the account ID for testing as Bob, who pays a fee to vote and then removes the vote
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(10));
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+        testing_env!(get_context(bob.clone()).build());
+        contract.remove_vote(0);
+
+        // The refund is pending and its amount is held as owed to Bob
+        let owed = LedgerAccount::PendingRefund(0, bob.clone());
+        let pending = contract.list_pending_transfers();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].to, bob);
+        assert_eq!(pending[0].amount, U128(10));
+        assert_eq!(pending[0].status, TransferStatus::Pending);
+        assert_eq!(contract.get_ledger_balance(owed.clone()), I128(10));
+
+        // The callback never arrives; once the timelock has passed the owner marks the transfer failed
+        let mut owner = get_context("alice.testnet".parse().unwrap());
+        owner.block_timestamp(TRANSFER_RECONCILE_DELAY_SECS * 1_000_000_000);
+        testing_env!(owner.build());
+        contract.reconcile_transfer(0, TransferStatus::Failed);
+
+        // Assert that the amount is still owed to Bob and the transfer stays listed as failed
+        assert_eq!(contract.get_ledger_balance(owed.clone()), I128(10));
+        assert_eq!(contract.list_pending_transfers()[0].status, TransferStatus::Failed);
+
+        // A late callback for the abandoned attempt changes nothing
+        contract.on_transfer_complete(0, 1, Ok(()));
+        assert_eq!(contract.list_pending_transfers()[0].status, TransferStatus::Failed);
+
+        // Bob claims the refund again, and this time the callback reports success
+        testing_env!(get_context(bob.clone()).build());
+        contract.retry_transfer(0);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, bob);
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(10)
+        ));
+        contract.on_transfer_complete(0, 2, Ok(()));
+
+        // Assert that the refund left the ledger and nothing is pending anymore
+        assert_eq!(contract.get_ledger_balance(owed), I128(0));
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::External), I128(0));
+        assert!(contract.list_pending_transfers().is_empty());
+    }
+
+    // Unit test to keep the owner from reconciling a transfer before its timelock has passed
+    #[test]
+    #[should_panic(expected = "Transfer can be reconciled from")]
+    fn reconcile_transfer_too_early() {
+        // Set the account ID for testing as Bob, who pays a fee to vote and then removes the vote
+        let bob: AccountId = "bob.testnet".parse().unwrap();
+        let mut context = get_context(bob.clone());
+        context.attached_deposit(NearToken::from_yoctonear(10));
         testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).build());
+        contract.add_vote(0, None);
+        testing_env!(get_context(bob).build());
+        contract.remove_vote(0);
 
-        // Create a new instance of the contract with Alice as the owner
+        // Try to reconcile it right away as the owner
+        testing_env!(get_context("alice.testnet".parse().unwrap()).build());
+        contract.reconcile_transfer(0, TransferStatus::Failed);
+    }
+
+    // Unit test to find the event whose deadline passed longest ago
+    #[test]
+    fn most_overdue_event() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.block_timestamp(1_000).build());
+
+        // Create a new instance of the contract with no deadlines yet
         let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        assert_eq!(contract.most_overdue_event(), None);
 
-        // Add a new event to the contract
-        contract.add_event(
-            "New Contemporary Art Show".to_string(),
-            200,
-            "Amazing selection of international artists from all over the world".to_string(),
+        // Add expired events, one of them deleted, and one still running
+        contract.events.push(EventBuilder::new(1).deadline(600).build());
+        contract.events.push(EventBuilder::new(2).deadline(300).build());
+        contract.events.push(EventBuilder::new(3).deadline(100).build());
+        contract.events.push(EventBuilder::new(4).deadline(5_000).build());
+        contract.delete_event(3);
+
+        // Assert that the visible event with the earliest past deadline is returned
+        assert_eq!(contract.most_overdue_event(), Some(contract.events[2].clone()));
+    }
+
+    // Unit test to refund the donors of a deleted event in batches, with one refund failing
+    #[test]
+    fn process_refunds_in_batches() {
+        // Set the account ID for testing as Alice, who owns the contract
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+        let mut contract = Contract::new(alice.clone());
+        contract.events.push(EventBuilder::new(0).budget(1_000).build());
+
+        // Three donors give 100 each
+        let donors = ["bob.testnet", "carol.testnet", "dave.testnet"];
+        for donor in donors {
+            let mut context = get_context(donor.parse().unwrap());
+            context.attached_deposit(NearToken::from_yoctonear(100));
+            testing_env!(context.build());
+            contract.donate(0);
+        }
+
+        // The owner cancels the event and, after the restore window, anyone refunds two donors at a time
+        testing_env!(get_context(alice).build());
+        contract.delete_event(0);
+        let mut eve = get_context("eve.testnet".parse().unwrap());
+        eve.block_timestamp(DEFAULT_RESTORE_WINDOW_SECS * 1_000_000_000);
+        testing_env!(eve.build());
+        assert_eq!(contract.process_refunds(0, 2), 2);
+        assert_eq!(contract.refund_progress(0).sent, 2);
+        assert_eq!(contract.list_pending_transfers().len(), 2);
+
+        // The next call resumes after the cursor and a further one has nothing left to send
+        assert_eq!(contract.process_refunds(0, 2), 1);
+        assert_eq!(contract.process_refunds(0, 2), 0);
+        let pending = contract.list_pending_transfers();
+        let recipients: Vec<String> = pending.iter().map(|t| t.to.to_string()).collect();
+        assert_eq!(recipients, donors);
+        assert_eq!(contract.events[0].funds_raised, 0);
+
+        // The first two refunds arrive, but Dave's fails
+        contract.on_transfer_complete(pending[0].id, 1, Ok(()));
+        contract.on_transfer_complete(pending[1].id, 1, Ok(()));
+        contract.on_transfer_complete(pending[2].id, 1, Err(PromiseError::Failed));
+        assert_eq!(
+            contract.refund_progress(0),
+            RefundProgress {
+                event_id: 0,
+                donor_count: 3,
+                sent: 3,
+                settled: 2,
+                outstanding: 1,
+            }
         );
 
-        // Get the count of events after adding a new event
-        let result = contract.event_count();
+        // Assert that Dave's refund stays owed and Dave can claim it again
+        let dave: AccountId = "dave.testnet".parse().unwrap();
+        let owed = LedgerAccount::PendingRefund(0, dave.clone());
+        assert_eq!(contract.get_ledger_balance(owed.clone()), I128(100));
+        assert_eq!(contract.list_pending_transfers()[0].status, TransferStatus::Failed);
+        testing_env!(get_context(dave).build());
+        contract.retry_transfer(pending[2].id);
+        contract.on_transfer_complete(pending[2].id, 2, Ok(()));
+        assert_eq!(contract.get_ledger_balance(owed), I128(0));
+        assert_eq!(contract.refund_progress(0).outstanding, 0);
+    }
 
-        // Assert that the count of events is equal to 1
-        assert_eq!(result, 1);
+    // Unit test to refuse batched refunds for an event that was not deleted
+    #[test]
+    #[should_panic(expected = "Refunds are only processed for deleted events")]
+    fn process_refunds_open_event() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with an open event and try to refund it
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).build());
+        contract.process_refunds(0, 10);
     }
 
-    // Unit test to add a voter
+    // Unit test to refuse refunds inside the restore window, so a third party cannot make a deletion permanent
     #[test]
-    fn add_voter() {
+    #[should_panic(expected = "Refunds start once the restore window has expired")]
+    fn process_refunds_within_window() {
+        let mut contract = Scenario::new().create(BOB, "Art Show", 100, None).contract();
+        contract.delete_event(0);
+        ctx(CAROL, 0, (DEFAULT_RESTORE_WINDOW_SECS - 1) * 1_000_000_000);
+        contract.process_refunds(0, 1);
+    }
+
+    // Unit test to measure the serialized state growing with the events
+    #[test]
+    fn serialized_size_grows() {
         // Set the account ID for testing as Alice
         let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
 
-        // Set up the testing context and unit test environment
-        let context = get_context(alice.clone());
+        // Create a new instance of the contract and measure it empty
+        let mut contract = Contract::new(alice);
+        let empty = contract.serialized_size();
+        assert!(empty > 0);
+
+        // Assert that each added event makes the state bigger
+        contract.events.push(EventBuilder::new(0).build());
+        let one = contract.serialized_size();
+        assert!(one > empty);
+        contract.events.push(EventBuilder::new(1).voters(&["bob.testnet"]).build());
+        assert!(contract.serialized_size() > one);
+    }
+
+    // Unit test to keep estimate_creation_cost in line with what a worst-case add_event really stores
+    #[test]
+    fn creation_cost_estimate_matches_storage() {
+        // Set the account ID for testing as Alice, the owner
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with a template and a creator of the longest account ID
+        let mut contract = Contract::new(alice);
+        let art = contract.create_template(Template {
+            category: "Art".to_string(),
+            min_budget: 1,
+            max_budget: 1_000,
+            required_tags: vec![],
+        });
+        let creator: AccountId = format!("{}.testnet", "a".repeat(56)).parse().unwrap();
+        assert_eq!(creator.len() as u64, MAX_ACCOUNT_ID_BYTES);
+        contract.add_creator(creator.clone());
+
+        // Alice adds an event first, so the creator's title links a new bucket into a non-empty prefix index
+        let (title, description) = ("Book Fair".to_string(), "Description".to_string());
+        contract.add_event(title, 200, description, None, None, None, None);
+
+        // The creator adds a first event with multi-byte text at the length limits and every option
+        testing_env!(get_context(creator).build());
+        let title = "🎉".repeat(MAX_TITLE_CHARS);
+        let description = "展".repeat(MAX_DESCRIPTION_CHARS);
+        let url = format!("https://{}", "a".repeat(MAX_PROPOSAL_URL_CHARS - 9));
+        let options = CREATION_OPTION_PROPOSAL_URL | CREATION_OPTION_FUNDING_THRESHOLD;
+        let options = options | CREATION_OPTION_TEMPLATE;
+        let estimate =
+            contract.estimate_creation_cost(title.len() as u32, description.len() as u32, options);
+        let state_before = contract.serialized_size();
+        let storage_before = env::storage_usage();
+        contract.add_event(title, 200, description, Some(url), Some(1), Some(art), None);
+        let state_added = contract.serialized_size() - state_before;
+        let actual = state_added + env::storage_usage() - storage_before;
+
+        // Assert that the estimate covers the real bytes without overshooting by more than a few
+        let estimated = estimate.storage_bytes.0;
+        assert!(estimated >= actual, "estimated {} < actual {}", estimated, actual);
+        assert!(estimated - actual <= 8, "estimated {} vs actual {}", estimated, actual);
+
+        // Assert that the bytes are priced, but the deposit to attach is only the creation fee
+        let byte_cost = env::storage_byte_cost().as_yoctonear();
+        assert_eq!(estimate.storage_cost, U128(byte_cost * estimated as u128));
+        assert_eq!(estimate.creation_fee, U128(0));
+        assert_eq!(estimate.total_deposit, U128(0));
+    }
+
+    // Unit test to reject option flags estimate_creation_cost does not know
+    #[test]
+    #[should_panic(expected = "Unknown creation options")]
+    fn creation_cost_unknown_option() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Ask for an estimate with a flag beyond the known ones
+        Contract::new(alice).estimate_creation_cost(10, 10, 8);
+    }
+
+    // Unit test to match donations up to the sponsor's cap and let the sponsor reclaim the rest
+    #[test]
+    fn donation_match_up_to_cap() {
+        // Set the account ID for testing as Alice, who owns the contract
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice).build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).budget(10_000).deadline(1_000).build());
+
+        // Sam the sponsor escrows a match capped at 150
+        let sam: AccountId = "sam.testnet".parse().unwrap();
+        let mut context = get_context(sam.clone());
+        context.attached_deposit(NearToken::from_yoctonear(150));
+        testing_env!(context.build());
+        contract.create_match(0, U128(150));
+
+        // Bob's first donation is matched in full
+        let mut bob = get_context("bob.testnet".parse().unwrap());
+        bob.attached_deposit(NearToken::from_yoctonear(100));
+        testing_env!(bob.build());
+        contract.donate(0);
+        assert!(get_logs().last().unwrap().contains(r#""matched":"100","matched_by":"sam.testnet""#));
+        assert_eq!(contract.events[0].funds_raised, 200);
+
+        // Bob's second donation only gets the 50 left under the cap, and a third gets nothing
+        contract.donate(0);
+        assert!(get_logs().last().unwrap().contains(r#""matched":"50","matched_by":"sam.testnet""#));
+        contract.donate(0);
+        assert!(get_logs().last().unwrap().contains(r#""matched":"0","matched_by":null"#));
+        assert_eq!(contract.events[0].funds_raised, 450);
+        assert_eq!(contract.get_match(0).unwrap().remaining, U128(0));
+
+        // Assert that the match is credited to Sam, so a refund would return it to the sponsor
+        assert_eq!(contract.donations.get(&(0, sam.clone())), Some(150));
+        assert_eq!(contract.events[0].donors, vec!["bob.testnet", "sam.testnet"]);
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::MatchEscrow(0)), I128(0));
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::EventFunding(0)), I128(450));
+    }
+
+    // Unit test for the sponsor reclaiming the unused match once the event is closed
+    #[test]
+    fn reclaim_unused_match() {
+        // Set the account ID for testing as Sam, a sponsor escrowing a match capped at 150
+        let sam: AccountId = "sam.testnet".parse().unwrap();
+        let mut context = get_context(sam.clone());
+        context.attached_deposit(NearToken::from_yoctonear(150));
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).budget(10_000).deadline(1_000).build());
+        contract.create_match(0, U128(150));
+
+        // Bob donates 40, drawing 40 of the match
+        let mut bob = get_context("bob.testnet".parse().unwrap());
+        bob.attached_deposit(NearToken::from_yoctonear(40));
+        testing_env!(bob.build());
+        contract.donate(0);
+
+        // Once the deadline has passed, Sam reclaims the remaining 110
+        context.attached_deposit(NearToken::from_yoctonear(0));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+        contract.reclaim_match(0);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, sam);
+        assert!(matches!(
+            receipts[0].actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(110)
+        ));
+        assert_eq!(contract.get_match(0).unwrap().remaining, U128(0));
+        assert_eq!(contract.get_ledger_balance(LedgerAccount::MatchEscrow(0)), I128(0));
+    }
+
+    // Unit test to keep the sponsor from reclaiming the match while the event still takes donations
+    #[test]
+    #[should_panic(expected = "Event is still open")]
+    fn reclaim_match_while_open() {
+        // Set the account ID for testing as Sam, a sponsor escrowing a match
+        let sam: AccountId = "sam.testnet".parse().unwrap();
+        let mut context = get_context(sam);
+        context.attached_deposit(NearToken::from_yoctonear(150));
+        testing_env!(context.build());
+        let mut contract = Contract::new("alice.testnet".parse().unwrap());
+        contract.events.push(EventBuilder::new(0).deadline(1_000).build());
+        contract.create_match(0, U128(150));
+
+        // Try to reclaim it before the deadline
+        context.attached_deposit(NearToken::from_yoctonear(0));
         testing_env!(context.build());
+        contract.reclaim_match(0);
+    }
 
-        // Create a new instance of the contract with Alice as the owner
+    // Unit test to accept votes shortly after the deadline, within the grace period only
+    #[test]
+    fn deadline_grace_period() {
+        // Set the account ID for testing as Alice, the owner, allowing 500 ns after each deadline
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
         let mut contract = Contract::new(alice);
+        contract.set_deadline_grace_period(500);
+        contract.events.push(EventBuilder::new(0).deadline(1_000).build());
 
-        // Add a new event to the contract
-        contract.add_event(
-            "New Contemporary Art Show".to_string(),
-            200,
-            "Amazing selection of international artists from all over the world".to_string(),
+        // Assert that a vote just after the deadline is still accepted
+        let mut bob = get_context("bob.testnet".parse().unwrap());
+        testing_env!(bob.block_timestamp(1_499).build());
+        contract.add_vote(0, None);
+        assert_eq!(contract.get_total_votes(0), 1);
+
+        // Assert that a vote once the grace period is over is rejected
+        let mut carol = get_context("carol.testnet".parse().unwrap());
+        testing_env!(carol.block_timestamp(1_500).build());
+        assert_eq!(
+            contract.try_add_vote(0),
+            Err(VoteError::VotingClosed.message())
         );
+    }
+
+    // Unit test to break exact ties in finalize_round with each strategy
+    #[test]
+    fn finalize_round_tie_breaks() {
+        // Set the account ID for testing as Alice, with a fixed random seed
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        let mut context = get_context(alice.clone());
+        testing_env!(context.random_seed([7; 32]).build());
 
-        // Add a vote for the first event
-        contract.add_vote(0);
+        // Three events with one vote each: event 1 is the oldest, event 2 has the most donors
+        let tied = || {
+            let mut events = vec![
+                EventBuilder::new(0).created_at(300).voters(&["a.testnet"]).build(),
+                EventBuilder::new(1).created_at(100).voters(&["a.testnet"]).build(),
+                EventBuilder::new(2).created_at(200).voters(&["a.testnet"]).build(),
+            ];
+            events[0].donors = vec!["d.testnet".to_string()];
+            events[2].donors = vec!["d.testnet".to_string(), "e.testnet".to_string()];
+            events
+        };
 
-        // Get the total votes for the first event
-        let result = contract.get_total_votes(0);
+        // Pick a single winner out of the tie with each strategy
+        let pick = |tie_break: TieBreak| {
+            let mut contract = Contract::new(alice.clone());
+            contract.events = tied();
+            contract.set_tie_break(tie_break);
+            let approved = contract.finalize_round(1);
+            assert_eq!(contract.get_round(1).unwrap().tie_break, tie_break);
+            approved
+        };
+        assert_eq!(pick(TieBreak::LowestId), vec![0]);
+        assert_eq!(pick(TieBreak::EarliestCreated), vec![1]);
+        assert_eq!(pick(TieBreak::MostUniqueDonors), vec![2]);
 
-        // Assert that the total votes for the first event is equal to 1
-        assert_eq!(result, 1);
+        // Assert that the random pick follows the seeded draw and is the same for the same seed
+        let draw = |id: i32| env::sha256(&[&[7u8; 32][..], &id.to_le_bytes()].concat());
+        let expected = (0..3).min_by_key(|&id| draw(id)).unwrap() as u64;
+        assert_eq!(pick(TieBreak::Random), vec![expected]);
+        assert_eq!(pick(TieBreak::Random), vec![expected]);
+
+        // Assert that more votes still beat any tie-break
+        let mut contract = Contract::new(alice);
+        contract.events = tied();
+        contract.events[0].total_votes = 2;
+        contract.set_tie_break(TieBreak::EarliestCreated);
+        assert_eq!(contract.finalize_round(1), vec![0]);
+    }
+
+    // Unit test to flag no event while every creator is a valid account ID
+    #[test]
+    fn events_with_invalid_creator() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract with creators of several valid shapes
+        let mut contract = Contract::new(alice);
+        contract.events.push(EventBuilder::new(0).creator("alice.testnet").build());
+        contract.events.push(EventBuilder::new(1).creator("a-b_c.near").build());
+        contract.events.push(EventBuilder::new(2).creator(&"0".repeat(64)).build());
+
+        // Assert that none is flagged, and that the strings it rejects would be
+        assert!(contract.events_with_invalid_creator().is_empty());
+        assert!(AccountId::validate("Bob.Testnet").is_err());
+        assert!(AccountId::validate("bob..testnet").is_err());
+    }
+
+    // Unit test for the live round turnout, counting each voter once across events
+    #[test]
+    fn round_turnout_counts_unique_voters() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract where Bob voted on both events, once in a legacy form
+        let mut contract = Contract::new(alice);
+        contract.set_round_minimums(3, 4);
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet", "carol.testnet"]).build());
+        contract.events.push(EventBuilder::new(1).voters(&["Bob.Testnet"]).build());
+
+        // Assert that Bob counts once towards the turnout but twice towards the votes
+        assert_eq!(
+            contract.get_round_turnout(),
+            RoundTurnout {
+                round: 1,
+                unique_voters: 2,
+                total_votes: 3,
+                min_round_turnout: 3,
+                min_round_votes: 4,
+            }
+        );
+
+        // Events approved in an earlier round no longer count
+        contract.events[1].approved_in_round = Some(1);
+        assert_eq!(contract.get_round_turnout().unique_voters, 2);
+        assert_eq!(contract.get_round_turnout().total_votes, 2);
+    }
+
+    // Unit test to reject finalizing a round below the minimum turnout
+    #[test]
+    #[should_panic(expected = "Round needs 3 unique voters and 1 votes, it has 2 and 2")]
+    fn finalize_round_below_turnout() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract requiring three voters, with only two
+        let mut contract = Contract::new(alice);
+        contract.set_round_minimums(3, 1);
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet", "carol.testnet"]).build());
+        contract.finalize_round(1);
+    }
+
+    // Unit test for the owner forcing a round through below the minimum turnout
+    #[test]
+    fn finalize_round_force_records_reason() {
+        // Set the account ID for testing as Alice
+        let alice: AccountId = "alice.testnet".parse().unwrap();
+        testing_env!(get_context(alice.clone()).build());
+
+        // Create a new instance of the contract requiring more turnout than there is
+        let mut contract = Contract::new(alice);
+        contract.set_round_minimums(10, 10);
+        contract.events.push(EventBuilder::new(0).voters(&["bob.testnet"]).build());
+
+        // Force the round and assert that it was finalized with the reason on record
+        let reason = "Holiday week, low turnout expected".to_string();
+        assert_eq!(contract.finalize_round_force(1, reason.clone()), vec![0]);
+        assert_eq!(contract.get_round(1).unwrap().forced_reason, Some(reason));
+        assert_eq!(contract.get_round_turnout().round, 2);
+    }
+
+    // Public call a random sequence is built from; indexes pick among Alice, Bob, Carol and the events
+    #[derive(Clone, Debug)]
+    enum Op {
+        Create(usize),
+        Vote(usize, usize),
+        Retract(usize, usize),
+        Close(usize),
+        Delete(usize),
+    }
+
+    // Strategy generating one call, with event indexes that may point past the last event
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..3usize).prop_map(Op::Create),
+            (0..3usize, 0..4usize).prop_map(|(voter, id)| Op::Vote(voter, id)),
+            (0..3usize, 0..4usize).prop_map(|(voter, id)| Op::Retract(voter, id)),
+            (0..4usize).prop_map(Op::Close),
+            (0..4usize).prop_map(Op::Delete),
+        ]
+    }
+
+    // Check what no sequence of calls may break. `frozen` holds the voters of each event that
+    // stopped taking votes, as they were when it stopped.
+    fn check_core_invariants(
+        contract: &Contract,
+        frozen: &HashMap<usize, Vec<String>>,
+    ) -> Result<(), TestCaseError> {
+        for (index, event) in contract.events.iter().enumerate() {
+            // IDs are positions in the list, so they are never reused
+            prop_assert_eq!(event.id as usize, index);
+
+            // Counters match the voter list, which holds each account once
+            prop_assert_eq!(event.total_votes as usize, event.votes.len());
+            prop_assert_eq!(event.weighted_total, event.votes.len() as u64);
+            let unique: HashSet<&String> = event.votes.iter().collect();
+            prop_assert_eq!(unique.len(), event.votes.len());
+
+            // Every vote has its cast time indexed and a receipt in the Merkle tree
+            for voter in event.votes.iter() {
+                let key = (index as u64, account(voter));
+                let indexed = contract.voted_at.get(&key).is_some();
+                prop_assert!(indexed, "{} has no vote time", voter);
+            }
+            prop_assert!(event.vote_leaves >= event.votes.len() as u64);
+            if event.vote_leaves > 0 {
+                let last = (index as u64, event.vote_leaves - 1);
+                prop_assert!(contract.vote_receipts.get(&last).is_some());
+            }
+
+            // Events that stopped taking votes keep exactly the voters they had
+            if let Some(voters) = frozen.get(&index) {
+                prop_assert_eq!(&event.votes, voters);
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        // Property test running random sequences of create, vote, retract, close and delete calls
+        #[test]
+        fn voting_core_invariants(ops in proptest::collection::vec(op(), 1..40)) {
+            // Alice owns the contract and Bob and Carol may create events too
+            let accounts = [ALICE, BOB, CAROL];
+            let mut contract = Scenario::new().contract();
+            contract.add_creator(account(BOB));
+            contract.add_creator(account(CAROL));
+            let mut now = 0;
+            let mut frozen: HashMap<usize, Vec<String>> = HashMap::new();
+
+            for op in ops {
+                match op {
+                    // Add and publish an event, which must always get the next ID
+                    Op::Create(creator) => {
+                        ctx(accounts[creator], 0, now);
+                        let id = contract.events.len();
+                        let title = format!("Event {}", id);
+                        contract.add_event(title, 100, "Fuzzed".to_string(), None, None, None, None);
+                        contract.publish_event(id);
+                    }
+
+                    // Vote through try_add_vote, which must accept exactly the new votes on open events
+                    Op::Vote(voter, id) => {
+                        ctx(accounts[voter], 0, now);
+                        let voter = accounts[voter].to_string();
+                        let expected = contract.events.get(id).is_some_and(|event| {
+                            !frozen.contains_key(&id) && !event.votes.contains(&voter)
+                        });
+                        prop_assert_eq!(contract.try_add_vote(id).is_ok(), expected);
+                    }
+
+                    // Retract a vote on an open event, which must drop its index entry too
+                    Op::Retract(voter, id) => {
+                        let name = accounts[voter].to_string();
+                        let voted = contract
+                            .events
+                            .get(id)
+                            .is_some_and(|event| event.votes.contains(&name));
+                        if voted && !frozen.contains_key(&id) {
+                            ctx(accounts[voter], 0, now);
+                            contract.remove_vote(id);
+                            let key = (id as u64, account(accounts[voter]));
+                            prop_assert!(contract.voted_at.get(&key).is_none());
+                        }
+                    }
+
+                    // Close voting on an open event by having its creator set a deadline that then passes
+                    Op::Close(id) => {
+                        if id < contract.events.len() && !frozen.contains_key(&id) {
+                            let creator = contract.events[id].creator.clone();
+                            ctx(creator.as_str(), 0, now);
+                            contract.set_voting_deadline(id, now + 1);
+                            now += 1;
+                            frozen.insert(id, contract.events[id].votes.clone());
+                        }
+                    }
+
+                    // Delete a visible event as the owner
+                    Op::Delete(id) => {
+                        if contract.events.get(id).is_some_and(|event| event.is_visible()) {
+                            ctx(ALICE, 0, now);
+                            contract.delete_event(id);
+                            let voters = contract.events[id].votes.clone();
+                            frozen.entry(id).or_insert(voters);
+                        }
+                    }
+                }
+                check_core_invariants(&contract, &frozen)?;
+            }
+        }
     }
 }
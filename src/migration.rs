@@ -0,0 +1,198 @@
+// Import necessary items from the near_sdk crate
+use near_sdk::borsh::BorshDeserialize;
+use near_sdk::{near, AccountId, Timestamp};
+
+use crate::{normalize_voter, Config, Contract, Event};
+
+/* Frozen copies of the state layouts migrate() can read. Never edit one: when a change to the
+stored layout needs a migration, bump STATE_VERSION and add the layout the old code wrote here,
+converting it field by field. */
+
+// Add serialization format for Borsh
+#[near(serializers=[borsh])]
+// Define the struct BaselineContract, the contract state the first deployed version wrote, before versioning
+pub(crate) struct BaselineContract {
+    owner: AccountId,
+    events: Vec<BaselineEvent>,
+}
+
+// Add serialization format for Borsh
+#[near(serializers=[borsh])]
+// Define the struct BaselineEvent, an event as the first deployed version stored it
+pub(crate) struct BaselineEvent {
+    id: i32,
+    creator: AccountId,
+    created_at: Timestamp,
+    title: String,
+    estimated_budget: u128,
+    total_votes: i64,
+    description: String,
+    votes: Vec<String>,
+}
+
+// Convert state written before the current layout
+pub(crate) fn convert_legacy_state(bytes: &[u8]) -> Contract {
+    BaselineContract::try_from_slice(bytes)
+        .expect("Cannot read the stored state")
+        .into_current()
+}
+
+// Implement methods for the BaselineContract struct
+impl BaselineContract {
+    // Convert to the current layout, taking the default settings and starting every added record empty.
+    // The title and vote indexes and the running totals are rebuilt from the events.
+    fn into_current(self) -> Contract {
+        let mut contract = Contract::with_config(self.owner, Config::default());
+        contract.events = self.events.into_iter().map(BaselineEvent::into_current).collect();
+        let votes = contract.events.iter().map(|event| event.total_votes.max(0) as u64);
+        contract.total_votes_cast = votes.sum();
+
+        // Index every title and vote, in event order
+        let indexed: Vec<(u64, String, Vec<AccountId>)> = contract
+            .events
+            .iter()
+            .map(|event| {
+                let voters = event.votes.iter().filter_map(|v| normalize_voter(v));
+                (event.id as u64, event.title.clone(), voters.collect())
+            })
+            .collect();
+        for (id, title, voters) in indexed {
+            contract.index_title(id, &title);
+            for voter in voters {
+                contract.index_vote(&voter, id);
+            }
+        }
+        contract
+    }
+}
+
+// Implement methods for the BaselineEvent struct
+impl BaselineEvent {
+    // Convert to the current layout: the event is published, open since its creation and counts each vote once.
+    // Votes cast before receipts existed have no Merkle leaves.
+    fn into_current(self) -> Event {
+        let mut event = Event::new(self.id, self.title, self.estimated_budget, self.description);
+        event.creator = self.creator;
+        event.created_at = self.created_at;
+        event.votes_open_at = self.created_at;
+        event.budget_history = vec![(self.created_at, self.estimated_budget)];
+        event.total_votes = self.total_votes;
+        event.weighted_total = self.total_votes.max(0) as u64;
+        event.votes = self.votes;
+        event.draft = false;
+        event
+    }
+}
+
+/* The rest of this file holds the inline tests for migrate. */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{account, ctx, ALICE, BOB, CAROL};
+    use crate::{EventStatus, FundingCap, STATE_VERSION};
+    use near_sdk::env;
+
+    // Event as the first deployed version stored it, created by Alice at time 5 with the given voters
+    fn baseline_event(id: i32, title: &str, voters: &[&str]) -> BaselineEvent {
+        BaselineEvent {
+            id,
+            creator: account(ALICE),
+            created_at: 5,
+            title: title.to_string(),
+            estimated_budget: 100,
+            total_votes: voters.len() as i64,
+            description: "Description".to_string(),
+            votes: voters.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    // Store the state the first deployed version would have written for Alice's events
+    fn store_baseline(events: Vec<BaselineEvent>) {
+        let state = BaselineContract {
+            owner: account(ALICE),
+            events,
+        };
+        env::storage_write(b"STATE", &near_sdk::borsh::to_vec(&state).unwrap());
+    }
+
+    // Unit test to migrate baseline state, keeping its events and rebuilding the indexes and totals
+    #[test]
+    fn migrate_reads_baseline_state() {
+        ctx(ALICE, 0, 100);
+        let art = baseline_event(0, "Art Show", &[BOB, CAROL]);
+        let book = baseline_event(1, "Book Fair", &[BOB]);
+        store_baseline(vec![art, book]);
+        let contract = Contract::migrate();
+
+        // Assert that the owner, events and votes were carried over and the events stay published
+        assert_eq!(contract.state_version(), STATE_VERSION);
+        assert_eq!(contract.owner, account(ALICE));
+        assert_eq!(contract.list_events().len(), 2);
+        let art = &contract.events[0];
+        assert_eq!(art.title, "Art Show");
+        assert_eq!(art.creator, account(ALICE));
+        assert_eq!(art.created_at, 5);
+        assert_eq!(art.votes, [BOB, CAROL]);
+        assert_eq!(art.weighted_total, 2);
+        assert_eq!(art.budget_history, vec![(5, 100)]);
+
+        // Assert that titles and votes are indexed and the running totals count every vote
+        let book = contract.autocomplete_titles("book".to_string(), 10);
+        assert_eq!(book, vec![(1, "Book Fair".to_string())]);
+        assert_eq!(contract.account_votes.get(&account(BOB)), Some(vec![0, 1]));
+        assert_eq!(contract.account_votes.get(&account(CAROL)), Some(vec![0]));
+        assert_eq!(contract.get_checksum().total_votes_cast, 3);
+        assert_eq!(contract.get_checksum().total_raised.0, 0);
+    }
+
+    // Unit test that every field added since the baseline starts at its default after migrating
+    #[test]
+    fn migrate_fills_added_fields_with_defaults() {
+        ctx(ALICE, 0, 100);
+        store_baseline(vec![baseline_event(0, "Art Show", &[BOB])]);
+        let contract = Contract::migrate();
+
+        // Assert that the event fields added since take the values of a new, published event
+        let event = &contract.events[0];
+        assert_eq!(event.status, EventStatus::Open);
+        assert!(!event.draft && !event.deadline_extended && !event.funded);
+        assert_eq!(event.voting_deadline, None);
+        assert_eq!(event.funding_cap, FundingCap::Unlimited);
+        assert_eq!(event.certification, None);
+        assert_eq!(event.vote_leaves, 0);
+
+        // Assert that the settings and contract fields added since take their defaults
+        assert_eq!(contract.get_config(), Config::default());
+        assert_eq!(contract.auditor, None);
+        assert_eq!(contract.pending_owner, None);
+        assert!(contract.creator_defaults.get(&account(ALICE)).is_none());
+        assert_eq!(contract.change_seq, 0);
+    }
+
+    // Unit test to migrate baseline state once, then refuse to migrate it again
+    #[test]
+    #[should_panic(expected = "Contract state is already at version 1")]
+    fn migrate_refuses_to_run_twice() {
+        ctx(ALICE, 0, 100);
+        store_baseline(vec![baseline_event(0, "Art Show", &[])]);
+
+        // Assert that the first migration keeps the events and records the version
+        let contract = Contract::migrate();
+        assert_eq!(contract.state_version(), STATE_VERSION);
+        assert_eq!(contract.list_events().len(), 1);
+
+        // Migrate the stored result again
+        env::state_write(&contract);
+        Contract::migrate();
+    }
+
+    // Unit test to refuse state that is neither the current nor the baseline layout
+    #[test]
+    #[should_panic(expected = "Cannot read the stored state")]
+    fn migrate_rejects_unknown_state() {
+        ctx(ALICE, 0, 100);
+        env::storage_write(b"STATE", &[1, 2, 3]);
+        Contract::migrate();
+    }
+}
@@ -0,0 +1,212 @@
+// Import necessary items from the near_sdk crate
+use near_sdk::json_types::U128;
+use near_sdk::{env, near};
+
+/*
+ * Compile-time defaults and hard bounds of every owner-tunable number in Config. The owner may move a
+ * setting anywhere within its bounds, but never outside them, so a typo cannot set e.g. a year-long cooldown.
+ */
+
+// Number of seconds in a day, used to express time-based defaults and bounds
+pub const SECONDS_PER_DAY: u64 = 86_400;
+const NANOS_PER_DAY: u64 = SECONDS_PER_DAY * 1_000_000_000;
+
+// One NEAR in yoctoNEAR
+const ONE_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+
+// Smallest estimated budget accepted by default, so percent-funded math never divides by zero
+pub const DEFAULT_MIN_BUDGET: u128 = 1;
+// Largest estimated budget accepted by default (one million NEAR in yoctoNEAR)
+pub const DEFAULT_MAX_BUDGET: u128 = 1_000_000 * ONE_NEAR;
+
+// Time a deleted event can be restored for by default, before it may be purged
+pub const DEFAULT_RESTORE_WINDOW_SECS: u64 = 30 * SECONDS_PER_DAY;
+
+// Number of logged state changes between two heartbeat logs by default
+pub const DEFAULT_HEARTBEAT_INTERVAL: u64 = 100;
+
+// Furthest ahead a voting deadline may be set by default
+pub const DEFAULT_MAX_VOTING_PERIOD_SECS: u64 = 90 * SECONDS_PER_DAY;
+
+// Furthest a creator may push back a voting deadline with extend_deadline by default
+pub const DEFAULT_MAX_DEADLINE_EXTENSION_SECS: u64 = 14 * SECONDS_PER_DAY;
+
+// Number of most recent voters returned inline by get_event by default
+pub const DEFAULT_MAX_INLINE_VOTERS: u32 = 50;
+
+// Most links a description may contain by default
+pub const DEFAULT_MAX_LINKS_IN_DESCRIPTION: u8 = 3;
+
+// Define the struct Param, the name, default and inclusive bounds of one owner-tunable setting
+pub struct Param {
+    pub name: &'static str,         // Name of the Config field
+    pub default: Option<u128>,      // Value in Config::default, or None when the setting is off by default
+    pub min: u128,                  // Smallest accepted value
+    pub max: u128,                  // Largest accepted value
+}
+
+// Implement methods for the Param struct
+impl Param {
+    // Panic, naming the setting, unless the value lies within its bounds
+    pub fn check(&self, value: impl Into<u128>) {
+        let value = value.into();
+        if value < self.min || value > self.max {
+            env::panic_str(&format!(
+                "{} must be between {} and {}",
+                self.name, self.min, self.max
+            ));
+        }
+    }
+
+    // Describe the setting for get_param_bounds
+    pub fn bound(&self) -> ParamBound {
+        ParamBound {
+            name: self.name.to_string(),
+            default: self.default.map(U128),
+            min: U128(self.min),
+            max: U128(self.max),
+        }
+    }
+}
+
+pub const MIN_BUDGET: Param = Param {
+    name: "min_budget",
+    default: Some(DEFAULT_MIN_BUDGET),
+    min: 1,
+    max: DEFAULT_MAX_BUDGET,
+};
+pub const MAX_BUDGET: Param = Param {
+    name: "max_budget",
+    default: Some(DEFAULT_MAX_BUDGET),
+    min: 1,
+    max: DEFAULT_MAX_BUDGET,
+};
+pub const MAX_INLINE_VOTERS: Param = Param {
+    name: "max_inline_voters",
+    default: Some(DEFAULT_MAX_INLINE_VOTERS as u128),
+    min: 1,
+    max: 1_000,
+};
+pub const CREATION_COOLDOWN_SECS: Param = Param {
+    name: "creation_cooldown_secs",
+    default: Some(0),
+    min: 0,
+    max: 7 * SECONDS_PER_DAY as u128,
+};
+pub const LOYALTY_MULTIPLIER: Param = Param {
+    name: "loyalty_multiplier",
+    default: None,
+    min: 1,
+    max: 10,
+};
+pub const MAX_LINKS_IN_DESCRIPTION: Param = Param {
+    name: "max_links_in_description",
+    default: Some(DEFAULT_MAX_LINKS_IN_DESCRIPTION as u128),
+    min: 0,
+    max: 20,
+};
+pub const RESTORE_WINDOW_SECS: Param = Param {
+    name: "restore_window_secs",
+    default: Some(DEFAULT_RESTORE_WINDOW_SECS as u128),
+    min: 0,
+    max: 90 * SECONDS_PER_DAY as u128,
+};
+pub const VOTE_FEE: Param = Param {
+    name: "vote_fee",
+    default: Some(0),
+    min: 0,
+    max: 10 * ONE_NEAR,
+};
+pub const CREATION_FEE: Param = Param {
+    name: "creation_fee",
+    default: Some(0),
+    min: 0,
+    max: 10 * ONE_NEAR,
+};
+pub const VOTE_GRACE_PERIOD_NS: Param = Param {
+    name: "vote_grace_period_ns",
+    default: Some(0),
+    min: 0,
+    max: 7 * NANOS_PER_DAY as u128,
+};
+pub const GRACE_EXEMPT_MIN_AGE_SECS: Param = Param {
+    name: "grace_exempt_min_age_secs",
+    default: None,
+    min: 0,
+    max: 365 * SECONDS_PER_DAY as u128,
+};
+pub const DEADLINE_GRACE_PERIOD_NS: Param = Param {
+    name: "deadline_grace_period_ns",
+    default: Some(0),
+    min: 0,
+    max: NANOS_PER_DAY as u128,
+};
+pub const MIN_ROUND_TURNOUT: Param = Param {
+    name: "min_round_turnout",
+    default: Some(0),
+    min: 0,
+    max: 1_000_000,
+};
+pub const MIN_ROUND_VOTES: Param = Param {
+    name: "min_round_votes",
+    default: Some(0),
+    min: 0,
+    max: 1_000_000,
+};
+pub const MAX_OPEN_EVENTS: Param = Param {
+    name: "max_open_events",
+    default: None,
+    min: 1,
+    max: 100_000,
+};
+pub const MAX_DEADLINE_EXTENSION_SECS: Param = Param {
+    name: "max_deadline_extension_secs",
+    default: Some(DEFAULT_MAX_DEADLINE_EXTENSION_SECS as u128),
+    min: 0,
+    max: 90 * SECONDS_PER_DAY as u128,
+};
+pub const HEARTBEAT_INTERVAL: Param = Param {
+    name: "heartbeat_interval",
+    default: Some(DEFAULT_HEARTBEAT_INTERVAL as u128),
+    min: 0,
+    max: 1_000_000,
+};
+pub const MAX_VOTING_PERIOD_SECS: Param = Param {
+    name: "max_voting_period_secs",
+    default: Some(DEFAULT_MAX_VOTING_PERIOD_SECS as u128),
+    min: 3_600,
+    max: 365 * SECONDS_PER_DAY as u128,
+};
+
+// Every bounded setting, in the order get_param_bounds reports them
+pub const ALL: [&Param; 18] = [
+    &MIN_BUDGET,
+    &MAX_BUDGET,
+    &MAX_INLINE_VOTERS,
+    &CREATION_COOLDOWN_SECS,
+    &LOYALTY_MULTIPLIER,
+    &MAX_LINKS_IN_DESCRIPTION,
+    &RESTORE_WINDOW_SECS,
+    &VOTE_FEE,
+    &VOTE_GRACE_PERIOD_NS,
+    &GRACE_EXEMPT_MIN_AGE_SECS,
+    &DEADLINE_GRACE_PERIOD_NS,
+    &MIN_ROUND_TURNOUT,
+    &MIN_ROUND_VOTES,
+    &MAX_OPEN_EVENTS,
+    &MAX_DEADLINE_EXTENSION_SECS,
+    &HEARTBEAT_INTERVAL,
+    &MAX_VOTING_PERIOD_SECS,
+    &CREATION_FEE,
+];
+
+// Add serialization format for JSON, and derive Debug and PartialEq traits for the struct
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+// Define the struct ParamBound returned by get_param_bounds, one per bounded setting
+pub struct ParamBound {
+    pub name: String,               // Name of the Config field
+    pub default: Option<U128>,      // Value in Config::default, or None when the setting is off by default
+    pub min: U128,                  // Smallest accepted value
+    pub max: U128,                  // Largest accepted value
+}
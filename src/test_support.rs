@@ -0,0 +1,114 @@
+// Import necessary items from the near_sdk crate
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, AccountId, NearToken, Timestamp};
+
+use crate::Contract;
+
+// Named accounts shared by the tests; Alice owns every contract the helpers create
+pub const ALICE: &str = "alice.testnet";
+pub const BOB: &str = "bob.testnet";
+pub const CAROL: &str = "carol.testnet";
+
+// Parse a test account ID
+pub fn account(name: &str) -> AccountId {
+    name.parse().unwrap()
+}
+
+// Set up the unit test environment with the given caller, attached deposit and block time
+pub fn ctx(predecessor: &str, deposit: u128, timestamp: Timestamp) {
+    let mut builder = VMContextBuilder::new();
+    builder
+        .predecessor_account_id(account(predecessor))
+        .attached_deposit(NearToken::from_yoctonear(deposit))
+        .block_timestamp(timestamp);
+    testing_env!(builder.build());
+}
+
+// Create a contract owned by Alice with `n` published events of budget 100 by her, with IDs 0 to n - 1
+pub fn contract_with_events(n: usize) -> Contract {
+    Scenario::new().events(n).contract()
+}
+
+// Builder chaining contract calls made by named accounts at a controlled block time.
+// Every step panics like the contract method it calls; finish with contract() to make assertions.
+pub struct Scenario {
+    contract: Contract,
+    now: Timestamp,
+}
+
+// Implement methods for the Scenario struct
+impl Scenario {
+    // Start at time 0 with a fresh contract owned by Alice
+    pub fn new() -> Self {
+        ctx(ALICE, 0, 0);
+        Scenario {
+            contract: Contract::new(account(ALICE)),
+            now: 0,
+        }
+    }
+
+    // Add `n` published events of budget 100 by Alice
+    pub fn events(mut self, n: usize) -> Self {
+        for i in 0..n {
+            self = self.create(ALICE, &format!("Event {}", i), 100, None);
+        }
+        self
+    }
+
+    // Add and publish an event by `creator`, whitelisting them first unless they are Alice
+    pub fn create(
+        mut self,
+        creator: &str,
+        title: &str,
+        budget: u128,
+        min_votes_for_funding: Option<u64>,
+    ) -> Self {
+        if creator != ALICE {
+            ctx(ALICE, 0, self.now);
+            self.contract.add_creator(account(creator));
+        }
+        ctx(creator, 0, self.now);
+        let id = self.contract.events.len();
+        let description = "Scenario event".to_string();
+        let title = title.to_string();
+        self.contract
+            .add_event(title, budget, description, None, min_votes_for_funding, None, None);
+        self.contract.publish_event(id);
+        self
+    }
+
+    // Cast `voter`'s vote for the event
+    pub fn vote(mut self, voter: &str, id: usize) -> Self {
+        ctx(voter, 0, self.now);
+        self.contract.add_vote(id, None);
+        self
+    }
+
+    // Donate `amount` yoctoNEAR to the event as `donor`
+    pub fn donate(mut self, donor: &str, id: usize, amount: u128) -> Self {
+        ctx(donor, amount, self.now);
+        self.contract.donate(id);
+        self
+    }
+
+    // Close voting on the event: its creator sets the deadline one nanosecond ahead, then time passes it
+    pub fn close(mut self, id: usize) -> Self {
+        let creator = self.contract.events[id].creator.clone();
+        ctx(creator.as_str(), 0, self.now);
+        self.contract.set_voting_deadline(id, self.now + 1);
+        self.advance(1)
+    }
+
+    // Move the block time forward
+    pub fn advance(mut self, nanos: u64) -> Self {
+        self.now += nanos;
+        ctx(ALICE, 0, self.now);
+        self
+    }
+
+    // Finish the scenario, leaving Alice as the caller at the current time
+    pub fn contract(self) -> Contract {
+        ctx(ALICE, 0, self.now);
+        self.contract
+    }
+}
@@ -0,0 +1,19 @@
+// Gas budgets, in TGas, that each operation measured by the gas report must stay under.
+// Raise a budget only on purpose, in the same change that makes the operation more expensive.
+
+// add_event with a short title and description, matching its suggested gas
+pub const ADD_EVENT: u64 = 10;
+
+// add_vote on an event with 100 and 1,000 earlier voters. With none it must stay under the
+// gas_budget get_vote_requirements reports.
+pub const ADD_VOTE_100_VOTERS: u64 = 15;
+pub const ADD_VOTE_1000_VOTERS: u64 = 40;
+
+// One page of 100 voters from get_voters on an event with 1,000 voters
+pub const GET_VOTERS_PAGE: u64 = 40;
+
+// list_events with 50 published events
+pub const LIST_EVENTS_50: u64 = 60;
+
+// finalize_round over 50 events
+pub const FINALIZE_ROUND_50: u64 = 150;
@@ -0,0 +1,209 @@
+/*
+ * Gas report against the compiled wasm in a local sandbox, printing the TGas each operation burns and
+ * failing when one goes over its budget in budgets.rs. It seeds over a thousand accounts, so it only
+ * builds with the gas-report feature:
+ *
+ *     cargo test --features gas-report --test gas_report -- --nocapture
+ */
+#![cfg(feature = "gas-report")]
+
+mod budgets;
+
+use near_workspaces::network::Sandbox;
+use near_workspaces::result::ExecutionFinalResult;
+use near_workspaces::types::NearToken;
+use near_workspaces::{Account, Contract, Worker};
+use serde_json::{json, Value};
+use tokio::task::JoinSet;
+
+type TestResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+// Votes sent at once while seeding, each from its own account
+const SEED_CONCURRENCY: usize = 50;
+
+// Deploy a freshly compiled contract owned by Alice, returning the sandbox, the contract and Alice
+async fn setup() -> TestResult<(Worker<Sandbox>, Contract, Account)> {
+    let sandbox = near_workspaces::sandbox().await?;
+    let wasm = near_workspaces::compile_project("./").await?;
+    let contract = sandbox.dev_deploy(&wasm).await?;
+    let alice = sandbox.dev_create_account().await?;
+
+    let outcome = contract
+        .call("new")
+        .args_json(json!({ "owner": alice.id() }))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+    Ok((sandbox, contract, alice))
+}
+
+// Print the gas an operation burned and assert it succeeded within its budget
+fn report(name: &str, outcome: &ExecutionFinalResult, budget_tgas: u64) {
+    assert!(outcome.is_success(), "{} failed: {:?}", name, outcome);
+    let burnt = outcome.total_gas_burnt.as_gas();
+    println!(
+        "{:<24} {:>8.3} TGas  (budget {} TGas)",
+        name,
+        burnt as f64 / 1e12,
+        budget_tgas
+    );
+    assert!(
+        burnt <= budget_tgas * 1_000_000_000_000,
+        "{} burned {} gas, over its budget of {} TGas",
+        name,
+        burnt,
+        budget_tgas
+    );
+}
+
+// Read the gas add_vote must stay under from get_vote_requirements, in TGas
+async fn vote_gas_budget(contract: &Contract) -> TestResult<u64> {
+    let requirements: Value = contract
+        .view("get_vote_requirements")
+        .args_json(json!({}))
+        .await?
+        .json()?;
+    let budget = &requirements["gas_budget"];
+    let gas = match budget.as_str() {
+        Some(gas) => gas.parse()?,
+        None => budget.as_u64().ok_or("gas_budget is not a number")?,
+    };
+    Ok(gas / 1_000_000_000_000)
+}
+
+// Add and publish event `id` as Alice, returning the outcome of add_event alone
+async fn add_event(
+    contract: &Contract,
+    alice: &Account,
+    id: u64,
+) -> TestResult<ExecutionFinalResult> {
+    let outcome = alice
+        .call(contract.id(), "add_event")
+        .args_json(json!({
+            "title": format!("Event {}", id),
+            "estimated_budget": 200,
+            "description": "Gas report event",
+            "proposal_url": null,
+            "min_votes_for_funding": null,
+            "template_id": null,
+            "invite_code": null,
+        }))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+
+    let published = alice
+        .call(contract.id(), "publish_event")
+        .args_json(json!({ "id": id }))
+        .transact()
+        .await?;
+    assert!(published.is_success(), "{:?}", published);
+    Ok(outcome)
+}
+
+// Create `count` voter accounts under the sandbox root account
+async fn create_voters(sandbox: &Worker<Sandbox>, count: usize) -> TestResult<Vec<Account>> {
+    let root = sandbox.root_account()?;
+    let mut voters = Vec::with_capacity(count);
+    for i in 0..count {
+        let voter = root
+            .create_subaccount(&format!("voter{}", i))
+            .initial_balance(NearToken::from_near(1))
+            .transact()
+            .await?
+            .into_result()?;
+        voters.push(voter);
+    }
+    Ok(voters)
+}
+
+// Cast a vote from every account for the event, SEED_CONCURRENCY at a time
+async fn seed_votes(contract: &Contract, voters: &[Account], id: u64) -> TestResult<()> {
+    for chunk in voters.chunks(SEED_CONCURRENCY) {
+        let mut votes = JoinSet::new();
+        for voter in chunk {
+            let voter = voter.clone();
+            let contract_id = contract.id().clone();
+            votes.spawn(async move {
+                voter
+                    .call(&contract_id, "add_vote")
+                    .args_json(json!({ "id": id, "answers": null }))
+                    .transact()
+                    .await
+            });
+        }
+        while let Some(outcome) = votes.join_next().await {
+            let outcome = outcome??;
+            assert!(outcome.is_success(), "{:?}", outcome);
+        }
+    }
+    Ok(())
+}
+
+// Gas report for adding an event, voting as the number of earlier voters grows, and paging voters
+#[tokio::test]
+async fn gas_add_event_and_votes() -> TestResult<()> {
+    let (sandbox, contract, alice) = setup().await?;
+
+    // add_event on an empty contract
+    let outcome = add_event(&contract, &alice, 0).await?;
+    report("add_event", &outcome, budgets::ADD_EVENT);
+    add_event(&contract, &alice, 1).await?;
+    add_event(&contract, &alice, 2).await?;
+
+    // The whole event list is loaded by every call, so measure each vote before seeding the next event
+    let prober = sandbox.dev_create_account().await?;
+    let vote = |id: u64| {
+        prober
+            .call(contract.id(), "add_vote")
+            .args_json(json!({ "id": id, "answers": null }))
+            .max_gas()
+            .transact()
+    };
+    let budget = vote_gas_budget(&contract).await?;
+    report("add_vote, 0 voters", &vote(0).await?, budget);
+
+    let voters = create_voters(&sandbox, 1000).await?;
+    seed_votes(&contract, &voters[..100], 1).await?;
+    report("add_vote, 100 voters", &vote(1).await?, budgets::ADD_VOTE_100_VOTERS);
+
+    seed_votes(&contract, &voters, 2).await?;
+    report("add_vote, 1000 voters", &vote(2).await?, budgets::ADD_VOTE_1000_VOTERS);
+
+    // Views burn no gas when queried, so page through the voters in a transaction to measure it
+    let outcome = alice
+        .call(contract.id(), "get_voters")
+        .args_json(json!({ "id": 2, "from_index": 500, "limit": 100 }))
+        .max_gas()
+        .transact()
+        .await?;
+    report("get_voters, 100 of 1000", &outcome, budgets::GET_VOTERS_PAGE);
+    Ok(())
+}
+
+// Gas report for listing and finalizing a round over 50 events
+#[tokio::test]
+async fn gas_list_and_finalize_round() -> TestResult<()> {
+    let (_sandbox, contract, alice) = setup().await?;
+    for id in 0..50 {
+        add_event(&contract, &alice, id).await?;
+    }
+
+    // list_events has no paging, so the page is every published event
+    let outcome = alice
+        .call(contract.id(), "list_events")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?;
+    report("list_events, 50 events", &outcome, budgets::LIST_EVENTS_50);
+
+    let outcome = alice
+        .call(contract.id(), "finalize_round")
+        .args_json(json!({ "max_approvals": 10 }))
+        .max_gas()
+        .transact()
+        .await?;
+    report("finalize_round, 50", &outcome, budgets::FINALIZE_ROUND_50);
+    Ok(())
+}
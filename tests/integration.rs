@@ -0,0 +1,294 @@
+/*
+ * End-to-end tests against the compiled wasm in a local sandbox, covering what testing_env! cannot:
+ * JSON (de)serialization of arguments and views, gas, and how panics surface in execution outcomes
+ */
+use near_workspaces::types::NearToken;
+use near_workspaces::{Account, Contract};
+use serde_json::{json, Value};
+
+// Deploy a freshly compiled contract owned by Alice, returning it with Alice, Bob and Carol
+async fn setup() -> Result<(Contract, Account, Account, Account), Box<dyn std::error::Error>> {
+    setup_with("new", json!({})).await
+}
+
+// Deploy as setup does, initializing through `init` with `args` plus Alice as the owner
+async fn setup_with(
+    init: &str,
+    mut args: Value,
+) -> Result<(Contract, Account, Account, Account), Box<dyn std::error::Error>> {
+    let sandbox = near_workspaces::sandbox().await?;
+    let wasm = near_workspaces::compile_project("./").await?;
+    let contract = sandbox.dev_deploy(&wasm).await?;
+    let alice = sandbox.dev_create_account().await?;
+    let bob = sandbox.dev_create_account().await?;
+    let carol = sandbox.dev_create_account().await?;
+
+    args["owner"] = json!(alice.id());
+    let outcome = contract.call(init).args_json(args).transact().await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+    Ok((contract, alice, bob, carol))
+}
+
+// Add event `id` as `creator` attaching `deposit`, and publish it.
+// The creator must be the owner or a whitelisted creator.
+async fn add_published_event(
+    contract: &Contract,
+    creator: &Account,
+    id: u64,
+    title: &str,
+    deposit: NearToken,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let outcome = creator
+        .call(contract.id(), "add_event")
+        .args_json(json!({
+            "title": title,
+            "estimated_budget": 200,
+            "description": "Sandbox event",
+            "proposal_url": null,
+            "min_votes_for_funding": null,
+            "template_id": null,
+            "invite_code": null,
+        }))
+        .deposit(deposit)
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+
+    let outcome = creator
+        .call(contract.id(), "publish_event")
+        .args_json(json!({ "id": id }))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+    Ok(())
+}
+
+// Integration test for the full flow: create, vote from two accounts at once, list, and double vote
+#[tokio::test]
+async fn event_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, bob, carol) = setup().await?;
+
+    // The cost estimate reports no creation fee, so add_event is called without a deposit
+    let estimate: Value = contract
+        .view("estimate_creation_cost")
+        .args_json(json!({ "title_len": 8, "description_len": 13, "options": 0 }))
+        .await?
+        .json()?;
+    assert_eq!(estimate["creation_fee"], json!("0"));
+    let no_deposit = NearToken::from_yoctonear(0);
+    add_published_event(&contract, &alice, 0, "Art Show", no_deposit).await?;
+
+    // Bob and Carol vote in the same block range, each with a one yoctoNEAR deposit
+    let vote = |account: &Account| {
+        account
+            .call(contract.id(), "add_vote")
+            .args_json(json!({ "id": 0, "answers": null }))
+            .deposit(NearToken::from_yoctonear(1))
+            .transact()
+    };
+    let (bob_vote, carol_vote) = tokio::join!(vote(&bob), vote(&carol));
+    assert!(bob_vote?.is_success());
+    assert!(carol_vote?.is_success());
+
+    // The listing keeps its JSON field names, and both votes counted
+    let events: Value = contract.view("list_events").args_json(json!({})).await?.json()?;
+    let event = events[0].as_object().expect("list_events returns event objects");
+    for field in [
+        "id",
+        "creator",
+        "created_at",
+        "title",
+        "estimated_budget",
+        "total_votes",
+        "description",
+        "votes",
+        "tags",
+        "voting_deadline",
+        "status",
+        "draft",
+        "funds_raised",
+    ] {
+        assert!(event.contains_key(field), "missing field {}", field);
+    }
+    assert_eq!(event["title"], json!("Art Show"));
+    assert_eq!(event["creator"], json!(alice.id()));
+    assert_eq!(event["total_votes"], json!(2));
+    assert_eq!(event["status"], json!("Open"));
+    assert_eq!(event["draft"], json!(false));
+
+    // A second vote by Bob fails with the same message add_vote panics with in unit tests
+    let outcome = vote(&bob).await?;
+    assert!(outcome.is_failure());
+    let error = format!("{:?}", outcome.into_result().unwrap_err());
+    assert!(error.contains("You have already voted for this event"), "{}", error);
+
+    Ok(())
+}
+
+// Integration test for the error surfaced when add_event is sent more than the creation fee
+#[tokio::test]
+async fn add_event_rejects_deposit() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, _, _) = setup().await?;
+
+    // The creation fee is zero by default, so attaching a deposit fails the call before any state changes
+    let outcome = alice
+        .call(contract.id(), "add_event")
+        .args_json(json!({
+            "title": "Art Show",
+            "estimated_budget": 200,
+            "description": "Sandbox event",
+            "proposal_url": null,
+            "min_votes_for_funding": null,
+            "template_id": null,
+            "invite_code": null,
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure());
+    let error = format!("{:?}", outcome.into_result().unwrap_err());
+    assert!(
+        error.contains("Attach exactly the creation fee of 0 yoctoNEAR"),
+        "{}",
+        error
+    );
+
+    let events: Value = contract.view("list_events").args_json(json!({})).await?.json()?;
+    assert_eq!(events, json!([]));
+    Ok(())
+}
+
+// Integration test for a contract set up with a creation fee, which add_event takes when it is attached
+#[tokio::test]
+async fn add_event_with_creation_fee() -> Result<(), Box<dyn std::error::Error>> {
+    let fee = NearToken::from_millinear(10);
+    let config = json!({
+        "creation_fee": fee.as_yoctonear().to_string(),
+        "vote_fee": "0",
+        "max_events": 10,
+    });
+    let (contract, alice, _, _) = setup_with("init_with_config", config).await?;
+
+    // The cost estimate asks for exactly the creation fee, and two events are added attaching it
+    let estimate: Value = contract
+        .view("estimate_creation_cost")
+        .args_json(json!({ "title_len": 8, "description_len": 13, "options": 0 }))
+        .await?
+        .json()?;
+    let fee_json = json!(fee.as_yoctonear().to_string());
+    assert_eq!(estimate["total_deposit"], fee_json);
+    add_published_event(&contract, &alice, 0, "Art Show", fee).await?;
+    add_published_event(&contract, &alice, 1, "Book Fair", fee).await?;
+
+    // Both events are listed, each published under its own ID
+    let events: Value = contract.view("list_events").args_json(json!({})).await?.json()?;
+    assert_eq!(events[0]["title"], json!("Art Show"));
+    assert_eq!(events[1]["title"], json!("Book Fair"));
+    assert_eq!(events[1]["id"], json!(1));
+    Ok(())
+}
+
+// Integration test for the creator's own listings, which read the caller and so are called as transactions
+#[tokio::test]
+async fn creator_listings_by_call() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, bob, _) = setup().await?;
+
+    // Alice publishes one event and keeps a second one as a draft
+    let no_deposit = NearToken::from_yoctonear(0);
+    add_published_event(&contract, &alice, 0, "Art Show", no_deposit).await?;
+    let outcome = alice
+        .call(contract.id(), "add_event")
+        .args_json(json!({
+            "title": "Book Fair",
+            "estimated_budget": 200,
+            "description": "Sandbox event",
+            "proposal_url": null,
+            "min_votes_for_funding": null,
+            "template_id": null,
+            "invite_code": null,
+        }))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+
+    // A view call has no caller, so the listing is refused there
+    let view = contract.view("list_my_drafts").args_json(json!({})).await;
+    assert!(view.is_err());
+
+    // Called by Alice it returns her draft only, and Bob has none
+    let drafts: Value = alice
+        .call(contract.id(), "list_my_drafts")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(drafts.as_array().map(Vec::len), Some(1));
+    assert_eq!(drafts[0]["title"], json!("Book Fair"));
+    let drafts: Value = bob
+        .call(contract.id(), "list_my_drafts")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(drafts, json!([]));
+
+    // Alice's own events include the draft
+    let events: Value = alice
+        .call(contract.id(), "my_events")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(events[0]["title"], json!("Art Show"));
+    assert_eq!(events[1]["title"], json!("Book Fair"));
+
+    // Bob votes on the published event and his count includes it
+    let outcome = bob
+        .call(contract.id(), "add_vote")
+        .args_json(json!({ "id": 0, "answers": null }))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+    let count: u32 = bob
+        .call(contract.id(), "my_vote_count")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+// Integration test that a deposit-free vote on a new event stays under the gas budget wallets are told.
+// Unit tests cannot check this: testing_env! does not charge for wasm execution.
+#[tokio::test]
+async fn add_vote_within_gas_budget() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, bob, _) = setup().await?;
+    let no_deposit = NearToken::from_yoctonear(0);
+    add_published_event(&contract, &alice, 0, "Art Show", no_deposit).await?;
+
+    // The budget comes from get_vote_requirements, so the reported and enforced values cannot drift
+    let requirements: Value = contract
+        .view("get_vote_requirements")
+        .args_json(json!({}))
+        .await?
+        .json()?;
+    assert_eq!(requirements["deposit_required"], json!(false));
+    let budget = &requirements["gas_budget"];
+    let budget: u64 = match budget.as_str() {
+        Some(gas) => gas.parse()?,
+        None => budget.as_u64().ok_or("gas_budget is not a number")?,
+    };
+
+    // Bob votes without a deposit, and the whole transaction burns less than the budget
+    let outcome = bob
+        .call(contract.id(), "add_vote")
+        .args_json(json!({ "id": 0, "answers": null }))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:?}", outcome);
+    let burnt = outcome.total_gas_burnt.as_gas();
+    assert!(burnt < budget, "add_vote burned {burnt} of {budget} gas");
+    Ok(())
+}